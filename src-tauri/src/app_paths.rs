@@ -0,0 +1,55 @@
+//! 应用目录解析模块
+//!
+//! 统一解析配置、数据、缓存与日志目录，供更新、代理等模块复用，
+//! 避免各处直接调用 `app.path()` 导致路径规则不一致。
+
+use std::path::PathBuf;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+/// 应用目录信息
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AppPaths {
+    pub config_dir: String,
+    pub data_dir: String,
+    pub cache_dir: String,
+    pub log_dir: String,
+}
+
+/// 应用配置目录
+pub(crate) fn config_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path().app_config_dir().map_err(|err| err.to_string())
+}
+
+/// 应用数据目录（`config.json`、待安装更新记录等均位于此处）
+pub(crate) fn data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path().app_data_dir().map_err(|err| err.to_string())
+}
+
+/// 应用缓存目录（更新安装包等可再生文件）
+pub(crate) fn cache_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path().app_cache_dir().map_err(|err| err.to_string())
+}
+
+/// 应用日志目录
+pub(crate) fn log_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path().app_log_dir().map_err(|err| err.to_string())
+}
+
+/// 优先使用数据目录，无法解析时回退到缓存目录
+pub(crate) fn data_or_cache_dir(app: &AppHandle) -> Option<PathBuf> {
+    data_dir(app).or_else(|_| cache_dir(app)).ok()
+}
+
+/// 获取应用各类目录路径
+#[tauri::command]
+pub(crate) async fn get_app_paths(app: AppHandle) -> Result<AppPaths, String> {
+    Ok(AppPaths {
+        config_dir: config_dir(&app)?.to_string_lossy().into_owned(),
+        data_dir: data_dir(&app)?.to_string_lossy().into_owned(),
+        cache_dir: cache_dir(&app)?.to_string_lossy().into_owned(),
+        log_dir: log_dir(&app)?.to_string_lossy().into_owned(),
+    })
+}
@@ -0,0 +1,196 @@
+//! 划词内容类型识别模块
+//!
+//! 对选中文本做轻量的启发式分类（链接、邮箱、电话、代码），随工具栏事件一并发送，
+//! 便于工具栏优先展示“打开链接”“解释代码”等动作。识别只求廉价与大致准确，不做完整校验。
+
+use serde::Serialize;
+
+/// 代码启发式最多检查的字节数，避免超长选区拖慢工具栏显示
+const MAX_CODE_SCAN_BYTES: usize = 4096;
+/// 电话号码允许的数字个数范围（E.164 最长 15 位）
+const PHONE_DIGITS_RANGE: std::ops::RangeInclusive<usize> = 7..=15;
+
+/// 选中文本的内容类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum SelectionContentType {
+    Url,
+    Email,
+    Phone,
+    Code,
+    Text,
+}
+
+/// 识别选中文本的内容类型，无法归类时返回 `Text`
+pub(crate) fn classify_selection(text: &str) -> SelectionContentType {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return SelectionContentType::Text;
+    }
+
+    if !trimmed.contains(char::is_whitespace) {
+        if looks_like_url(trimmed) {
+            return SelectionContentType::Url;
+        }
+        if looks_like_email(trimmed) {
+            return SelectionContentType::Email;
+        }
+    }
+
+    if looks_like_phone(trimmed) {
+        return SelectionContentType::Phone;
+    }
+
+    if looks_like_code(trimmed) {
+        return SelectionContentType::Code;
+    }
+
+    SelectionContentType::Text
+}
+
+fn looks_like_url(token: &str) -> bool {
+    let lower = token.to_ascii_lowercase();
+    let rest = ["http://", "https://", "ftp://"]
+        .iter()
+        .find_map(|scheme| lower.strip_prefix(scheme))
+        .or_else(|| lower.strip_prefix("www."));
+    let Some(rest) = rest else {
+        return false;
+    };
+
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = host.rsplit('@').next().unwrap_or_default();
+    let host = host.split(':').next().unwrap_or_default();
+    !host.is_empty()
+        && (host.contains('.') || host == "localhost")
+        && host
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '.' || c == '-')
+}
+
+fn looks_like_email(token: &str) -> bool {
+    let token = token.strip_prefix("mailto:").unwrap_or(token);
+    let Some((local, domain)) = token.split_once('@') else {
+        return false;
+    };
+    if local.is_empty() || domain.contains('@') {
+        return false;
+    }
+    let local_ok = local
+        .chars()
+        .all(|c| c.is_alphanumeric() || "._%+-".contains(c));
+    let domain_ok = domain
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '.' || c == '-');
+    let tld_ok = domain.rsplit_once('.').is_some_and(|(name, tld)| {
+        !name.is_empty() && tld.len() >= 2 && tld.chars().all(char::is_alphabetic)
+    });
+    local_ok && domain_ok && tld_ok
+}
+
+fn looks_like_phone(text: &str) -> bool {
+    let body = text.strip_prefix('+').unwrap_or(text);
+    if !body
+        .chars()
+        .all(|c| c.is_ascii_digit() || " -().".contains(c))
+    {
+        return false;
+    }
+    let digits = body.chars().filter(char::is_ascii_digit).count();
+    PHONE_DIGITS_RANGE.contains(&digits)
+}
+
+fn looks_like_code(text: &str) -> bool {
+    let mut end = text.len().min(MAX_CODE_SCAN_BYTES);
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    let sample = &text[..end];
+
+    const STRONG_MARKERS: [&str; 8] = ["=>", "->", "::", "&&", "||", "!=", "==", "</"];
+    const LINE_PREFIXES: [&str; 12] = [
+        "fn ",
+        "def ",
+        "function ",
+        "const ",
+        "let ",
+        "var ",
+        "import ",
+        "#include",
+        "class ",
+        "public ",
+        "return ",
+        "if (",
+    ];
+
+    let mut score = 0;
+    if sample.contains('{') && sample.contains('}') {
+        score += 2;
+    }
+    if STRONG_MARKERS.iter().any(|marker| sample.contains(marker)) {
+        score += 1;
+    }
+
+    let mut statement_lines = 0;
+    for line in sample.lines().map(str::trim) {
+        if line.ends_with(';') || line.ends_with('{') || line.ends_with('}') {
+            statement_lines += 1;
+        }
+        if LINE_PREFIXES.iter().any(|prefix| line.starts_with(prefix)) {
+            score += 1;
+        }
+    }
+    score += statement_lines.min(3);
+
+    score >= 3
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_urls_and_emails() {
+        assert_eq!(
+            classify_selection(" https://example.com/path?q=1 "),
+            SelectionContentType::Url
+        );
+        assert_eq!(
+            classify_selection("www.example.org"),
+            SelectionContentType::Url
+        );
+        assert_eq!(
+            classify_selection("user.name+tag@example.co"),
+            SelectionContentType::Email
+        );
+        assert_eq!(
+            classify_selection("not@an@email"),
+            SelectionContentType::Text
+        );
+        assert_eq!(
+            classify_selection("see https://example.com for details"),
+            SelectionContentType::Text
+        );
+    }
+
+    #[test]
+    fn classifies_phone_numbers() {
+        assert_eq!(
+            classify_selection("+1 (555) 123-4567"),
+            SelectionContentType::Phone
+        );
+        assert_eq!(classify_selection("2024"), SelectionContentType::Text);
+    }
+
+    #[test]
+    fn classifies_code_snippets() {
+        let rust = "fn main() {\n    let x = 1;\n    println!(\"{}\", x);\n}";
+        assert_eq!(classify_selection(rust), SelectionContentType::Code);
+        let js = "const add = (a, b) => a + b;";
+        assert_eq!(classify_selection(js), SelectionContentType::Code);
+        assert_eq!(
+            classify_selection("The quick brown fox jumps over the lazy dog."),
+            SelectionContentType::Text
+        );
+    }
+}
@@ -12,6 +12,12 @@
 //! - 按项目规范保留英文日志，注释改为中文便于维护。
 
 use arboard::Clipboard;
+use serde::Serialize;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+#[cfg(target_os = "macos")]
+use std::sync::Condvar;
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant, SystemTime};
@@ -28,20 +34,29 @@ use tauri::{AppHandle, Manager};
 #[cfg(target_os = "windows")]
 use std::ptr::null_mut;
 #[cfg(target_os = "windows")]
-use std::sync::atomic::{AtomicPtr, Ordering};
+use std::sync::atomic::AtomicPtr;
 #[cfg(target_os = "windows")]
 use windows::Win32::Foundation::{HINSTANCE, LPARAM, LRESULT, WPARAM};
 #[cfg(target_os = "windows")]
+use windows::Win32::System::Threading::GetCurrentThreadId;
+#[cfg(target_os = "windows")]
 use windows::Win32::UI::WindowsAndMessaging::{
-    CallNextHookEx, DispatchMessageW, GetMessageW, SetWindowsHookExW, TranslateMessage,
-    UnhookWindowsHookEx, MSG, MSLLHOOKSTRUCT, WH_MOUSE_LL, WM_LBUTTONUP, WM_MOUSEMOVE,
+    CallNextHookEx, DispatchMessageW, GetMessageW, PostThreadMessageW, SetWindowsHookExW,
+    TranslateMessage, UnhookWindowsHookEx, MSG, MSLLHOOKSTRUCT, WH_MOUSE_LL, WM_LBUTTONDOWN,
+    WM_LBUTTONUP, WM_MOUSEMOVE, WM_QUIT,
 };
 
+use crate::log_redaction::capture_log_preview;
+use crate::screen::Rect;
 use crate::selection_toolbar::{
     hide_selection_toolbar_with_manager, platform_cursor_position, resolve_active_app_identifiers,
     show_selection_toolbar_force_with_manager, show_selection_toolbar_with_manager, CursorPosition,
     ToolbarManager,
 };
+use crate::settings;
+use crate::utils::lock_or_recover;
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+use crate::utils::try_lock_or_recover;
 use crate::window_control::resolve_main_window;
 
 #[cfg(target_os = "macos")]
@@ -63,6 +78,51 @@ const TRIGGER_DEBOUNCE_MS: u64 = 200;
 /// 用于防止 UIA/Accessibility API 卡死导致整个应用无响应
 const CAPTURE_TIMEOUT_MS: u64 = 2000;
 
+/// 默认最小拖拽距离（像素）：按下到抬起移动距离不足且非双击时视为普通点击
+const DEFAULT_MIN_DRAG_DISTANCE_PX: u32 = 5;
+
+/// 两次按下间隔小于该值（毫秒）视为双击选词
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+const DOUBLE_CLICK_INTERVAL_MS: u64 = 500;
+
+/// 最小拖拽距离的持久化键
+const SETTING_MIN_DRAG_DISTANCE: &str = "selectionMinDragDistance";
+
+/// 当前生效的最小拖拽距离（像素）
+static MIN_DRAG_DISTANCE_PX: AtomicU32 = AtomicU32::new(DEFAULT_MIN_DRAG_DISTANCE_PX);
+
+/// 模拟复制回退开关的持久化键
+const SETTING_SIMULATED_COPY: &str = "selectionSimulatedCopy";
+
+/// 是否启用模拟 Ctrl+C 的剪贴板回退（仅 Windows 自动划词生效，默认关闭）
+static SIMULATED_COPY_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// 默认最大捕获长度（字符数），超出部分会被截断
+const DEFAULT_MAX_CAPTURE_LENGTH: usize = 20_000;
+
+/// 允许设置的最小捕获长度上限（字符数）
+const MIN_MAX_CAPTURE_LENGTH: usize = 100;
+
+/// 最大捕获长度的持久化键
+const SETTING_MAX_CAPTURE_LENGTH: &str = "selectionMaxCaptureLength";
+
+/// 当前生效的最大捕获长度（字符数）
+static MAX_CAPTURE_LENGTH: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_CAPTURE_LENGTH);
+
+thread_local! {
+    /// 当前线程最近一次捕获是否因超长被截断（捕获在阻塞线程中同步完成，按线程记录即可）
+    static CAPTURE_TRUNCATED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// 划词历史开关的持久化键
+const SETTING_CAPTURE_HISTORY: &str = "selectionCaptureHistory";
+
+/// 划词历史最多保留的条目数
+const CAPTURE_HISTORY_LIMIT: usize = 20;
+
+/// 是否记录划词历史（出于隐私考虑默认关闭）
+static CAPTURE_HISTORY_ENABLED: AtomicBool = AtomicBool::new(false);
+
 /// 预留节流时间窗口（当前未使用）
 const _RESERVED_SUPPRESS_MS: u64 = 0;
 
@@ -70,6 +130,57 @@ const _RESERVED_SUPPRESS_MS: u64 = 0;
 #[cfg(target_os = "macos")]
 const LISTENER_RETRY_DELAY_MS: u64 = 2_000;
 
+/// 监听器是否正在接收系统事件（macOS 事件循环运行中 / Windows 鼠标钩子已安装）
+static LISTENER_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// 监听线程代数：每次停止监听器都会递增，旧代数的线程据此退出
+static LISTENER_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// 监听器健康统计：最近一次收到系统事件的时间（Unix 毫秒，0 表示尚未收到）
+static LAST_EVENT_AT_MS: AtomicU64 = AtomicU64::new(0);
+/// 已处理的系统事件数量
+static EVENTS_PROCESSED: AtomicU64 = AtomicU64::new(0);
+/// 已发起的文本捕获次数
+static CAPTURES_ATTEMPTED: AtomicU64 = AtomicU64::new(0);
+/// 成功捕获到文本的次数
+static CAPTURES_SUCCEEDED: AtomicU64 = AtomicU64::new(0);
+/// 最近一次成功捕获文本的来源（provider 名称或 `clipboard`）
+static LAST_CAPTURE_PROVIDER: Mutex<Option<String>> = Mutex::new(None);
+
+/// 在 release 构建中启用 `simulate_selection` 的环境变量
+const SELECTION_TEST_ENV: &str = "AI_ASK_SELECTION_TEST";
+
+/// macOS：当前监听线程的 CFRunLoop（以地址保存），用于从其他线程停止事件循环
+#[cfg(target_os = "macos")]
+static MACOS_LISTENER_RUN_LOOP: Mutex<Option<usize>> = Mutex::new(None);
+
+/// Windows：鼠标钩子消息循环所在线程 ID，用于投递 WM_QUIT
+#[cfg(target_os = "windows")]
+static WINDOWS_LISTENER_THREAD_ID: AtomicU32 = AtomicU32::new(0);
+
+/// macOS：用于提前唤醒处于重试等待中的监听线程
+#[cfg(target_os = "macos")]
+static LISTENER_RETRY_WAKE: (Mutex<bool>, Condvar) = (Mutex::new(false), Condvar::new());
+
+/// macOS：等待下一次监听重试，期间可被 `wake_listener_retry` 提前唤醒
+#[cfg(target_os = "macos")]
+fn wait_for_listener_retry(delay: Duration) {
+    let (lock, condvar) = &LISTENER_RETRY_WAKE;
+    let guard = lock_or_recover(lock, "listener retry wake");
+    let (mut woken, _) = condvar
+        .wait_timeout_while(guard, delay, |woken| !*woken)
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    *woken = false;
+}
+
+/// macOS：唤醒监听线程立即重试
+#[cfg(target_os = "macos")]
+fn wake_listener_retry() {
+    let (lock, condvar) = &LISTENER_RETRY_WAKE;
+    *lock_or_recover(lock, "listener retry wake") = true;
+    condvar.notify_all();
+}
+
 /// 检查 macOS 辅助功能权限是否已授予
 #[cfg(target_os = "macos")]
 fn check_macos_accessibility_permission() -> bool {
@@ -152,6 +263,16 @@ trait GlobalSelectionProvider: Send + Sync {
 
     /// 尝试从活动窗口捕获选中文本；若无选区或失败则返回 None
     fn capture(&self, app: &AppHandle) -> Option<String>;
+
+    /// 当前焦点元素是否为密码/安全输入框；命中时所有 provider 都不会捕获文本
+    fn secure_field_focused(&self) -> bool {
+        false
+    }
+
+    /// 当前选区在屏幕上的外接矩形（与光标位置同一坐标系）；不支持时返回 None
+    fn selection_bounds(&self) -> Option<Rect> {
+        None
+    }
 }
 
 type ProviderList = Vec<Box<dyn GlobalSelectionProvider>>;
@@ -176,7 +297,23 @@ fn build_providers() -> ProviderList {
     list
 }
 
-/// 规范化与校验捕获文本；过短或为空白时返回 None
+/// 自动划词监听使用的 provider 列表：在通用 provider 之后追加仅限自动监听的最后手段
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+fn build_monitor_providers() -> ProviderList {
+    #[cfg(target_os = "windows")]
+    {
+        let mut list = build_providers();
+        list.push(Box::new(WindowsSimulatedCopyProvider::new()));
+        list
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        build_providers()
+    }
+}
+
+/// 规范化与校验捕获文本；过短或为空白时返回 None，超长时截断到最大捕获长度
 #[cfg(any(target_os = "windows", target_os = "macos"))]
 fn normalize_selection(text: &str) -> Option<String> {
     let trimmed = text.trim();
@@ -189,7 +326,55 @@ fn normalize_selection(text: &str) -> Option<String> {
         return None;
     }
 
-    Some(trimmed.to_string())
+    Some(limit_capture_length(trimmed).to_string())
+}
+
+/// 按最大捕获长度截断文本（按字符计），发生截断时记录到当前线程的截断标记
+fn limit_capture_length(text: &str) -> &str {
+    let max_len = MAX_CAPTURE_LENGTH.load(Ordering::Relaxed);
+    match text.char_indices().nth(max_len) {
+        Some((end, _)) => {
+            log::info!(
+                "Captured selection truncated to {} characters (limit exceeded)",
+                max_len
+            );
+            CAPTURE_TRUNCATED.with(|flag| flag.set(true));
+            &text[..end]
+        }
+        None => text,
+    }
+}
+
+/// 读取并清除当前线程的截断标记
+fn take_capture_truncated() -> bool {
+    CAPTURE_TRUNCATED.with(|flag| flag.replace(false))
+}
+
+/// 在当前线程执行一次捕获，并返回捕获期间是否发生截断
+fn capture_tracking_truncation<T>(capture: impl FnOnce() -> Option<T>) -> (Option<T>, bool) {
+    take_capture_truncated();
+    let text = capture();
+    (text, take_capture_truncated())
+}
+
+/// 设置最大捕获长度（字符数）并持久化，超出部分在捕获时截断
+#[tauri::command]
+pub async fn set_selection_max_capture_length(app: AppHandle, length: usize) -> Result<(), String> {
+    if length < MIN_MAX_CAPTURE_LENGTH {
+        return Err(format!(
+            "max capture length must be at least {MIN_MAX_CAPTURE_LENGTH} characters"
+        ));
+    }
+    settings::save_setting(&app, SETTING_MAX_CAPTURE_LENGTH, &length)?;
+    MAX_CAPTURE_LENGTH.store(length, Ordering::Relaxed);
+    log::info!("Selection max capture length set to {}", length);
+    Ok(())
+}
+
+/// 获取当前最大捕获长度（字符数）
+#[tauri::command]
+pub async fn get_selection_max_capture_length() -> Result<usize, String> {
+    Ok(MAX_CAPTURE_LENGTH.load(Ordering::Relaxed))
 }
 
 // -----------------------------------------------------------------------------
@@ -217,13 +402,17 @@ mod windows_uia {
     //! - 仅直接尝试会导致部分应用无法捕获（因为 TextPattern 暴露在后代节点中）；
     //! - 不加限制的子树查找会严重卡顿（draw.io Desktop 就属于此类场景）；
     //! - 因此选择“受限搜索”以在“功能性”和“性能”之间取得平衡。相关阈值可按需微调。
-    use super::{normalize_selection, GlobalSelectionProvider};
+    use super::{normalize_selection, GlobalSelectionProvider, Rect};
     use std::collections::VecDeque;
     use tauri::AppHandle;
     use windows::Win32::Foundation::HWND;
     use windows::Win32::System::Com::{
         CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_INPROC_SERVER,
-        COINIT_APARTMENTTHREADED,
+        COINIT_APARTMENTTHREADED, SAFEARRAY,
+    };
+    use windows::Win32::System::Ole::{
+        SafeArrayAccessData, SafeArrayDestroy, SafeArrayGetLBound, SafeArrayGetUBound,
+        SafeArrayUnaccessData,
     };
     use windows::Win32::UI::Accessibility::{
         CUIAutomation, IUIAutomation, IUIAutomationElement, IUIAutomationTextPattern,
@@ -314,6 +503,47 @@ mod windows_uia {
         search_descendants_for_text_pattern(ui, element)
     }
 
+    /// 读取 UIA 返回的 double 类型 SAFEARRAY，读取后释放数组
+    unsafe fn read_f64_safearray(array: *mut SAFEARRAY) -> Vec<f64> {
+        if array.is_null() {
+            return Vec::new();
+        }
+
+        let mut values = Vec::new();
+        if let (Ok(lower), Ok(upper)) = (SafeArrayGetLBound(array, 1), SafeArrayGetUBound(array, 1))
+        {
+            let count = (upper - lower + 1).max(0) as usize;
+            let mut data: *mut std::ffi::c_void = std::ptr::null_mut();
+            if count > 0 && SafeArrayAccessData(array, &mut data).is_ok() {
+                values.extend_from_slice(std::slice::from_raw_parts(data as *const f64, count));
+                let _ = SafeArrayUnaccessData(array);
+            }
+        }
+
+        let _ = SafeArrayDestroy(array);
+        values
+    }
+
+    /// 将 `[left, top, width, height, ...]` 形式的多行矩形合并为一个外接矩形
+    fn union_bounding_rectangles(values: &[f64]) -> Option<Rect> {
+        let mut bounds: Option<(f64, f64, f64, f64)> = None;
+        for chunk in values.chunks_exact(4) {
+            let (left, top, right, bottom) =
+                (chunk[0], chunk[1], chunk[0] + chunk[2], chunk[1] + chunk[3]);
+            bounds = Some(match bounds {
+                Some((l, t, r, b)) => (l.min(left), t.min(top), r.max(right), b.max(bottom)),
+                None => (left, top, right, bottom),
+            });
+        }
+
+        bounds.map(|(left, top, right, bottom)| Rect {
+            x: left,
+            y: top,
+            width: right - left,
+            height: bottom - top,
+        })
+    }
+
     pub struct WindowsUIAutomationProvider;
 
     impl WindowsUIAutomationProvider {
@@ -321,6 +551,54 @@ mod windows_uia {
             Self
         }
 
+        /// 通过 TextRange 的 GetBoundingRectangles 读取焦点元素选区的屏幕矩形
+        fn selection_bounds_impl(&self) -> Option<Rect> {
+            unsafe {
+                let init_hr = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+                let should_uninit = init_hr.is_ok();
+
+                let bounds = (|| -> Option<Rect> {
+                    let ui: IUIAutomation =
+                        CoCreateInstance(&CUIAutomation, None, CLSCTX_INPROC_SERVER).ok()?;
+                    let focus = ui.GetFocusedElement().ok()?;
+                    let pattern = obtain_text_pattern(&ui, &focus)?;
+                    let range = pattern.GetSelection().ok()?.GetElement(0).ok()?;
+                    let rectangles = read_f64_safearray(range.GetBoundingRectangles().ok()?);
+                    union_bounding_rectangles(&rectangles)
+                })();
+
+                if should_uninit {
+                    CoUninitialize();
+                }
+
+                bounds
+            }
+        }
+
+        /// 通过 UIA 的 IsPassword 属性判断焦点元素是否为密码输入框
+        fn focused_is_password(&self) -> bool {
+            unsafe {
+                let init_hr = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+                let should_uninit = init_hr.is_ok();
+
+                let is_password = CoCreateInstance::<_, IUIAutomation>(
+                    &CUIAutomation,
+                    None,
+                    CLSCTX_INPROC_SERVER,
+                )
+                .and_then(|ui| ui.GetFocusedElement())
+                .and_then(|focus| focus.CurrentIsPassword())
+                .map(|value| value.as_bool())
+                .unwrap_or(false);
+
+                if should_uninit {
+                    CoUninitialize();
+                }
+
+                is_password
+            }
+        }
+
         fn capture_impl(&self) -> Option<String> {
             unsafe {
                 // 初始化线程 COM；已初始化返回 S_FALSE，首次成功返回 S_OK
@@ -477,6 +755,14 @@ mod windows_uia {
         fn capture(&self, _app: &AppHandle) -> Option<String> {
             self.capture_impl()
         }
+
+        fn secure_field_focused(&self) -> bool {
+            self.focused_is_password()
+        }
+
+        fn selection_bounds(&self) -> Option<Rect> {
+            self.selection_bounds_impl()
+        }
     }
 }
 
@@ -495,10 +781,12 @@ mod windows_win32 {
     use tauri::AppHandle;
     use windows::Win32::Foundation::{HWND, LPARAM, WPARAM};
     use windows::Win32::UI::WindowsAndMessaging::{
-        GetForegroundWindow, RealGetWindowClassW, SendMessageW, WM_GETTEXT, WM_GETTEXTLENGTH,
+        GetForegroundWindow, GetWindowLongW, RealGetWindowClassW, SendMessageW, GWL_STYLE,
+        WM_GETTEXT, WM_GETTEXTLENGTH,
     };
 
     const EM_GETSEL: u32 = 0x00B0;
+    const ES_PASSWORD: u32 = 0x0020;
 
     pub struct WindowsWin32EditProvider;
 
@@ -532,6 +820,22 @@ mod windows_win32 {
         fn capture(&self, _app: &AppHandle) -> Option<String> {
             self.capture_impl()
         }
+
+        fn secure_field_focused(&self) -> bool {
+            unsafe {
+                let hwnd: HWND = GetForegroundWindow();
+                if hwnd.0.is_null() {
+                    return false;
+                }
+
+                match get_window_class(hwnd) {
+                    Some(class_name) if is_supported_class(&class_name) => {
+                        (GetWindowLongW(hwnd, GWL_STYLE) as u32) & ES_PASSWORD != 0
+                    }
+                    _ => false,
+                }
+            }
+        }
     }
 
     fn supported_classes() -> &'static HashSet<String> {
@@ -613,19 +917,182 @@ mod windows_win32 {
 #[cfg(target_os = "windows")]
 use windows_win32::WindowsWin32EditProvider;
 
+// -----------------------------------------------------------------------------
+// Windows 模拟复制回退 Provider（需手动开启）
+// -----------------------------------------------------------------------------
+#[cfg(target_os = "windows")]
+mod windows_simulated_copy {
+    // Chromium/Electron 应用常不暴露可用的 TextPattern 选区，作为最后手段：
+    // 模拟 Ctrl+C 读取剪贴板，随后恢复原有文本内容。会改写剪贴板，因此仅在设置开启时生效。
+    use super::{normalize_selection, GlobalSelectionProvider, SIMULATED_COPY_ENABLED};
+    use arboard::Clipboard;
+    use std::sync::atomic::Ordering;
+    use std::thread;
+    use std::time::{Duration, Instant};
+    use tauri::AppHandle;
+    use windows::Win32::System::DataExchange::{CountClipboardFormats, GetClipboardSequenceNumber};
+    use windows::Win32::UI::Input::KeyboardAndMouse::{
+        SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYBD_EVENT_FLAGS, KEYEVENTF_KEYUP,
+        VIRTUAL_KEY, VK_CONTROL,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{GetClassNameW, GetForegroundWindow};
+
+    /// 终端窗口类（传统控制台与 Windows Terminal）：Ctrl+C 会中断前台进程而不是复制
+    const TERMINAL_WINDOW_CLASSES: [&str; 2] =
+        ["ConsoleWindowClass", "CASCADIA_HOSTING_WINDOW_CLASS"];
+    /// 等待目标应用写入剪贴板的最长时间（毫秒）
+    const COPY_WAIT_TIMEOUT_MS: u64 = 300;
+    /// 轮询剪贴板序列号的间隔（毫秒）
+    const COPY_POLL_INTERVAL_MS: u64 = 10;
+
+    pub struct WindowsSimulatedCopyProvider;
+
+    impl WindowsSimulatedCopyProvider {
+        pub fn new() -> Self {
+            Self
+        }
+
+        fn capture_impl(&self) -> Option<String> {
+            let mut clipboard = Clipboard::new().ok()?;
+            let previous = clipboard.get_text().ok();
+            // 剪贴板中是无法按文本恢复的内容（图片、文件等）时放弃，避免破坏用户数据
+            if previous.is_none() && unsafe { CountClipboardFormats() } > 0 {
+                log::debug!("Simulated copy skipped: clipboard holds non-text content");
+                return None;
+            }
+
+            let sequence_before = unsafe { GetClipboardSequenceNumber() };
+            if !send_copy_shortcut() {
+                return None;
+            }
+
+            let deadline = Instant::now() + Duration::from_millis(COPY_WAIT_TIMEOUT_MS);
+            while unsafe { GetClipboardSequenceNumber() } == sequence_before {
+                if Instant::now() >= deadline {
+                    log::debug!("Simulated copy produced no clipboard change");
+                    return None;
+                }
+                thread::sleep(Duration::from_millis(COPY_POLL_INTERVAL_MS));
+            }
+
+            let copied = clipboard.get_text().ok();
+            let restored = match previous {
+                Some(text) => clipboard.set_text(text),
+                None => clipboard.clear(),
+            };
+            if let Err(error) = restored {
+                log::warn!(
+                    "Failed to restore clipboard after simulated copy: {}",
+                    error
+                );
+            }
+
+            copied.and_then(|text| normalize_selection(&text))
+        }
+    }
+
+    /// 前台窗口是否为终端
+    fn foreground_is_terminal() -> bool {
+        let mut buffer = [0u16; 256];
+        let length = unsafe {
+            let hwnd = GetForegroundWindow();
+            if hwnd.is_invalid() {
+                return false;
+            }
+            GetClassNameW(hwnd, &mut buffer)
+        };
+        let Ok(length) = usize::try_from(length) else {
+            return false;
+        };
+        let class_name = String::from_utf16_lossy(&buffer[..length]);
+        TERMINAL_WINDOW_CLASSES.contains(&class_name.as_str())
+    }
+
+    /// 通过 SendInput 发送 Ctrl+C
+    fn send_copy_shortcut() -> bool {
+        let key = |vk: VIRTUAL_KEY, flags: KEYBD_EVENT_FLAGS| INPUT {
+            r#type: INPUT_KEYBOARD,
+            Anonymous: INPUT_0 {
+                ki: KEYBDINPUT {
+                    wVk: vk,
+                    wScan: 0,
+                    dwFlags: flags,
+                    time: 0,
+                    dwExtraInfo: 0,
+                },
+            },
+        };
+        let vk_c = VIRTUAL_KEY(u16::from(b'C'));
+        let inputs = [
+            key(VK_CONTROL, KEYBD_EVENT_FLAGS(0)),
+            key(vk_c, KEYBD_EVENT_FLAGS(0)),
+            key(vk_c, KEYEVENTF_KEYUP),
+            key(VK_CONTROL, KEYEVENTF_KEYUP),
+        ];
+
+        let sent = unsafe { SendInput(&inputs, std::mem::size_of::<INPUT>() as i32) };
+        if sent as usize != inputs.len() {
+            log::debug!("SendInput injected {} of {} events", sent, inputs.len());
+            return false;
+        }
+        true
+    }
+
+    impl GlobalSelectionProvider for WindowsSimulatedCopyProvider {
+        fn name(&self) -> &'static str {
+            "windows-simulated-copy"
+        }
+
+        fn capture(&self, _app: &AppHandle) -> Option<String> {
+            if !SIMULATED_COPY_ENABLED.load(Ordering::Relaxed) {
+                return None;
+            }
+            if foreground_is_terminal() {
+                log::debug!("Simulated copy skipped: foreground window is a terminal");
+                return None;
+            }
+            self.capture_impl()
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+use windows_simulated_copy::WindowsSimulatedCopyProvider;
+
 // -----------------------------------------------------------------------------
 // macOS Accessibility Provider（阶段 2）
 // -----------------------------------------------------------------------------
 #[cfg(target_os = "macos")]
 mod macos_accessibility {
-    use super::{normalize_selection, GlobalSelectionProvider};
+    use super::{normalize_selection, GlobalSelectionProvider, Rect};
     use accessibility::{AXAttribute, AXUIElement, Error as AccessibilityError};
-    use core_foundation::string::CFString;
+    use core_foundation::base::{CFType, CFTypeRef, TCFType};
+    use core_foundation::string::{CFString, CFStringRef};
+    use core_graphics::geometry::{CGPoint, CGRect, CGSize};
     use log::debug;
+    use std::ffi::c_void;
     use tauri::AppHandle;
 
     const ATTR_FOCUSED_UI_ELEMENT: &str = "AXFocusedUIElement";
     const ATTR_SELECTED_TEXT: &str = "AXSelectedText";
+    const ATTR_ROLE: &str = "AXRole";
+    const ATTR_SUBROLE: &str = "AXSubrole";
+    const SECURE_TEXT_FIELD: &str = "AXSecureTextField";
+    const ATTR_SELECTED_TEXT_RANGE: &str = "AXSelectedTextRange";
+    const PARAM_ATTR_BOUNDS_FOR_RANGE: &str = "AXBoundsForRange";
+    /// `kAXValueCGRectType`
+    const AX_VALUE_CG_RECT_TYPE: u32 = 3;
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXUIElementCopyParameterizedAttributeValue(
+            element: CFTypeRef,
+            parameterized_attribute: CFStringRef,
+            parameter: CFTypeRef,
+            result: *mut CFTypeRef,
+        ) -> i32;
+        fn AXValueGetValue(value: CFTypeRef, value_type: u32, value_ptr: *mut c_void) -> bool;
+    }
 
     pub struct MacosAccessibilityProvider;
 
@@ -673,6 +1140,62 @@ mod macos_accessibility {
             }
         }
 
+        /// 通过 AXBoundsForRange 读取焦点元素当前选区的屏幕矩形
+        fn selection_bounds_impl(&self) -> Option<Rect> {
+            let system = AXUIElement::system_wide();
+            let focused = Self::focused_element(&system)?;
+            let range_attr =
+                AXAttribute::new(&CFString::from_static_string(ATTR_SELECTED_TEXT_RANGE));
+            let range = focused.attribute(&range_attr).ok()?;
+            let parameter_name = CFString::from_static_string(PARAM_ATTR_BOUNDS_FOR_RANGE);
+
+            unsafe {
+                let mut raw: CFTypeRef = std::ptr::null();
+                let status = AXUIElementCopyParameterizedAttributeValue(
+                    focused.as_CFTypeRef(),
+                    parameter_name.as_concrete_TypeRef(),
+                    range.as_CFTypeRef(),
+                    &mut raw,
+                );
+                if status != 0 || raw.is_null() {
+                    debug!(
+                        "macOS accessibility provider failed to read selection bounds: AX error {}",
+                        status
+                    );
+                    return None;
+                }
+
+                let value = CFType::wrap_under_create_rule(raw);
+                let mut rect = CGRect::new(&CGPoint::new(0.0, 0.0), &CGSize::new(0.0, 0.0));
+                if !AXValueGetValue(
+                    value.as_CFTypeRef(),
+                    AX_VALUE_CG_RECT_TYPE,
+                    &mut rect as *mut CGRect as *mut c_void,
+                ) {
+                    return None;
+                }
+
+                Some(Rect {
+                    x: rect.origin.x,
+                    y: rect.origin.y,
+                    width: rect.size.width,
+                    height: rect.size.height,
+                })
+            }
+        }
+
+        /// 焦点元素的角色或子角色为 AXSecureTextField 时视为密码输入框
+        fn is_secure_text_field(element: &AXUIElement) -> bool {
+            [ATTR_ROLE, ATTR_SUBROLE].iter().any(|name| {
+                let attr = AXAttribute::new(&CFString::from_static_string(name));
+                element
+                    .attribute(&attr)
+                    .ok()
+                    .and_then(|value| value.downcast::<CFString>())
+                    .is_some_and(|value| value == SECURE_TEXT_FIELD)
+            })
+        }
+
         fn read_selected_text(element: &AXUIElement) -> Option<String> {
             let attr = AXAttribute::new(&CFString::from_static_string(ATTR_SELECTED_TEXT));
             let value = match element.attribute(&attr) {
@@ -716,6 +1239,17 @@ mod macos_accessibility {
         fn capture(&self, _app: &AppHandle) -> Option<String> {
             self.capture_impl()
         }
+
+        fn secure_field_focused(&self) -> bool {
+            let system = AXUIElement::system_wide();
+            Self::focused_element(&system)
+                .map(|focused| Self::is_secure_text_field(&focused))
+                .unwrap_or(false)
+        }
+
+        fn selection_bounds(&self) -> Option<Rect> {
+            self.selection_bounds_impl()
+        }
     }
 }
 
@@ -733,68 +1267,253 @@ struct MonitorState {
     last_mouse_position: (f64, f64),
     /// 并发保护标记（避免同时进行多次捕获）
     capture_in_progress: bool,
+    /// 最近一次左键按下的坐标与时间（用于判断拖拽距离与双击）
+    last_press: Option<((f64, f64), Instant)>,
+    /// 当前这次按下是否构成双击
+    double_click_pending: bool,
+    /// 最近捕获的不重复文本（新的在前），仅在开启划词历史时记录
+    capture_history: VecDeque<CaptureHistoryEntry>,
 }
 
-#[cfg(target_os = "windows")]
-struct WindowsMouseHookContext {
-    app_handle: AppHandle,
-    toolbar_manager: ToolbarManager,
-    monitor_state: Arc<Mutex<MonitorState>>,
-    providers: Arc<ProviderList>,
+/// 划词历史条目
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureHistoryEntry {
+    pub text: String,
+    /// 捕获时间（Unix 毫秒）
+    pub captured_at_ms: u64,
 }
 
-#[cfg(target_os = "windows")]
-static WINDOWS_MOUSE_CONTEXT: AtomicPtr<WindowsMouseHookContext> = AtomicPtr::new(null_mut());
-
-#[cfg(target_os = "windows")]
-unsafe extern "system" fn windows_mouse_hook_proc(
-    code: i32,
-    wparam: WPARAM,
-    lparam: LPARAM,
-) -> LRESULT {
-    // Windows 低级鼠标钩子：仅转发鼠标移动与左键抬起至统一事件处理函数
-    if code < 0 {
-        return CallNextHookEx(None, code, wparam, lparam);
+impl MonitorState {
+    /// 记录一次捕获：相同文本移到最前，超出上限时丢弃最旧的条目
+    fn record_capture(&mut self, text: &str) {
+        if !CAPTURE_HISTORY_ENABLED.load(Ordering::Relaxed) {
+            return;
+        }
+        let captured_at_ms = SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or_default();
+        self.capture_history.retain(|entry| entry.text != text);
+        self.capture_history.push_front(CaptureHistoryEntry {
+            text: text.to_string(),
+            captured_at_ms,
+        });
+        self.capture_history.truncate(CAPTURE_HISTORY_LIMIT);
     }
+}
 
-    let context_ptr = WINDOWS_MOUSE_CONTEXT.load(Ordering::SeqCst);
-    if context_ptr.is_null() {
-        return CallNextHookEx(None, code, wparam, lparam);
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+impl MonitorState {
+    /// 记录一次左键按下，并根据与上次按下的间隔与距离判断是否为双击
+    fn record_press(&mut self, now: Instant) {
+        let position = self.last_mouse_position;
+        let threshold = f64::from(MIN_DRAG_DISTANCE_PX.load(Ordering::Relaxed));
+        self.double_click_pending = self.last_press.is_some_and(|(previous, at)| {
+            now.duration_since(at) <= Duration::from_millis(DOUBLE_CLICK_INTERVAL_MS)
+                && pointer_distance(previous, position) <= threshold.max(1.0)
+        });
+        self.last_press = Some((position, now));
     }
 
-    let mouse_info = &*(lparam.0 as *const MSLLHOOKSTRUCT);
-    let event_type = match wparam.0 as u32 {
-        WM_MOUSEMOVE => Some(EventType::MouseMove {
-            x: mouse_info.pt.x as f64,
-            y: mouse_info.pt.y as f64,
-        }),
-        WM_LBUTTONUP => Some(EventType::ButtonRelease(Button::Left)),
-        _ => None,
-    };
-
-    if let Some(event_type) = event_type {
-        let event = Event {
-            event_type,
-            name: None,
-            time: SystemTime::now(),
+    /// 左键抬起时判断是否构成划词手势：移动距离超过阈值或为双击选词
+    fn is_selection_gesture(&self) -> bool {
+        let Some((press_position, _)) = self.last_press else {
+            // 未记录到按下事件时保持原有行为
+            return true;
         };
 
-        let context = &*context_ptr;
-        handle_event(
-            event,
-            &context.app_handle,
-            &context.toolbar_manager,
-            &context.monitor_state,
-            &context.providers,
-        );
+        let threshold = f64::from(MIN_DRAG_DISTANCE_PX.load(Ordering::Relaxed));
+        self.double_click_pending
+            || pointer_distance(press_position, self.last_mouse_position) > threshold
     }
+}
 
-    CallNextHookEx(None, code, wparam, lparam)
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+fn pointer_distance(from: (f64, f64), to: (f64, f64)) -> f64 {
+    (to.0 - from.0).hypot(to.1 - from.1)
 }
 
-pub fn start_global_selection_monitor(app: AppHandle) {
-    #[cfg(any(target_os = "windows", target_os = "macos"))]
-    {
+/// 设置划词判定的最小拖拽距离（像素）并持久化
+#[tauri::command]
+pub async fn set_selection_min_drag_distance(app: AppHandle, distance: u32) -> Result<(), String> {
+    settings::save_setting(&app, SETTING_MIN_DRAG_DISTANCE, &distance)?;
+    MIN_DRAG_DISTANCE_PX.store(distance, Ordering::Relaxed);
+    log::info!("Selection minimum drag distance set to {}px", distance);
+    Ok(())
+}
+
+/// 获取当前划词判定的最小拖拽距离（像素）
+#[tauri::command]
+pub async fn get_selection_min_drag_distance() -> Result<u32, String> {
+    Ok(MIN_DRAG_DISTANCE_PX.load(Ordering::Relaxed))
+}
+
+/// 启用或关闭模拟复制回退（Windows：UIA 与 Win32 均无结果时模拟 Ctrl+C 读取剪贴板并恢复）
+///
+/// 该方式会短暂改写剪贴板，因此默认关闭，仅作用于自动划词监听。
+#[tauri::command]
+pub async fn set_selection_simulated_copy(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::save_setting(&app, SETTING_SIMULATED_COPY, &enabled)?;
+    SIMULATED_COPY_ENABLED.store(enabled, Ordering::Relaxed);
+    log::info!("Selection simulated copy fallback set to {}", enabled);
+    Ok(())
+}
+
+/// 获取模拟复制回退是否启用
+#[tauri::command]
+pub async fn get_selection_simulated_copy() -> Result<bool, String> {
+    Ok(SIMULATED_COPY_ENABLED.load(Ordering::Relaxed))
+}
+
+/// 启用或关闭划词历史；关闭时同时清空已记录的历史
+///
+/// 密码等安全输入框中的选区不会被捕获，因此也不会进入历史。
+#[tauri::command]
+pub async fn set_selection_capture_history(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::save_setting(&app, SETTING_CAPTURE_HISTORY, &enabled)?;
+    CAPTURE_HISTORY_ENABLED.store(enabled, Ordering::Relaxed);
+    if !enabled {
+        clear_history_entries();
+    }
+    log::info!("Selection capture history set to {}", enabled);
+    Ok(())
+}
+
+/// 获取划词历史是否启用
+#[tauri::command]
+pub async fn get_selection_capture_history_enabled() -> Result<bool, String> {
+    Ok(CAPTURE_HISTORY_ENABLED.load(Ordering::Relaxed))
+}
+
+/// 获取最近捕获的划词历史（新的在前）；监听器未运行时返回空列表
+#[tauri::command]
+pub async fn get_capture_history() -> Result<Vec<CaptureHistoryEntry>, String> {
+    Ok(
+        match lock_or_recover(&ACTIVE_MONITOR_STATE, "monitor registry").as_ref() {
+            Some(monitor_state) => lock_or_recover(monitor_state, "global selection state")
+                .capture_history
+                .iter()
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        },
+    )
+}
+
+/// 清空划词历史
+#[tauri::command]
+pub async fn clear_capture_history() -> Result<(), String> {
+    clear_history_entries();
+    log::info!("Cleared selection capture history");
+    Ok(())
+}
+
+fn clear_history_entries() {
+    if let Some(monitor_state) = lock_or_recover(&ACTIVE_MONITOR_STATE, "monitor registry").as_ref()
+    {
+        lock_or_recover(monitor_state, "global selection state")
+            .capture_history
+            .clear();
+    }
+}
+
+/// 将快捷键（或模拟）捕获的文本写入运行中监听器的划词历史
+fn record_hotkey_capture(text: &str) {
+    if let Some(monitor_state) = lock_or_recover(&ACTIVE_MONITOR_STATE, "monitor registry").as_ref()
+    {
+        lock_or_recover(monitor_state, "global selection state").record_capture(text);
+    }
+}
+
+/// 当前运行中监听器的共享状态，供命令（如清除捕获文本）访问
+static ACTIVE_MONITOR_STATE: Mutex<Option<Arc<Mutex<MonitorState>>>> = Mutex::new(None);
+
+/// 清除内存中缓存的划词文本（监听器状态与工具栏状态）
+///
+/// 供注重隐私的用户手动清空最近一次选中文本，清除后 `get_selection_toolbar_state`
+/// 不再返回旧文本。
+#[tauri::command]
+pub async fn clear_captured_text(
+    toolbar_state: tauri::State<'_, ToolbarManager>,
+) -> Result<(), String> {
+    if let Some(monitor_state) = lock_or_recover(&ACTIVE_MONITOR_STATE, "monitor registry").as_ref()
+    {
+        lock_or_recover(monitor_state, "global selection state").last_text = None;
+    }
+
+    toolbar_state
+        .lock()
+        .map_err(|e| format!("Failed to lock toolbar state: {}", e))?
+        .clear_last_text();
+
+    log::info!("Cleared captured selection text");
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+struct WindowsMouseHookContext {
+    app_handle: AppHandle,
+    toolbar_manager: ToolbarManager,
+    monitor_state: Arc<Mutex<MonitorState>>,
+    providers: Arc<ProviderList>,
+}
+
+#[cfg(target_os = "windows")]
+static WINDOWS_MOUSE_CONTEXT: AtomicPtr<WindowsMouseHookContext> = AtomicPtr::new(null_mut());
+
+#[cfg(target_os = "windows")]
+unsafe extern "system" fn windows_mouse_hook_proc(
+    code: i32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    // Windows 低级鼠标钩子：仅转发鼠标移动与左键按下/抬起至统一事件处理函数
+    if code < 0 {
+        return CallNextHookEx(None, code, wparam, lparam);
+    }
+
+    let context_ptr = WINDOWS_MOUSE_CONTEXT.load(Ordering::SeqCst);
+    if context_ptr.is_null() {
+        return CallNextHookEx(None, code, wparam, lparam);
+    }
+
+    let mouse_info = &*(lparam.0 as *const MSLLHOOKSTRUCT);
+    let event_type = match wparam.0 as u32 {
+        WM_MOUSEMOVE => Some(EventType::MouseMove {
+            x: mouse_info.pt.x as f64,
+            y: mouse_info.pt.y as f64,
+        }),
+        WM_LBUTTONDOWN => Some(EventType::ButtonPress(Button::Left)),
+        WM_LBUTTONUP => Some(EventType::ButtonRelease(Button::Left)),
+        _ => None,
+    };
+
+    if let Some(event_type) = event_type {
+        let event = Event {
+            event_type,
+            name: None,
+            time: SystemTime::now(),
+        };
+
+        let context = &*context_ptr;
+        handle_event(
+            event,
+            &context.app_handle,
+            &context.toolbar_manager,
+            &context.monitor_state,
+            &context.providers,
+            EventOrigin::Hook,
+        );
+    }
+
+    CallNextHookEx(None, code, wparam, lparam)
+}
+
+/// 启动全局划词监听器，返回是否启动了监听线程（不支持的平台返回 `false`）
+pub fn start_global_selection_monitor(app: AppHandle) -> bool {
+    #[cfg(any(target_os = "windows", target_os = "macos"))]
+    {
         // macOS：检测辅助功能权限（未授权时仍会启动监听并周期重试）
         #[cfg(target_os = "macos")]
         {
@@ -809,22 +1528,227 @@ pub fn start_global_selection_monitor(app: AppHandle) {
             }
         }
 
+        if let Some(distance) = settings::load_setting::<u32>(&app, SETTING_MIN_DRAG_DISTANCE) {
+            MIN_DRAG_DISTANCE_PX.store(distance, Ordering::Relaxed);
+        }
+        if let Some(enabled) = settings::load_setting::<bool>(&app, SETTING_SIMULATED_COPY) {
+            SIMULATED_COPY_ENABLED.store(enabled, Ordering::Relaxed);
+        }
+        if let Some(length) = settings::load_setting::<usize>(&app, SETTING_MAX_CAPTURE_LENGTH) {
+            MAX_CAPTURE_LENGTH.store(length.max(MIN_MAX_CAPTURE_LENGTH), Ordering::Relaxed);
+        }
+        if let Some(enabled) = settings::load_setting::<bool>(&app, SETTING_CAPTURE_HISTORY) {
+            CAPTURE_HISTORY_ENABLED.store(enabled, Ordering::Relaxed);
+        }
+
         let app_handle = app.clone();
         let toolbar_manager = app.state::<ToolbarManager>().inner().clone();
-        let providers = Arc::new(build_providers());
+        let providers = Arc::new(build_monitor_providers());
         let shared_state = Arc::new(Mutex::new(MonitorState::default()));
+        *lock_or_recover(&ACTIVE_MONITOR_STATE, "monitor registry") = Some(shared_state.clone());
+        let generation = LISTENER_GENERATION.load(Ordering::SeqCst);
 
         #[cfg(target_os = "macos")]
-        spawn_macos_selection_listener(app_handle, toolbar_manager, providers, shared_state);
+        spawn_macos_selection_listener(
+            app_handle,
+            toolbar_manager,
+            providers,
+            shared_state,
+            generation,
+        );
 
         #[cfg(target_os = "windows")]
-        spawn_windows_selection_listener(app_handle, toolbar_manager, providers, shared_state);
+        spawn_windows_selection_listener(
+            app_handle,
+            toolbar_manager,
+            providers,
+            shared_state,
+            generation,
+        );
+
+        true
     }
 
     #[cfg(not(any(target_os = "windows", target_os = "macos")))]
     {
         log::warn!("Global selection monitor is not available on this platform");
+        let _ = app;
+        false
+    }
+}
+
+/// 监听器健康状况
+///
+/// 鼠标有活动但 `last_event_at_ms` 长时间不变，通常意味着系统钩子已失效
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorHealth {
+    pub running: bool,
+    pub last_event_at_ms: Option<u64>,
+    pub events_processed: u64,
+    pub captures_attempted: u64,
+    pub captures_succeeded: u64,
+    /// 最近一次成功捕获文本的来源
+    pub last_capture_provider: Option<String>,
+}
+
+/// 获取全局划词监听器的运行状态与事件统计
+#[tauri::command]
+pub async fn get_monitor_health() -> Result<MonitorHealth, String> {
+    let last_event_at_ms = LAST_EVENT_AT_MS.load(Ordering::Relaxed);
+    Ok(MonitorHealth {
+        running: LISTENER_RUNNING.load(Ordering::SeqCst),
+        last_event_at_ms: (last_event_at_ms != 0).then_some(last_event_at_ms),
+        events_processed: EVENTS_PROCESSED.load(Ordering::Relaxed),
+        captures_attempted: CAPTURES_ATTEMPTED.load(Ordering::Relaxed),
+        captures_succeeded: CAPTURES_SUCCEEDED.load(Ordering::Relaxed),
+        last_capture_provider: lock_or_recover(&LAST_CAPTURE_PROVIDER, "capture provider").clone(),
+    })
+}
+
+/// 记录收到一次系统事件
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+fn record_monitor_event(time: SystemTime) {
+    EVENTS_PROCESSED.fetch_add(1, Ordering::Relaxed);
+    if let Ok(elapsed) = time.duration_since(std::time::UNIX_EPOCH) {
+        LAST_EVENT_AT_MS.store(elapsed.as_millis() as u64, Ordering::Relaxed);
+    }
+}
+
+/// 停止全局划词监听器：卸载系统钩子并结束监听线程，之后可通过 `start_global_selection_monitor` 重新启动
+///
+/// 划词工具栏被禁用时调用，避免长期保留无用的系统级钩子
+pub fn stop_global_selection_monitor() {
+    let registered = lock_or_recover(&ACTIVE_MONITOR_STATE, "monitor registry")
+        .take()
+        .is_some();
+    if !registered {
+        return;
+    }
+
+    LISTENER_GENERATION.fetch_add(1, Ordering::SeqCst);
+
+    #[cfg(target_os = "macos")]
+    {
+        if let Some(run_loop) = *lock_or_recover(&MACOS_LISTENER_RUN_LOOP, "listener run loop") {
+            // 持锁期间监听线程仍在事件循环内，CFRunLoop 地址有效
+            unsafe {
+                core_foundation::runloop::CFRunLoopStop(
+                    run_loop as core_foundation::runloop::CFRunLoopRef,
+                );
+            }
+        }
+        wake_listener_retry();
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let thread_id = WINDOWS_LISTENER_THREAD_ID.swap(0, Ordering::SeqCst);
+        if thread_id != 0 {
+            if let Err(error) =
+                unsafe { PostThreadMessageW(thread_id, WM_QUIT, WPARAM(0), LPARAM(0)) }
+            {
+                log::warn!("Failed to stop Windows mouse hook thread: {:?}", error);
+            }
+        }
+    }
+
+    LISTENER_RUNNING.store(false, Ordering::SeqCst);
+    log::info!("Global selection monitor stopped");
+}
+
+/// 按划词工具栏启用状态启动或停止监听器
+pub fn set_global_selection_monitor_active(app: &AppHandle, active: bool) {
+    if active {
+        let registered = lock_or_recover(&ACTIVE_MONITOR_STATE, "monitor registry").is_some();
+        if !registered {
+            start_global_selection_monitor(app.clone());
+        }
+    } else {
+        stop_global_selection_monitor();
+    }
+}
+
+/// 停止全局划词监听器
+#[tauri::command]
+pub async fn stop_selection_monitor() -> Result<(), String> {
+    stop_global_selection_monitor();
+    Ok(())
+}
+
+/// 划词工具栏是否已启用；未启用时监听器应保持停止
+fn selection_toolbar_enabled(app: &AppHandle) -> bool {
+    app.try_state::<ToolbarManager>()
+        .is_some_and(|manager| lock_or_recover(manager.inner(), "toolbar state").is_enabled())
+}
+
+/// 停止并重新启动全局划词监听器；划词工具栏已关闭时只停止，不再重新启动
+#[tauri::command]
+pub async fn restart_selection_monitor(app: AppHandle) -> Result<bool, String> {
+    stop_global_selection_monitor();
+    if !selection_toolbar_enabled(&app) {
+        log::info!("Selection toolbar is disabled, leaving global selection monitor stopped");
+        return Ok(false);
+    }
+    start_global_selection_monitor(app);
+    // 给监听线程一点时间进入事件循环，使返回的状态更准确
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    Ok(LISTENER_RUNNING.load(Ordering::SeqCst))
+}
+
+/// 权限重新检查结果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorRecheckResult {
+    pub permission_granted: bool,
+    pub listener_running: bool,
+    /// 本次调用是否重新激活了监听器
+    pub restarted: bool,
+}
+
+/// 重新激活监听器：macOS 唤醒处于重试等待中的监听线程；监听器未注册或已退出时重新启动
+///
+/// 返回是否确实重新激活了监听器；划词工具栏已关闭或平台不支持时返回 `false`
+fn restart_selection_listener(app: AppHandle) -> bool {
+    if !selection_toolbar_enabled(&app) {
+        log::info!("Selection toolbar is disabled, not reactivating global selection monitor");
+        return false;
     }
+
+    #[cfg(target_os = "macos")]
+    {
+        let registered = lock_or_recover(&ACTIVE_MONITOR_STATE, "monitor registry").is_some();
+        if registered {
+            wake_listener_retry();
+            return true;
+        }
+    }
+
+    start_global_selection_monitor(app)
+}
+
+/// 重新检查辅助功能权限；若已授权但监听器未在接收事件，则立即重新激活监听器
+///
+/// 供前端在用户授权后提供“重新检查”入口，无需重启应用。
+#[tauri::command]
+pub async fn recheck_and_restart_monitor(app: AppHandle) -> Result<MonitorRecheckResult, String> {
+    let permission_granted = check_accessibility_permission().await?;
+    let mut restarted = false;
+
+    if permission_granted && !LISTENER_RUNNING.load(Ordering::SeqCst) {
+        log::info!("Accessibility permission available, reactivating global selection monitor");
+        restarted = restart_selection_listener(app);
+        if restarted {
+            // 给监听线程一点时间进入事件循环，使返回的状态更准确
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+    }
+
+    Ok(MonitorRecheckResult {
+        permission_granted,
+        listener_running: LISTENER_RUNNING.load(Ordering::SeqCst),
+        restarted,
+    })
 }
 
 #[cfg(target_os = "macos")]
@@ -833,11 +1757,14 @@ fn spawn_macos_selection_listener(
     toolbar_manager: ToolbarManager,
     providers: Arc<ProviderList>,
     shared_state: Arc<Mutex<MonitorState>>,
+    generation: u64,
 ) {
+    use core_foundation::runloop::CFRunLoop;
+
     thread::spawn(move || {
         let mut attempt: u64 = 0;
 
-        loop {
+        while LISTENER_GENERATION.load(Ordering::SeqCst) == generation {
             attempt += 1;
             log::info!(
                 "Starting global selection monitor listener (attempt #{})",
@@ -849,15 +1776,39 @@ fn spawn_macos_selection_listener(
             let listener_state = shared_state.clone();
             let listener_providers = providers.clone();
 
-            match listen(move |event| {
+            let run_loop = CFRunLoop::get_current().as_concrete_TypeRef() as usize;
+            *lock_or_recover(&MACOS_LISTENER_RUN_LOOP, "listener run loop") = Some(run_loop);
+            LISTENER_RUNNING.store(true, Ordering::SeqCst);
+            let result = listen(move |event| {
+                // 发布 run loop 与 listen 进入 CFRunLoopRun 之间发出的停止请求会丢失，
+                // 这里按代次自检：已过期的监听不再处理事件，并主动退出自己的 run loop
+                if LISTENER_GENERATION.load(Ordering::SeqCst) != generation {
+                    CFRunLoop::get_current().stop();
+                    return;
+                }
                 handle_event(
                     event,
                     &listener_app,
                     &listener_toolbar,
                     &listener_state,
                     &listener_providers,
+                    EventOrigin::Hook,
                 );
-            }) {
+            });
+            {
+                let mut published = lock_or_recover(&MACOS_LISTENER_RUN_LOOP, "listener run loop");
+                // 新一代监听可能已经发布了自己的 run loop，不能被旧线程清掉
+                if *published == Some(run_loop) {
+                    *published = None;
+                }
+            }
+
+            if LISTENER_GENERATION.load(Ordering::SeqCst) != generation {
+                break;
+            }
+            LISTENER_RUNNING.store(false, Ordering::SeqCst);
+
+            match result {
                 Ok(()) => {
                     log::warn!("Global selection monitor listener exited unexpectedly; restarting");
                 }
@@ -870,8 +1821,10 @@ fn spawn_macos_selection_listener(
                 "Retrying global selection monitor in {} ms",
                 LISTENER_RETRY_DELAY_MS
             );
-            thread::sleep(Duration::from_millis(LISTENER_RETRY_DELAY_MS));
+            wait_for_listener_retry(Duration::from_millis(LISTENER_RETRY_DELAY_MS));
         }
+
+        log::info!("Global selection monitor listener thread exited");
     });
 
     log::info!("Global selection monitor worker spawned"); // 启动监听线程（macOS）
@@ -883,6 +1836,7 @@ fn spawn_windows_selection_listener(
     toolbar_manager: ToolbarManager,
     providers: Arc<ProviderList>,
     shared_state: Arc<Mutex<MonitorState>>,
+    generation: u64,
 ) {
     thread::spawn(move || unsafe {
         let context = Box::new(WindowsMouseHookContext {
@@ -903,25 +1857,52 @@ fn spawn_windows_selection_listener(
             Ok(handle) => handle,
             Err(error) => {
                 log::error!("Failed to install Windows mouse hook: {:?}", error);
-                WINDOWS_MOUSE_CONTEXT.store(null_mut(), Ordering::SeqCst);
+                let _ = WINDOWS_MOUSE_CONTEXT.compare_exchange(
+                    context_ptr,
+                    null_mut(),
+                    Ordering::SeqCst,
+                    Ordering::SeqCst,
+                );
                 drop(Box::from_raw(context_ptr));
                 return;
             }
         };
 
-        log::info!("Global selection monitor started (Windows mouse hook)"); // 启动监听线程（Windows）
-
-        let mut message = MSG::default();
-        while GetMessageW(&mut message, None, 0, 0).into() {
-            let _ = TranslateMessage(&message);
-            DispatchMessageW(&message);
+        WINDOWS_LISTENER_THREAD_ID.store(GetCurrentThreadId(), Ordering::SeqCst);
+        // 停止请求可能在钩子安装期间到达，此时直接退出
+        let stopped = LISTENER_GENERATION.load(Ordering::SeqCst) != generation;
+        if !stopped {
+            LISTENER_RUNNING.store(true, Ordering::SeqCst);
+            log::info!("Global selection monitor started (Windows mouse hook)"); // 启动监听线程（Windows）
+
+            let mut message = MSG::default();
+            while GetMessageW(&mut message, None, 0, 0).into() {
+                let _ = TranslateMessage(&message);
+                DispatchMessageW(&message);
+            }
         }
 
+        // 已被停止的旧线程不能覆盖新监听线程的状态
+        if LISTENER_GENERATION.load(Ordering::SeqCst) == generation {
+            LISTENER_RUNNING.store(false, Ordering::SeqCst);
+        }
+        let _ = WINDOWS_LISTENER_THREAD_ID.compare_exchange(
+            GetCurrentThreadId(),
+            0,
+            Ordering::SeqCst,
+            Ordering::SeqCst,
+        );
         if let Err(error) = UnhookWindowsHookEx(hook) {
             log::error!("Failed to unhook Windows mouse hook: {:?}", error);
         }
-        WINDOWS_MOUSE_CONTEXT.store(null_mut(), Ordering::SeqCst);
+        let _ = WINDOWS_MOUSE_CONTEXT.compare_exchange(
+            context_ptr,
+            null_mut(),
+            Ordering::SeqCst,
+            Ordering::SeqCst,
+        );
         drop(Box::from_raw(context_ptr));
+        log::info!("Global selection monitor hook thread exited");
     });
 }
 
@@ -959,15 +1940,33 @@ struct CaptureResetGuard {
 
 impl Drop for CaptureResetGuard {
     fn drop(&mut self) {
-        if let Ok(mut state) = self.state.lock() {
-            state.capture_in_progress = false;
-        }
+        lock_or_recover(&self.state, "global selection state").capture_in_progress = false;
     }
 }
 
+/// 输入事件来源
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EventOrigin {
+    /// 系统输入钩子
+    Hook,
+    /// 链路自检注入的合成事件：不计入监听统计，也不因主窗口聚焦（自检从主窗口发起）而被忽略
+    SelfTest,
+}
+
+/// 主窗口或工具栏自身是否处于焦点
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+fn own_window_focused(app: &AppHandle) -> bool {
+    resolve_main_window(app).is_some_and(|window| window.is_focused().unwrap_or(false))
+        || app
+            .get_webview_window("selection-toolbar")
+            .is_some_and(|toolbar| toolbar.is_focused().unwrap_or(false))
+}
+
 /// 事件核心处理：
 /// - 鼠标移动：只更新坐标并返回；
-/// - 左键抬起：触发一次捕获流程；
+/// - 左键按下：记录起点，用于最小拖拽距离与双击判断；
+/// - 左键抬起：移动距离超过阈值或为双击时触发一次捕获流程；
 /// - 键盘事件（macOS）：直接忽略，避免输入干扰。
 ///
 /// 性能优化说明：
@@ -981,6 +1980,7 @@ fn handle_event(
     toolbar_manager: &ToolbarManager,
     monitor_state: &Arc<Mutex<MonitorState>>,
     providers: &Arc<ProviderList>,
+    origin: EventOrigin,
 ) {
     #[cfg(target_os = "macos")]
     if matches!(
@@ -990,14 +1990,24 @@ fn handle_event(
         return;
     }
 
+    if origin == EventOrigin::Hook {
+        record_monitor_event(event.time);
+    }
+
     // 鼠标移动：使用 try_lock 避免阻塞，失败则丢弃（高频事件可容忍丢失）
     if let EventType::MouseMove { x, y } = event.event_type {
-        if let Ok(mut state) = monitor_state.try_lock() {
+        if let Some(mut state) = try_lock_or_recover(monitor_state, "global selection state") {
             state.last_mouse_position = (x, y);
         }
         return;
     }
 
+    // 左键按下：记录起点与时间，用于抬起时判断拖拽距离/双击
+    if matches!(event.event_type, EventType::ButtonPress(Button::Left)) {
+        lock_or_recover(monitor_state, "global selection state").record_press(Instant::now());
+        return;
+    }
+
     if !matches!(event.event_type, EventType::ButtonRelease(Button::Left)) {
         return;
     }
@@ -1017,25 +2027,25 @@ fn handle_event(
         return;
     }
 
-    // 忽略主窗口自身的选中（仅响应外部应用）
+    // 忽略主窗口与工具栏自身的选中（仅响应外部应用）；工具栏可能因键盘导航获得焦点
     // 注意：此检查放在锁操作之前，因为 is_focused 可能有一定开销
-    if let Some(window) = resolve_main_window(app) {
-        if window.is_focused().unwrap_or(false) {
-            return;
-        }
+    if origin == EventOrigin::Hook && own_window_focused(app) {
+        return;
     }
 
     // 合并去抖检查和并发保护为单次锁获取，减少锁竞争
     {
-        let mut state = match monitor_state.try_lock() {
-            Ok(guard) => guard,
-            Err(_) => {
-                // 锁被占用，说明有其他操作正在进行，跳过本次触发
-                log::debug!("Monitor state lock busy, skipping this trigger");
-                return;
-            }
+        let Some(mut state) = try_lock_or_recover(monitor_state, "global selection state") else {
+            // 锁被占用，说明有其他操作正在进行，跳过本次触发
+            log::debug!("Monitor state lock busy, skipping this trigger");
+            return;
         };
 
+        // 微小拖拽（点击时的轻微抖动）不视为划词
+        if !state.is_selection_gesture() {
+            return;
+        }
+
         let now = Instant::now();
 
         // 去抖处理：若与上次触发间隔小于阈值则跳过
@@ -1062,6 +2072,10 @@ fn handle_event(
     let state_task = Arc::clone(monitor_state);
     let providers_task = Arc::clone(providers);
 
+    if origin == EventOrigin::Hook {
+        CAPTURES_ATTEMPTED.fetch_add(1, Ordering::Relaxed);
+    }
+
     // 启动异步任务：避免阻塞输入钩子线程
     tauri::async_runtime::spawn(async move {
         // 守卫：确保任务结束后复位并发标记
@@ -1074,7 +2088,7 @@ fn handle_event(
         let capture_app = app_task.clone();
         let capture_providers = Arc::clone(&providers_task);
         let capture_task = tauri::async_runtime::spawn_blocking(move || {
-            capture_with_providers(&capture_app, &capture_providers)
+            capture_tracking_truncation(|| capture_with_providers(&capture_app, &capture_providers))
         });
 
         // 使用 tokio::time::timeout 添加超时保护
@@ -1082,11 +2096,11 @@ fn handle_event(
             tokio::time::timeout(Duration::from_millis(CAPTURE_TIMEOUT_MS), capture_task).await;
 
         // 处理捕获结果（包括超时情况）
-        let selected_text = match capture_result {
-            Ok(Ok(text)) => text,
+        let (selected_text, truncated) = match capture_result {
+            Ok(Ok(captured)) => captured,
             Ok(Err(error)) => {
                 log::error!("Global selection capture task panicked: {}", error);
-                None
+                (None, false)
             }
             Err(_) => {
                 // 捕获超时，这通常意味着 UIA/Accessibility API 卡住了
@@ -1095,7 +2109,7 @@ fn handle_event(
                     "Global selection capture timed out after {} ms, skipping",
                     CAPTURE_TIMEOUT_MS
                 );
-                None
+                (None, false)
             }
         };
 
@@ -1104,27 +2118,18 @@ fn handle_event(
             schedule_hide_toolbar(&app_task, toolbar_task.clone());
             return;
         };
+        if origin == EventOrigin::Hook {
+            CAPTURES_SUCCEEDED.fetch_add(1, Ordering::Relaxed);
+        }
 
         log::debug!(
-            "Global selection detected: {} characters (preview: \"{}\")",
-            selected_text.len(),
-            selected_text
-                .chars()
-                .take(50)
-                .collect::<String>()
-                .replace('\n', " ")
-                .replace('\r', "")
+            "Global selection detected: {}",
+            capture_log_preview(&selected_text, 50)
         );
 
         // 避免重复：与上次文本相同则跳过；否则使用最近记录的鼠标坐标
         let maybe_position = {
-            let mut state = match state_task.lock() {
-                Ok(guard) => guard,
-                Err(err) => {
-                    log::error!("Failed to lock global selection state: {}", err);
-                    return;
-                }
-            };
+            let mut state = lock_or_recover(&state_task, "global selection state");
 
             let is_duplicate = state
                 .last_text
@@ -1147,27 +2152,590 @@ fn handle_event(
             return;
         };
 
-        if let Err(error) =
-            show_selection_toolbar_with_manager(app_task, selected_text, position, toolbar_task)
-                .await
+        if truncated {
+            mark_toolbar_capture_truncated(&toolbar_task);
+        }
+        // 只有通过忽略列表、禁用状态等检查的文本才写入划词历史
+        match show_selection_toolbar_with_manager(
+            app_task,
+            selected_text.clone(),
+            position,
+            toolbar_task,
+        )
+        .await
         {
-            log::error!(
+            Ok(true) => lock_or_recover(&state_task, "global selection state")
+                .record_capture(&selected_text),
+            Ok(false) => {}
+            Err(error) => log::error!(
                 "Failed to show selection toolbar from global monitor: {}",
                 error
+            ),
+        }
+    });
+}
+
+/// 记录最近一次成功捕获文本的来源
+fn record_capture_provider(name: &str) {
+    *lock_or_recover(&LAST_CAPTURE_PROVIDER, "capture provider") = Some(name.to_string());
+}
+
+/// 模拟一次划词：把文本当作 `provider_name` 捕获的结果，走完整的工具栏展示流程
+///
+/// 更新监听器的最近文本、划词历史与捕获统计，供前端端到端测试在无辅助功能权限、
+/// 无真实前台应用时驱动工具栏。仅 debug 构建或设置了 `AI_ASK_SELECTION_TEST` 时可用。
+#[tauri::command]
+pub async fn simulate_selection(
+    app: AppHandle,
+    text: String,
+    x: f64,
+    y: f64,
+    provider_name: String,
+) -> Result<(), String> {
+    let test_enabled = cfg!(debug_assertions)
+        || std::env::var_os(SELECTION_TEST_ENV).is_some_and(|value| value != "0");
+    if !test_enabled {
+        return Err(format!(
+            "Selection simulation is only available in debug builds or with {} set",
+            SELECTION_TEST_ENV
+        ));
+    }
+    if !x.is_finite() || !y.is_finite() {
+        return Err("Invalid selection position".to_string());
+    }
+
+    CAPTURES_ATTEMPTED.fetch_add(1, Ordering::Relaxed);
+    let trimmed = text.trim();
+    if trimmed
+        .chars()
+        .filter(|character| !character.is_whitespace())
+        .count()
+        < MIN_TEXT_LENGTH
+    {
+        return Err("Simulated selection text is too short".to_string());
+    }
+    let (captured, truncated) =
+        capture_tracking_truncation(|| Some(limit_capture_length(trimmed).to_string()));
+    let Some(captured) = captured else {
+        return Ok(());
+    };
+    CAPTURES_SUCCEEDED.fetch_add(1, Ordering::Relaxed);
+    record_capture_provider(&provider_name);
+    log::info!(
+        "Simulated selection from provider {}: {}",
+        provider_name,
+        capture_log_preview(&captured, 50)
+    );
+
+    if let Some(monitor_state) = lock_or_recover(&ACTIVE_MONITOR_STATE, "monitor registry").as_ref()
+    {
+        lock_or_recover(monitor_state, "global selection state").last_text = Some(captured.clone());
+    }
+
+    let toolbar_manager = app.state::<ToolbarManager>().inner().clone();
+    if truncated {
+        mark_toolbar_capture_truncated(&toolbar_manager);
+    }
+    let accepted = show_selection_toolbar_with_manager(
+        app,
+        captured.clone(),
+        CursorPosition { x, y },
+        toolbar_manager,
+    )
+    .await?;
+    if accepted {
+        record_hotkey_capture(&captured);
+    }
+    Ok(())
+}
+
+/// 自检使用的固定选中文本
+const SELF_TEST_TEXT: &str = "AI Ask selection self-test";
+/// 自检时等待工具栏窗口显示的时间（毫秒）
+const SELF_TEST_TOOLBAR_WAIT_MS: u64 = 300;
+
+/// 自检阶段结果
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SelfTestStatus {
+    Passed,
+    Failed,
+    Skipped,
+    /// 焦点位于密码/安全输入框，捕获按隐私保护被跳过（不视为失败）
+    #[serde(rename = "secureField")]
+    #[cfg_attr(not(any(target_os = "windows", target_os = "macos")), allow(dead_code))]
+    SecureField,
+}
+
+/// 单个自检阶段
+#[derive(Debug, Serialize)]
+pub struct SelfTestStage {
+    pub name: &'static str,
+    pub status: SelfTestStatus,
+    pub detail: Option<String>,
+}
+
+impl SelfTestStage {
+    fn from_result(name: &'static str, result: Result<Option<String>, String>) -> Self {
+        match result {
+            Ok(detail) => Self {
+                name,
+                status: SelfTestStatus::Passed,
+                detail,
+            },
+            Err(detail) => Self {
+                name,
+                status: SelfTestStatus::Failed,
+                detail: Some(detail),
+            },
+        }
+    }
+
+    fn skipped(name: &'static str, detail: &str) -> Self {
+        Self {
+            name,
+            status: SelfTestStatus::Skipped,
+            detail: Some(detail.to_string()),
+        }
+    }
+
+    #[cfg(any(target_os = "windows", target_os = "macos"))]
+    fn secure_field(name: &'static str) -> Self {
+        Self {
+            name,
+            status: SelfTestStatus::SecureField,
+            detail: Some("secure input field focused; capture skipped".to_string()),
+        }
+    }
+}
+
+/// 划词链路自检报告
+#[derive(Debug, Serialize)]
+pub struct SelectionPipelineSelfTest {
+    /// 所有未跳过的阶段均通过
+    pub passed: bool,
+    pub stages: Vec<SelfTestStage>,
+}
+
+/// 自检用 provider：始终返回固定文本，使自检不依赖当前前台应用是否可捕获
+///
+/// 安全输入框检测仍交给平台 provider，与真实捕获一样受隐私保护约束。
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+struct SelfTestProvider {
+    platform: ProviderList,
+}
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+impl GlobalSelectionProvider for SelfTestProvider {
+    fn name(&self) -> &'static str {
+        "SelfTest"
+    }
+
+    fn capture(&self, _app: &AppHandle) -> Option<String> {
+        Some(SELF_TEST_TEXT.to_string())
+    }
+
+    fn secure_field_focused(&self) -> bool {
+        self.platform
+            .iter()
+            .any(|provider| provider.secure_field_focused())
+    }
+}
+
+/// 自检等待合成划词的捕获任务结束的上限（毫秒）
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+const SELF_TEST_CAPTURE_WAIT_MS: u64 = CAPTURE_TIMEOUT_MS + 500;
+
+/// 经 `handle_event` 注入一次从 `from` 到 `to` 的合成按下/移动/抬起序列
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+fn feed_synthetic_gesture(
+    app: &AppHandle,
+    toolbar_manager: &ToolbarManager,
+    state: &Arc<Mutex<MonitorState>>,
+    providers: &Arc<ProviderList>,
+    from: (f64, f64),
+    to: (f64, f64),
+) {
+    let events = [
+        EventType::MouseMove {
+            x: from.0,
+            y: from.1,
+        },
+        EventType::ButtonPress(Button::Left),
+        EventType::MouseMove { x: to.0, y: to.1 },
+        EventType::ButtonRelease(Button::Left),
+    ];
+    for event_type in events {
+        let event = Event {
+            event_type,
+            name: None,
+            time: SystemTime::now(),
+        };
+        handle_event(
+            event,
+            app,
+            toolbar_manager,
+            state,
+            providers,
+            EventOrigin::SelfTest,
+        );
+    }
+}
+
+/// 经 `handle_event` 注入合成点击与拖选，验证手势判定与触发逻辑
+///
+/// 点击不应触发捕获；拖选应触发，并返回其监听状态供捕获阶段确认结果。
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+fn self_test_gesture(
+    app: &AppHandle,
+    toolbar_manager: &ToolbarManager,
+    providers: &Arc<ProviderList>,
+) -> Result<(Option<String>, Arc<Mutex<MonitorState>>), String> {
+    if !lock_or_recover(toolbar_manager, "toolbar state").is_enabled() {
+        return Err("selection toolbar feature is disabled".to_string());
+    }
+    let threshold = f64::from(MIN_DRAG_DISTANCE_PX.load(Ordering::Relaxed));
+    let origin = platform_cursor_position()?;
+
+    let click = Arc::new(Mutex::new(MonitorState::default()));
+    feed_synthetic_gesture(app, toolbar_manager, &click, providers, origin, origin);
+    if lock_or_recover(&click, "self-test state")
+        .last_trigger_at
+        .is_some()
+    {
+        return Err("synthetic click was recognized as a selection".to_string());
+    }
+
+    let drag = Arc::new(Mutex::new(MonitorState::default()));
+    let target = (origin.0 + threshold + 20.0, origin.1);
+    feed_synthetic_gesture(app, toolbar_manager, &drag, providers, origin, target);
+    if lock_or_recover(&drag, "self-test state")
+        .last_trigger_at
+        .is_none()
+    {
+        return Err("synthetic drag was not recognized as a selection".to_string());
+    }
+
+    Ok((Some(format!("min drag distance {}px", threshold)), drag))
+}
+
+/// 等待合成拖选触发的捕获任务结束并检查捕获结果
+///
+/// 焦点位于安全输入框时返回独立的 `SecureField` 结果，而不是捕获失败。
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+async fn self_test_capture(
+    state: &Arc<Mutex<MonitorState>>,
+    providers: &Arc<ProviderList>,
+) -> SelfTestStage {
+    let deadline = Instant::now() + Duration::from_millis(SELF_TEST_CAPTURE_WAIT_MS);
+    while lock_or_recover(state, "self-test state").capture_in_progress {
+        if Instant::now() >= deadline {
+            return SelfTestStage::from_result(
+                "capture",
+                Err("capture pipeline did not finish in time".to_string()),
             );
         }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    let captured = lock_or_recover(state, "self-test state").last_text.clone();
+    match captured {
+        Some(text) if text == SELF_TEST_TEXT => SelfTestStage::from_result("capture", Ok(None)),
+        Some(_) => SelfTestStage::from_result(
+            "capture",
+            Err("captured text does not match the stub provider".to_string()),
+        ),
+        None if providers
+            .iter()
+            .any(|provider| provider.secure_field_focused()) =>
+        {
+            SelfTestStage::secure_field("capture")
+        }
+        None => SelfTestStage::from_result(
+            "capture",
+            Err("capture pipeline returned no text".to_string()),
+        ),
+    }
+}
+
+/// 以固定文本显示工具栏并确认窗口可见，随后隐藏
+async fn self_test_toolbar(
+    app: &AppHandle,
+    toolbar_manager: ToolbarManager,
+) -> Result<Option<String>, String> {
+    if !lock_or_recover(&toolbar_manager, "toolbar state").is_enabled() {
+        return Err("selection toolbar feature is disabled".to_string());
+    }
+
+    let (x, y) = platform_cursor_position()?;
+    show_selection_toolbar_force_with_manager(
+        app.clone(),
+        SELF_TEST_TEXT.to_string(),
+        CursorPosition { x, y },
+        toolbar_manager.clone(),
+    )
+    .await?;
+    tokio::time::sleep(Duration::from_millis(SELF_TEST_TOOLBAR_WAIT_MS)).await;
+
+    let visible = app
+        .get_webview_window("selection-toolbar")
+        .and_then(|window| window.is_visible().ok())
+        .unwrap_or(false);
+    if let Err(error) = hide_selection_toolbar_with_manager(app.clone(), toolbar_manager).await {
+        log::warn!("Failed to hide toolbar after self-test: {}", error);
+    }
+
+    if visible {
+        Ok(None)
+    } else {
+        Err("toolbar window is not visible after show".to_string())
+    }
+}
+
+/// 端到端自检划词链路（监听 → 手势判定 → 捕获 → 工具栏），逐阶段返回结果
+///
+/// 手势与捕获阶段把合成的鼠标事件送入 `handle_event`，捕获使用返回固定文本的自检 provider，
+/// 因此结果不受当前前台应用是否可捕获的影响，便于支持人员确认链路本身是否正常。
+/// 焦点位于安全输入框时捕获阶段报告 `secureField`，不计为失败。
+#[tauri::command]
+pub async fn self_test_selection_pipeline(
+    app: AppHandle,
+) -> Result<SelectionPipelineSelfTest, String> {
+    let supported = cfg!(any(target_os = "windows", target_os = "macos"));
+    let unsupported = "Global selection monitor is not available on this platform";
+    let mut stages = Vec::new();
+
+    stages.push(if supported {
+        SelfTestStage::from_result(
+            "listener",
+            if LISTENER_RUNNING.load(Ordering::SeqCst) {
+                Ok(None)
+            } else {
+                Err("selection listener is not running".to_string())
+            },
+        )
+    } else {
+        SelfTestStage::skipped("listener", unsupported)
+    });
+
+    let toolbar_manager = app.state::<ToolbarManager>().inner().clone();
+
+    #[cfg(any(target_os = "windows", target_os = "macos"))]
+    {
+        let providers: Arc<ProviderList> = Arc::new(vec![Box::new(SelfTestProvider {
+            platform: build_providers(),
+        })]);
+        match self_test_gesture(&app, &toolbar_manager, &providers) {
+            Ok((detail, state)) => {
+                stages.push(SelfTestStage::from_result("gesture", Ok(detail)));
+                stages.push(self_test_capture(&state, &providers).await);
+            }
+            Err(error) => {
+                stages.push(SelfTestStage::from_result("gesture", Err(error)));
+                stages.push(SelfTestStage::skipped(
+                    "capture",
+                    "gesture stage did not trigger a capture",
+                ));
+            }
+        }
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        stages.push(SelfTestStage::skipped("gesture", unsupported));
+        stages.push(SelfTestStage::skipped("capture", unsupported));
+    }
+
+    stages.push(SelfTestStage::from_result(
+        "toolbar",
+        self_test_toolbar(&app, toolbar_manager).await,
+    ));
+
+    let passed = stages
+        .iter()
+        .all(|stage| stage.status != SelfTestStatus::Failed);
+    log::info!(
+        "Selection pipeline self-test finished: passed={}, stages={:?}",
+        passed,
+        stages
+            .iter()
+            .map(|stage| (stage.name, stage.status))
+            .collect::<Vec<_>>()
+    );
+    Ok(SelectionPipelineSelfTest { passed, stages })
+}
+
+/// 诊断时保留的捕获文本预览字符数
+const DIAGNOSTIC_PREVIEW_CHARS: usize = 8;
+
+/// 诊断时等待用户切换到目标应用的最长延迟（毫秒）
+const DIAGNOSTIC_MAX_DELAY_MS: u64 = 10_000;
+
+/// 捕获文本的脱敏摘要：仅保留长度与开头少量字符
+#[derive(Debug, Serialize)]
+pub struct CapturePreview {
+    pub length: usize,
+    pub preview: String,
+}
+
+/// 单个 provider 的诊断结果
+#[derive(Debug, Serialize)]
+pub struct ProviderDiagnostic {
+    pub name: &'static str,
+    pub captured: Option<CapturePreview>,
+    pub error: Option<String>,
+    pub elapsed_ms: u64,
+}
+
+/// 针对当前前台应用的捕获诊断报告
+#[derive(Debug, Serialize)]
+pub struct CaptureDiagnostics {
+    pub app_identifiers: Vec<String>,
+    pub providers: Vec<ProviderDiagnostic>,
+}
+
+fn capture_preview(text: &str) -> CapturePreview {
+    let length = text.chars().count();
+    let mut preview: String = text.chars().take(DIAGNOSTIC_PREVIEW_CHARS).collect();
+    if length > DIAGNOSTIC_PREVIEW_CHARS {
+        preview.push('…');
+    }
+    CapturePreview { length, preview }
+}
+
+/// 逐个运行 provider 并记录结果，不因某个 provider 成功而提前返回
+fn diagnose_providers(app: &AppHandle, providers: &ProviderList) -> Vec<ProviderDiagnostic> {
+    providers
+        .iter()
+        .map(|provider| {
+            let started = Instant::now();
+            let (captured, error) = if provider.secure_field_focused() {
+                (
+                    None,
+                    Some("Secure input field focused; capture skipped".to_string()),
+                )
+            } else {
+                (provider.capture(app).as_deref().map(capture_preview), None)
+            };
+
+            ProviderDiagnostic {
+                name: provider.name(),
+                captured,
+                error,
+                elapsed_ms: started.elapsed().as_millis().try_into().unwrap_or(u64::MAX),
+            }
+        })
+        .collect()
+}
+
+/// 导出当前前台应用的捕获诊断信息
+///
+/// 对每个 provider 单独执行一次捕获，返回脱敏后的结果、耗时以及解析到的应用标识，
+/// 便于排查“某应用无法划词”的问题。`delay_ms` 用于给用户留出切换到目标应用的时间。
+#[tauri::command]
+pub async fn diagnose_capture_for_active_app(
+    app: AppHandle,
+    delay_ms: Option<u64>,
+) -> Result<CaptureDiagnostics, String> {
+    if let Some(delay) = delay_ms {
+        tokio::time::sleep(Duration::from_millis(delay.min(DIAGNOSTIC_MAX_DELAY_MS))).await;
+    }
+
+    let diagnostics = tauri::async_runtime::spawn_blocking(move || {
+        let providers = build_providers();
+        CaptureDiagnostics {
+            app_identifiers: resolve_active_app_identifiers(),
+            providers: diagnose_providers(&app, &providers),
+        }
     });
+
+    let diagnostics =
+        tokio::time::timeout(Duration::from_millis(CAPTURE_TIMEOUT_MS * 2), diagnostics)
+            .await
+            .map_err(|_| "Capture diagnostics timed out".to_string())?
+            .map_err(|e| format!("Capture diagnostics task failed: {}", e))?;
+
+    log::info!(
+        "Capture diagnostics collected for {} provider(s)",
+        diagnostics.providers.len()
+    );
+    Ok(diagnostics)
+}
+
+/// 选区外接矩形查询结果
+#[derive(Debug, Serialize)]
+pub struct SelectionBounds {
+    /// 选区矩形；回退到光标时为光标处的零尺寸矩形
+    pub rect: Rect,
+    /// 是否因 provider 不支持而回退到光标位置
+    pub from_cursor: bool,
+}
+
+/// 获取当前选区在屏幕上的外接矩形
+///
+/// 供工具栏锚定到选区边缘而非原始光标位置；provider 无法提供矩形（或焦点为密码框）时
+/// 回退为光标位置处的零尺寸矩形。
+#[tauri::command]
+pub async fn get_selection_bounds() -> Result<SelectionBounds, String> {
+    let task = tauri::async_runtime::spawn_blocking(|| {
+        let providers = build_providers();
+        if providers
+            .iter()
+            .any(|provider| provider.secure_field_focused())
+        {
+            return None;
+        }
+        providers
+            .iter()
+            .find_map(|provider| provider.selection_bounds())
+    });
+
+    let bounds = tokio::time::timeout(Duration::from_millis(CAPTURE_TIMEOUT_MS), task)
+        .await
+        .ok()
+        .and_then(|result| result.ok())
+        .flatten();
+
+    if let Some(rect) = bounds {
+        return Ok(SelectionBounds {
+            rect,
+            from_cursor: false,
+        });
+    }
+
+    let (x, y) = platform_cursor_position()?;
+    Ok(SelectionBounds {
+        rect: Rect {
+            x,
+            y,
+            width: 0.0,
+            height: 0.0,
+        },
+        from_cursor: true,
+    })
 }
 
 /// 依优先级顺序使用各 provider 尝试捕获文本；第一个成功即返回，否则 None
 #[cfg(any(target_os = "windows", target_os = "macos"))]
 fn capture_with_providers(app: &AppHandle, providers: &ProviderList) -> Option<String> {
+    // 隐私保护：焦点位于密码/安全输入框时无条件跳过捕获（不可关闭）
+    if let Some(provider) = providers
+        .iter()
+        .find(|provider| provider.secure_field_focused())
+    {
+        log::info!(
+            "Skipped selection capture: secure input field focused (detected by {})",
+            provider.name()
+        );
+        return None;
+    }
+
     for provider in providers.iter() {
         if let Some(text) = provider.capture(app) {
             log::debug!(
                 "Global selection provider {} captured text successfully",
                 provider.name()
             );
+            record_capture_provider(provider.name());
             return Some(text);
         }
     }
@@ -1200,22 +2768,38 @@ fn capture_with_providers(_app: &AppHandle, _providers: &ProviderList) -> Option
 ///
 /// # 返回值
 ///
-/// - `Some(String)`: 成功捕获的有效文本（来自系统或剪贴板）
+/// - `Some(HotkeyCapture)`: 成功捕获的有效文本（来自系统或剪贴板）
 /// - `None`: 无法获取任何有效文本
-fn capture_text_for_hotkey(app: &AppHandle) -> Option<String> {
+fn capture_text_for_hotkey(app: &AppHandle) -> Option<HotkeyCapture> {
     // 步骤 1: 优先使用系统原生 provider 捕获选中文本
     let providers = build_providers();
     if let Some(text) = capture_with_providers(app, &providers) {
-        return Some(text);
+        return Some(HotkeyCapture {
+            text,
+            record_history: true,
+        });
     }
 
     // 步骤 2: 系统捕获失败，尝试从剪贴板读取作为回退方案
-    let clipboard_text = read_clipboard_text();
-    if clipboard_text.is_some() {
-        log::debug!("Hotkey fallback captured text from clipboard");
-    }
+    let text = read_clipboard_text()?;
+    log::debug!("Hotkey fallback captured text from clipboard");
+    record_capture_provider("clipboard");
+
+    // 焦点在密码框时剪贴板里很可能是刚复制的密码，不写入划词历史
+    let secure_field_focused = providers
+        .iter()
+        .any(|provider| provider.secure_field_focused());
+    Some(HotkeyCapture {
+        text,
+        record_history: !secure_field_focused,
+    })
+}
 
-    clipboard_text
+/// 快捷键捕获结果
+struct HotkeyCapture {
+    text: String,
+    /// 是否允许写入划词历史（密码框聚焦时的剪贴板回退不写入）
+    record_history: bool,
 }
 
 /// 从剪贴板读取文本并进行验证
@@ -1227,6 +2811,7 @@ fn capture_text_for_hotkey(app: &AppHandle) -> Option<String> {
 ///
 /// - 文本不能为空（去除首尾空白后）
 /// - 非空白字符数量必须 >= MIN_TEXT_LENGTH (2)
+/// - 超过最大捕获长度的部分会被截断
 ///
 /// # 返回值
 ///
@@ -1251,7 +2836,7 @@ fn read_clipboard_text() -> Option<String> {
                     return None;
                 }
 
-                Some(trimmed.to_string())
+                Some(limit_capture_length(trimmed).to_string())
             }
             Err(error) => {
                 log::debug!("Clipboard text read failed: {}", error);
@@ -1265,6 +2850,11 @@ fn read_clipboard_text() -> Option<String> {
     }
 }
 
+/// 标记下一次工具栏展示的文本已被截断，由 `toolbar-text-selected` 事件告知前端
+fn mark_toolbar_capture_truncated(toolbar_manager: &ToolbarManager) {
+    lock_or_recover(toolbar_manager, "toolbar state").mark_capture_truncated();
+}
+
 /// 异步隐藏工具栏（不阻塞当前线程）
 fn schedule_hide_toolbar(app: &AppHandle, toolbar_manager: ToolbarManager) {
     let app_handle = app.clone();
@@ -1314,15 +2904,17 @@ pub fn trigger_toolbar_from_hotkey(app: AppHandle, toolbar_manager: ToolbarManag
             };
 
             // 检查当前活动应用是否在忽略名单中
-            // 注意：只有在功能开启时才检查，避免无意义的系统调用
-            let ignore_active_app = if enabled && !temporarily_disabled {
-                let identifiers = resolve_active_app_identifiers();
-                identifiers
-                    .iter()
-                    .any(|identifier| state.should_ignore_app(identifier))
-            } else {
-                false
-            };
+            // 注意：只有在功能开启时才检查，避免无意义的系统调用；
+            // 设置了一次性绕过时交由展示逻辑消费标记
+            let ignore_active_app =
+                if enabled && !temporarily_disabled && !state.bypass_ignore_once() {
+                    let identifiers = resolve_active_app_identifiers();
+                    identifiers
+                        .iter()
+                        .any(|identifier| state.should_ignore_app(identifier))
+                } else {
+                    false
+                };
 
             (enabled, temporarily_disabled, ignore_active_app)
         }
@@ -1365,17 +2957,18 @@ pub fn trigger_toolbar_from_hotkey(app: AppHandle, toolbar_manager: ToolbarManag
         // 步骤 5.1: 在阻塞线程池中执行文本捕获，添加超时保护
         // 原因：Windows UIA / macOS Accessibility API 可能耗时较长
         // 使用 spawn_blocking 避免阻塞异步运行时
-        let capture_task =
-            tauri::async_runtime::spawn_blocking(move || capture_text_for_hotkey(&capture_app));
+        let capture_task = tauri::async_runtime::spawn_blocking(move || {
+            capture_tracking_truncation(|| capture_text_for_hotkey(&capture_app))
+        });
 
         // 添加超时保护，防止 API 卡死
         let capture_result =
             tokio::time::timeout(Duration::from_millis(CAPTURE_TIMEOUT_MS), capture_task).await;
 
         // 步骤 5.2: 处理捕获结果（包括超时情况）
-        let selected_text = match capture_result {
-            Ok(Ok(Some(text))) => text,
-            Ok(Ok(None)) => {
+        let (capture, truncated) = match capture_result {
+            Ok(Ok((Some(capture), truncated))) => (capture, truncated),
+            Ok(Ok((None, _))) => {
                 // 系统 provider 和剪贴板都没有可用文本，隐藏工具栏
                 log::debug!("Hotkey trigger skipped: no provider or clipboard text available");
                 schedule_hide_toolbar(&app_clone, toolbar_for_hide);
@@ -1398,6 +2991,14 @@ pub fn trigger_toolbar_from_hotkey(app: AppHandle, toolbar_manager: ToolbarManag
             }
         };
 
+        let HotkeyCapture {
+            text: selected_text,
+            record_history,
+        } = capture;
+        if truncated {
+            mark_toolbar_capture_truncated(&toolbar_manager_clone);
+        }
+
         // 步骤 5.3: 获取当前光标位置，用于定位工具栏
         let position = match platform_cursor_position() {
             Ok((x, y)) => CursorPosition { x, y },
@@ -1412,15 +3013,17 @@ pub fn trigger_toolbar_from_hotkey(app: AppHandle, toolbar_manager: ToolbarManag
         // 步骤 5.4: 调用强制展示函数，绕过临时禁用状态
         // 使用 show_selection_toolbar_force_with_manager 而非普通展示函数
         // 确保即使在临时禁用期间，快捷键仍能唤起工具栏
-        if let Err(error) = show_selection_toolbar_force_with_manager(
+        match show_selection_toolbar_force_with_manager(
             app_clone.clone(),
-            selected_text,
+            selected_text.clone(),
             position,
             toolbar_manager_clone.clone(),
         )
         .await
         {
-            log::error!("Failed to show selection toolbar from hotkey: {}", error);
+            Ok(true) if record_history => record_hotkey_capture(&selected_text),
+            Ok(_) => {}
+            Err(error) => log::error!("Failed to show selection toolbar from hotkey: {}", error),
         }
     });
 }
@@ -0,0 +1,217 @@
+//! 后端文案本地化模块
+//!
+//! 命令返回给前端的错误信息按界面语言输出，语言由前端通过 `set_backend_locale` 同步并持久化。
+//! 只收录面向用户的错误文案，日志保持英文；不认识的语言一律回退到英文。
+
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::settings;
+
+const SETTING_BACKEND_LOCALE: &str = "backendLocale";
+
+/// 后端文案语言
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub(crate) enum BackendLocale {
+    #[default]
+    #[serde(rename = "en")]
+    En,
+    #[serde(rename = "zh-CN")]
+    ZhCn,
+}
+
+impl BackendLocale {
+    /// 解析 BCP 47 风格的语言标签（`zh`、`zh-CN`、`zh_Hans`、`en-US` 等），无法识别时回退到英文
+    fn from_tag(tag: &str) -> Self {
+        let primary = tag
+            .trim()
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match primary.as_str() {
+            "zh" => Self::ZhCn,
+            _ => Self::En,
+        }
+    }
+
+    fn as_u8(self) -> u8 {
+        match self {
+            Self::En => 0,
+            Self::ZhCn => 1,
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::ZhCn,
+            _ => Self::En,
+        }
+    }
+
+    fn tag(self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::ZhCn => "zh-CN",
+        }
+    }
+}
+
+/// 当前生效的后端语言
+static BACKEND_LOCALE: AtomicU8 = AtomicU8::new(0);
+
+pub(crate) fn backend_locale() -> BackendLocale {
+    BackendLocale::from_u8(BACKEND_LOCALE.load(Ordering::Relaxed))
+}
+
+fn apply_backend_locale(locale: BackendLocale) {
+    BACKEND_LOCALE.store(locale.as_u8(), Ordering::Relaxed);
+}
+
+/// 启动时加载已保存的后端语言
+pub(crate) fn load_backend_locale(app: &AppHandle) {
+    if let Some(tag) = settings::load_setting::<String>(app, SETTING_BACKEND_LOCALE) {
+        let locale = BackendLocale::from_tag(&tag);
+        apply_backend_locale(locale);
+        log::debug!("Backend locale loaded: {}", locale.tag());
+    }
+}
+
+/// 设置后端文案语言，返回实际生效的语言（不支持的语言回退到英文）
+#[tauri::command]
+pub(crate) async fn set_backend_locale(
+    app: AppHandle,
+    locale: String,
+) -> Result<BackendLocale, String> {
+    let effective = BackendLocale::from_tag(&locale);
+    settings::save_setting(&app, SETTING_BACKEND_LOCALE, &effective.tag())?;
+    apply_backend_locale(effective);
+    log::info!(
+        "Backend locale set: requested={}, effective={}",
+        locale,
+        effective.tag()
+    );
+    Ok(effective)
+}
+
+/// 查询当前生效的后端文案语言
+#[tauri::command]
+pub(crate) async fn get_backend_locale() -> Result<BackendLocale, String> {
+    Ok(backend_locale())
+}
+
+/// 面向用户的命令错误信息
+///
+/// 通过 `Display`（即 `to_string()`）按当前后端语言输出。
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Message<'a> {
+    ProxyHostRequired,
+    ProxyPortRequired,
+    InvalidProxyPort(&'a str),
+    InvalidProxyUrl,
+    UnsupportedProxyType(&'a str),
+    UnsupportedProxyProtocol(&'a str),
+    UserAgentEmpty,
+    UserAgentInvalid,
+    UnsupportedUrlScheme(&'a str),
+    DownloadFailedStatus(u16),
+    NoPublishedRelease,
+    TargetReleaseNotFound,
+    TargetAssetNotFound,
+    DownloadTaskNotFound,
+    DownloadNotCompleted,
+    InstallerStillDownloading,
+    InstallerPathMissing,
+}
+
+impl Message<'_> {
+    fn localize(&self, locale: BackendLocale) -> String {
+        match locale {
+            BackendLocale::En => self.english(),
+            BackendLocale::ZhCn => self.chinese(),
+        }
+    }
+
+    fn english(&self) -> String {
+        match *self {
+            Self::ProxyHostRequired => "Proxy host is required".to_string(),
+            Self::ProxyPortRequired => "Proxy port is required".to_string(),
+            Self::InvalidProxyPort(port) => format!("Invalid proxy port: {port}"),
+            Self::InvalidProxyUrl => "Invalid proxy URL".to_string(),
+            Self::UnsupportedProxyType(kind) => format!("Unsupported proxy type: {kind}"),
+            Self::UnsupportedProxyProtocol(scheme) => {
+                format!("Unsupported proxy protocol: {scheme}")
+            }
+            Self::UserAgentEmpty => "User-Agent cannot be empty".to_string(),
+            Self::UserAgentInvalid => "User-Agent is not a valid header value".to_string(),
+            Self::UnsupportedUrlScheme(scheme) => format!("Unsupported URL scheme: {scheme}"),
+            Self::DownloadFailedStatus(status) => format!("Download failed, status {status}"),
+            Self::NoPublishedRelease => "No published release found".to_string(),
+            Self::TargetReleaseNotFound => "Target release not found".to_string(),
+            Self::TargetAssetNotFound => "Target release asset not found".to_string(),
+            Self::DownloadTaskNotFound => "Download task does not exist".to_string(),
+            Self::DownloadNotCompleted => "Download not completed".to_string(),
+            Self::InstallerStillDownloading => "Installer is still downloading".to_string(),
+            Self::InstallerPathMissing => "Downloaded installer path missing".to_string(),
+        }
+    }
+
+    fn chinese(&self) -> String {
+        match *self {
+            Self::ProxyHostRequired => "代理主机不能为空".to_string(),
+            Self::ProxyPortRequired => "代理端口不能为空".to_string(),
+            Self::InvalidProxyPort(port) => format!("代理端口无效：{port}"),
+            Self::InvalidProxyUrl => "代理地址无效".to_string(),
+            Self::UnsupportedProxyType(kind) => format!("不支持的代理类型：{kind}"),
+            Self::UnsupportedProxyProtocol(scheme) => format!("不支持的代理协议：{scheme}"),
+            Self::UserAgentEmpty => "User-Agent 不能为空".to_string(),
+            Self::UserAgentInvalid => "User-Agent 不是合法的请求头值".to_string(),
+            Self::UnsupportedUrlScheme(scheme) => format!("不支持的 URL 协议：{scheme}"),
+            Self::DownloadFailedStatus(status) => format!("下载失败，状态码 {status}"),
+            Self::NoPublishedRelease => "没有找到已发布的版本".to_string(),
+            Self::TargetReleaseNotFound => "未找到目标版本".to_string(),
+            Self::TargetAssetNotFound => "未找到目标版本的安装包".to_string(),
+            Self::DownloadTaskNotFound => "下载任务不存在".to_string(),
+            Self::DownloadNotCompleted => "下载尚未完成".to_string(),
+            Self::InstallerStillDownloading => "安装包仍在下载中".to_string(),
+            Self::InstallerPathMissing => "找不到已下载的安装包路径".to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Message<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.localize(backend_locale()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_locale_tags_with_english_fallback() {
+        assert_eq!(BackendLocale::from_tag("zh"), BackendLocale::ZhCn);
+        assert_eq!(BackendLocale::from_tag(" zh_Hans "), BackendLocale::ZhCn);
+        assert_eq!(BackendLocale::from_tag("ZH-TW"), BackendLocale::ZhCn);
+        assert_eq!(BackendLocale::from_tag("en-US"), BackendLocale::En);
+        assert_eq!(BackendLocale::from_tag("fr"), BackendLocale::En);
+        assert_eq!(BackendLocale::from_tag(""), BackendLocale::En);
+    }
+
+    #[test]
+    fn localizes_messages_with_arguments() {
+        let message = Message::UnsupportedProxyType("ftp");
+        assert_eq!(
+            message.localize(BackendLocale::En),
+            "Unsupported proxy type: ftp"
+        );
+        assert_eq!(
+            message.localize(BackendLocale::ZhCn),
+            "不支持的代理类型：ftp"
+        );
+    }
+}
@@ -6,14 +6,30 @@
 
 // 仅桌面平台编译的模块
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod app_paths;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod content_type;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
 mod desktop_notes;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 mod global_selection;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod i18n;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod log_redaction;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod process_usage;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
 mod proxy;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod screen;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
 mod selection_toolbar;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod settings;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod shortcuts;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
 mod update;
 mod utils;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
@@ -21,54 +37,101 @@ mod webview;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 mod window_control;
 
-pub use utils::{decode_base64, decode_base64url, decode_base64url_to_json};
+pub use utils::{
+    decode_base64, decode_base64url, decode_base64url_to_json, max_base64_input_len,
+    set_max_base64_input_len, sha256_digest_hex, DEFAULT_MAX_BASE64_INPUT_LEN,
+};
+use utils::{lock_or_recover, sha256_hex};
 
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
-use std::sync::{Arc, Mutex};
-#[cfg(not(any(target_os = "android", target_os = "ios")))]
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 use tauri::{
-    menu::{Menu, MenuItem},
-    tray::TrayIconEvent,
+    tray::{MouseButton, MouseButtonState, TrayIconEvent},
     Emitter, Listener, Manager, WindowEvent,
 };
 
 #[cfg(any(target_os = "android", target_os = "ios"))]
 use tauri::Manager;
 
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+use app_paths::get_app_paths;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 use desktop_notes::{close_desktop_note_window, ensure_desktop_note_window};
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
-use global_selection::{check_accessibility_permission, request_accessibility_permission};
+use global_selection::{
+    check_accessibility_permission, clear_capture_history, clear_captured_text,
+    diagnose_capture_for_active_app, get_capture_history, get_monitor_health, get_selection_bounds,
+    get_selection_capture_history_enabled, get_selection_max_capture_length,
+    get_selection_min_drag_distance, get_selection_simulated_copy, recheck_and_restart_monitor,
+    request_accessibility_permission, restart_selection_monitor, self_test_selection_pipeline,
+    set_selection_capture_history, set_selection_max_capture_length,
+    set_selection_min_drag_distance, set_selection_simulated_copy, simulate_selection,
+    stop_selection_monitor,
+};
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+use i18n::{get_backend_locale, set_backend_locale};
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+use log_redaction::{get_redact_capture_logs, set_redact_capture_logs};
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
-use proxy::test_proxy_connection;
+use proxy::{
+    get_supported_proxy_types, set_active_proxy, test_proxy_connection, validate_proxy_url,
+};
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+use screen::get_monitor_at_cursor;
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 use selection_toolbar::{
-    create_new_result_window_with_request, get_cursor_position, get_selection_toolbar_state,
-    hide_selection_result_window, hide_selection_toolbar, set_selection_toolbar_enabled,
-    set_selection_toolbar_ignored_apps, set_selection_toolbar_temporary_disabled_until,
-    show_selection_result_window, show_selection_toolbar, update_selection_result_position,
-    ToolbarManager,
+    bypass_ignore_list_once, create_new_result_window_with_request, dismiss_selection_toolbar,
+    focus_selection_toolbar, get_cursor_position, get_selection_toolbar_state,
+    get_temporary_disable_remaining_ms, get_toolbar_behavior, hide_selection_result_window,
+    hide_selection_toolbar, recreate_selection_toolbar, resize_selection_toolbar,
+    set_selection_toolbar_anchor, set_selection_toolbar_default_action,
+    set_selection_toolbar_enabled, set_selection_toolbar_fullscreen_boost,
+    set_selection_toolbar_ignored_apps, set_selection_toolbar_size_bounds,
+    set_selection_toolbar_temporary_disabled_until, set_selection_toolbar_window_level,
+    set_toolbar_behavior, set_toolbar_theme, show_selection_result_window, show_selection_toolbar,
+    update_selection_result_position, ToolbarManager,
+};
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+use shortcuts::{
+    get_shortcut_registration_status, parse_accelerator, retry_shortcut_registration,
+    ShortcutRegistry,
 };
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 use update::{
-    check_update, download_update, get_download_status, init as init_update, install_update_now,
-    schedule_install,
+    cancel_update_check, check_update, check_update_server_reachable, copy_release_notes,
+    delete_cached_update, download_update, get_app_integrity, get_changelog_since,
+    get_download_status, get_linux_package_format, get_raw_latest_release, get_update_mirrors,
+    hash_cached_update, init as init_update, install_update_now, list_cached_updates,
+    reset_update_state, reveal_downloaded_installer, schedule_install, set_update_mirrors,
+    test_download_throughput, test_launch_installer, validate_config,
 };
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 use webview::{
-    check_child_webview_exists, clear_child_webview_cache, close_child_webview,
-    ensure_child_webview, evaluate_child_webview_script, focus_child_webview,
-    hide_all_child_webviews, hide_child_webview, set_child_webview_bounds, show_child_webview,
-    ChildWebviewManager,
+    capture_child_webview_full_page, check_child_webview_exists, child_webview_hard_reload,
+    clear_child_webview_cache, close_child_webview, ensure_child_webview,
+    evaluate_child_webview_script, focus_child_webview, get_child_webview_devtools_info,
+    get_child_webview_idle_suspend, get_child_webview_incognito, get_child_webview_info,
+    get_child_webview_load_state, get_child_webviews_resource_usage, get_external_url_schemes,
+    hide_all_child_webviews, hide_child_webview, inject_child_webview_css,
+    is_child_webview_suspended, list_child_webviews, open_external_url, prewarm_child_webview,
+    read_injection_script, remove_child_webview_css, resume_child_webview,
+    set_all_child_webviews_zoom, set_child_webview_bounds, set_child_webview_idle_suspend,
+    set_child_webview_navigation_blocklist, set_child_webview_proxy, set_external_url_schemes,
+    show_child_webview, suspend_child_webview, ChildWebviewManager,
 };
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 use window_control::{
-    hide_main_window, hide_window, open_platform_in_main_window, resolve_main_window,
-    show_main_window, show_main_window_without_restore, show_window, toggle_main_window_visibility,
-    toggle_window,
+    apply_saved_main_window_decorations, build_saved_tray_menu, clear_user_attention, close_panel,
+    create_panel_window, focus_panel, get_main_window_state, get_minimize_to_tray,
+    get_system_theme, get_tray_click_action, get_tray_click_actions, handle_main_window_resized,
+    handle_tray_click, handle_tray_double_click, handle_tray_left_click, hide_main_window,
+    hide_window, open_platform_in_main_window, open_settings_in_main_window,
+    request_user_attention, resolve_active_panel_window, set_main_window_decorations,
+    set_minimize_to_tray, set_next_close_quits, set_tray_click_action, set_tray_menu_labels,
+    show_main_window, show_window, snap_main_window, toggle_window, PanelWindowManager,
+    TrayClickKind, WindowPreferences, MAIN_WINDOW_LABEL, TRAY_ID,
 };
 
 /// Enable auto launch on system startup (desktop only)
@@ -149,13 +212,40 @@ fn run_mobile() {
             log::info!("Mobile application setup completed");
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![])
+        .invoke_handler(tauri::generate_handler![sha256_hex])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 
     log::info!("AI Ask mobile application exited");
 }
 
+/// 退出应用前广播事件，给便签窗口/主窗口留出落盘与云同步机会
+///
+/// 等待前端发送 `app-exit-ready` 回执（表示所有便签位置已落盘、内容已同步），
+/// 最长等 3 秒超时，避免前端异常导致永久挂起。
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+async fn exit_after_frontend_flush(app_handle: tauri::AppHandle) {
+    let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+    let tx_shared = std::sync::Arc::new(std::sync::Mutex::new(Some(tx)));
+    let tx_for_listener = tx_shared.clone();
+    let listener_handle = app_handle.listen("app-exit-ready", move |_event| {
+        if let Ok(mut guard) = tx_for_listener.lock() {
+            if let Some(tx) = guard.take() {
+                let _ = tx.send(());
+            }
+        }
+    });
+    let _ = app_handle.emit("app-before-exit", ());
+
+    match tokio::time::timeout(Duration::from_secs(3), rx).await {
+        Ok(Ok(())) => log::info!("Received app-exit-ready from frontend"),
+        Ok(Err(_)) => log::warn!("app-exit-ready sender dropped"),
+        Err(_) => log::warn!("Timed out waiting for app-exit-ready (3s)"),
+    }
+    app_handle.unlisten(listener_handle);
+    app_handle.exit(0);
+}
+
 /// 桌面端应用初始化
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 fn run_desktop() {
@@ -164,6 +254,8 @@ fn run_desktop() {
     tauri::Builder::default()
         .manage(ChildWebviewManager::default())
         .manage(ToolbarManager::default())
+        .manage(PanelWindowManager::default())
+        .manage(ShortcutRegistry::default())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_store::Builder::default().build())
         .plugin(tauri_plugin_global_shortcut::Builder::new().build())
@@ -175,41 +267,56 @@ fn run_desktop() {
         .setup(|app| {
             log::debug!("Desktop application setup starting");
 
-            global_selection::start_global_selection_monitor(app.handle().clone());
+            app.manage(WindowPreferences::load(app.handle()));
+            app.manage(proxy::ActiveProxy::load(app.handle()));
+            apply_saved_main_window_decorations(app.handle());
+            window_control::restore_main_window_geometry(app.handle());
+            selection_toolbar::load_toolbar_preferences(app.handle());
+            log_redaction::load_capture_log_redaction(app.handle());
+            i18n::load_backend_locale(app.handle());
+            webview::init_external_url_schemes(app.handle());
+            webview::init_child_webview_zoom(app.handle());
+            webview::cleanup_incognito_data_dirs(app.handle());
+            webview::cleanup_screenshots(app.handle());
+            webview::start_idle_suspend_monitor(app.handle());
+
+            let toolbar_enabled =
+                lock_or_recover(app.state::<ToolbarManager>().inner(), "toolbar state")
+                    .is_enabled();
+            if toolbar_enabled {
+                global_selection::start_global_selection_monitor(app.handle().clone());
+            } else {
+                log::info!("Selection toolbar disabled; global selection monitor not started");
+            }
 
-            let show_item = MenuItem::with_id(app, "show", "显示主窗口", true, None::<&str>)?;
-            let settings_item = MenuItem::with_id(app, "settings", "偏好设置", true, None::<&str>)?;
-            let quit_item = MenuItem::with_id(app, "quit", "退出", true, None::<&str>)?;
-            let menu = Menu::with_items(app, &[&show_item, &settings_item, &quit_item])?;
+            let menu = build_saved_tray_menu(app.handle())?;
 
-            if let Some(tray) = app.tray_by_id("main") {
+            if let Some(tray) = app.tray_by_id(TRAY_ID) {
                 tray.set_menu(Some(menu))?;
 
-                tray.on_tray_icon_event(move |tray, event| {
-                    if let TrayIconEvent::Click {
+                tray.on_tray_icon_event(move |tray, event| match event {
+                    TrayIconEvent::Click {
                         button,
-                        button_state,
+                        button_state: MouseButtonState::Up,
                         ..
-                    } = event
-                    {
-                        if button == tauri::tray::MouseButton::Left
-                            && button_state == tauri::tray::MouseButtonState::Up
-                        {
-                            log::debug!("Tray icon clicked");
-                            let app = tray.app_handle().clone();
-                            tauri::async_runtime::spawn(async move {
-                                if let Some(window) = resolve_main_window(&app) {
-                                    let _ = toggle_main_window_visibility(&window).await;
-                                }
-                            });
+                    } => match button {
+                        MouseButton::Left => handle_tray_left_click(tray.app_handle()),
+                        MouseButton::Middle => {
+                            handle_tray_click(tray.app_handle(), TrayClickKind::Middle)
                         }
-                    }
+                        _ => {}
+                    },
+                    TrayIconEvent::DoubleClick {
+                        button: MouseButton::Left,
+                        ..
+                    } => handle_tray_double_click(tray.app_handle()),
+                    _ => {}
                 });
 
                 tray.on_menu_event(move |app, event| match event.id.as_ref() {
                     "show" => {
                         log::debug!("Tray menu: show main window");
-                        if let Some(window) = resolve_main_window(app) {
+                        if let Some(window) = resolve_active_panel_window(app) {
                             tauri::async_runtime::spawn(async move {
                                 let _ = show_main_window(&window).await;
                             });
@@ -217,165 +324,42 @@ fn run_desktop() {
                     }
                     "settings" => {
                         log::debug!("Tray menu: open settings");
-                        if let Some(window) = resolve_main_window(app) {
-                            tauri::async_runtime::spawn(async move {
-                                if show_main_window_without_restore(&window).await.is_ok() {
-                                    let _ = window.emit("open-settings", ());
-                                }
-                            });
-                        }
+                        open_settings_in_main_window(app);
                     }
                     "quit" => {
                         log::info!("Tray menu: quit application");
-                        let app_handle = app.clone();
-                        tauri::async_runtime::spawn(async move {
-                            // 退出前广播事件，给便签窗口/主窗口留出落盘与云同步机会。
-                            // 等待前端发送 `app-exit-ready` 回执（表示所有便签位置已落盘、内容已同步），
-                            // 最长等 3 秒超时，避免前端异常导致永久挂起。
-                            let (tx, rx) = tokio::sync::oneshot::channel::<()>();
-                            let tx_shared = std::sync::Arc::new(std::sync::Mutex::new(Some(tx)));
-                            let tx_for_listener = tx_shared.clone();
-                            let listener_handle =
-                                app_handle.listen("app-exit-ready", move |_event| {
-                                    if let Ok(mut guard) = tx_for_listener.lock() {
-                                        if let Some(tx) = guard.take() {
-                                            let _ = tx.send(());
-                                        }
-                                    }
-                                });
-                            let _ = app_handle.emit("app-before-exit", ());
-
-                            match tokio::time::timeout(Duration::from_secs(3), rx).await {
-                                Ok(Ok(())) => log::info!("Received app-exit-ready from frontend"),
-                                Ok(Err(_)) => log::warn!("app-exit-ready sender dropped"),
-                                Err(_) => log::warn!("Timed out waiting for app-exit-ready (3s)"),
-                            }
-                            app_handle.unlisten(listener_handle);
-                            app_handle.exit(0);
-                        });
+                        tauri::async_runtime::spawn(exit_after_frontend_flush(app.clone()));
                     }
                     _ => {}
                 });
             }
 
-            let handle = app.handle().clone();
-            init_update(handle.clone());
-            let last_shortcut_trigger = Arc::new(Mutex::new(None::<Instant>));
-
-            // 注册主快捷键
-            #[cfg(target_os = "macos")]
-            let main_shortcut = "Cmd+Shift+A";
-            #[cfg(not(target_os = "macos"))]
-            let main_shortcut = "Ctrl+Shift+A";
-
-            use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
-            if let Ok(shortcut) = main_shortcut.parse::<Shortcut>() {
-                log::info!("Registering main shortcut: {}", shortcut);
-                let throttle = last_shortcut_trigger.clone();
-                let handle_clone = handle.clone();
-                let _ =
-                    app.global_shortcut()
-                        .on_shortcut(shortcut, move |_app, _event, _shortcut| {
-                            // 锁中毒时仍然恢复内部状态，避免因一次 panic 永久禁用快捷键
-                            let mut last = match throttle.lock() {
-                                Ok(guard) => guard,
-                                Err(poisoned) => {
-                                    log::warn!(
-                                        "Shortcut throttle mutex poisoned, recovering inner state"
-                                    );
-                                    poisoned.into_inner()
-                                }
-                            };
-                            let now = Instant::now();
-                            if let Some(previous) = *last {
-                                let elapsed = now.duration_since(previous);
-                                if elapsed < Duration::from_millis(350) {
-                                    log::debug!(
-                                        "Shortcut trigger throttled: {}ms < 350ms",
-                                        elapsed.as_millis()
-                                    );
-                                    return;
-                                }
-                            }
-
-                            *last = Some(now);
-                            log::debug!("Main shortcut triggered");
-
-                            let app_handle = handle_clone.clone();
-                            tauri::async_runtime::spawn(async move {
-                                if let Some(window) = resolve_main_window(&app_handle) {
-                                    let _ = toggle_main_window_visibility(&window).await;
-                                }
-                            });
-                        });
-            }
-
-            // 注册翻译快捷键
-            #[cfg(target_os = "macos")]
-            let translation_shortcut = "Cmd+Shift+T";
-            #[cfg(not(target_os = "macos"))]
-            let translation_shortcut = "Ctrl+Shift+T";
-
-            if let Ok(shortcut) = translation_shortcut.parse::<Shortcut>() {
-                log::info!("Registering translation shortcut: {}", shortcut);
-                let handle_clone = handle.clone();
-                let _ =
-                    app.global_shortcut()
-                        .on_shortcut(shortcut, move |_app, _event, _shortcut| {
-                            log::debug!("Translation shortcut triggered");
-
-                            let app_handle = handle_clone.clone();
-                            tauri::async_runtime::spawn(async move {
-                                if let Some(window) = resolve_main_window(&app_handle) {
-                                    if show_main_window(&window).await.is_ok() {
-                                        let _ = window.emit("translation-hotkey-triggered", ());
-                                    }
-                                }
-                            });
-                        });
-            }
-
-            // 注册划词工具栏快捷键
-            #[cfg(target_os = "macos")]
-            let selection_shortcut = "Cmd+Shift+S";
-            #[cfg(not(target_os = "macos"))]
-            let selection_shortcut = "Ctrl+Shift+S";
-
-            if let Ok(shortcut) = selection_shortcut.parse::<Shortcut>() {
-                log::info!("Registering selection toolbar shortcut: {}", shortcut);
-                let handle_clone = handle.clone();
-                let _ =
-                    app.global_shortcut()
-                        .on_shortcut(shortcut, move |_app, _event, _shortcut| {
-                            log::debug!("Selection toolbar shortcut triggered");
-
-                            let app_handle = handle_clone.clone();
-                            if let Some(toolbar_state) = app_handle.try_state::<ToolbarManager>() {
-                                let toolbar_manager = toolbar_state.inner().clone();
-                                global_selection::trigger_toolbar_from_hotkey(
-                                    app_handle,
-                                    toolbar_manager,
-                                );
-                            } else {
-                                log::warn!(
-                                    "Selection toolbar shortcut triggered but manager state missing"
-                                );
-                            }
-                        });
-            }
+            init_update(app.handle().clone());
+            shortcuts::register_global_shortcuts(app.handle());
 
             log::info!("Desktop application setup completed");
             Ok(())
         })
-        .on_window_event(|window, event| {
-            if let WindowEvent::CloseRequested { api, .. } = event {
-                // 仅主窗口拦截关闭并隐藏到托盘；其他窗口（便签、工具栏等）允许正常关闭
-                if window.label() != "main" {
+        .on_window_event(|window, event| match event {
+            WindowEvent::CloseRequested { api, .. } => {
+                // 仅主窗口拦截关闭并隐藏到托盘；其他窗口（便签、工具栏、面板等）允许正常关闭
+                if window.label() != MAIN_WINDOW_LABEL {
                     return;
                 }
 
-                log::debug!("Window close request intercepted, hiding to tray");
                 api.prevent_close();
+                let quit_requested = window
+                    .try_state::<WindowPreferences>()
+                    .is_some_and(|preferences| preferences.take_next_close_quits());
+                if quit_requested {
+                    log::info!("Main window closed with one-shot quit flag set, exiting");
+                    tauri::async_runtime::spawn(exit_after_frontend_flush(
+                        window.app_handle().clone(),
+                    ));
+                    return;
+                }
+
+                log::debug!("Window close request intercepted, hiding to tray");
                 let window = window.clone();
                 tauri::async_runtime::spawn(async move {
                     if let Err(err) = hide_main_window(&window).await {
@@ -383,12 +367,42 @@ fn run_desktop() {
                     }
                 });
             }
+            WindowEvent::Resized(_) if window.label() == MAIN_WINDOW_LABEL => {
+                handle_main_window_resized(window);
+                window_control::schedule_main_window_geometry_save(window);
+            }
+            WindowEvent::Moved(_) if window.label() == MAIN_WINDOW_LABEL => {
+                window_control::schedule_main_window_geometry_save(window);
+            }
+            WindowEvent::Focused(true) => {
+                clear_user_attention(window);
+                if let Some(manager) = window.try_state::<PanelWindowManager>() {
+                    manager.mark_focused(window.label());
+                }
+            }
+            WindowEvent::ThemeChanged(theme) => {
+                window_control::handle_system_theme_changed(window, *theme);
+            }
+            WindowEvent::Destroyed => {
+                if let Some(manager) = window.try_state::<PanelWindowManager>() {
+                    manager.remove(window.label());
+                }
+            }
+            _ => {}
         })
         .invoke_handler(tauri::generate_handler![
             toggle_window,
             show_window,
             hide_window,
             open_platform_in_main_window,
+            create_panel_window,
+            focus_panel,
+            close_panel,
+            set_minimize_to_tray,
+            get_minimize_to_tray,
+            request_user_attention,
+            set_main_window_decorations,
+            snap_main_window,
             ensure_child_webview,
             set_child_webview_bounds,
             show_child_webview,
@@ -399,7 +413,9 @@ fn run_desktop() {
             check_child_webview_exists,
             hide_all_child_webviews,
             evaluate_child_webview_script,
+            read_injection_script,
             test_proxy_connection,
+            validate_proxy_url,
             check_update,
             download_update,
             get_download_status,
@@ -422,10 +438,108 @@ fn run_desktop() {
             ensure_desktop_note_window,
             close_desktop_note_window,
             check_accessibility_permission,
-            request_accessibility_permission
+            request_accessibility_permission,
+            get_app_paths,
+            sha256_hex,
+            parse_accelerator,
+            get_child_webview_info,
+            set_external_url_schemes,
+            get_external_url_schemes,
+            inject_child_webview_css,
+            remove_child_webview_css,
+            set_child_webview_navigation_blocklist,
+            get_child_webview_load_state,
+            resize_selection_toolbar,
+            get_temporary_disable_remaining_ms,
+            clear_captured_text,
+            set_selection_min_drag_distance,
+            get_selection_min_drag_distance,
+            diagnose_capture_for_active_app,
+            set_selection_toolbar_anchor,
+            get_monitor_at_cursor,
+            get_selection_bounds,
+            open_external_url,
+            copy_release_notes,
+            get_changelog_since,
+            test_download_throughput,
+            get_app_integrity,
+            cancel_update_check,
+            reveal_downloaded_installer,
+            reset_update_state,
+            recheck_and_restart_monitor,
+            set_selection_simulated_copy,
+            get_selection_simulated_copy,
+            set_redact_capture_logs,
+            get_redact_capture_logs,
+            set_update_mirrors,
+            get_update_mirrors,
+            check_update_server_reachable,
+            child_webview_hard_reload,
+            get_child_webview_incognito,
+            get_child_webviews_resource_usage,
+            suspend_child_webview,
+            resume_child_webview,
+            is_child_webview_suspended,
+            set_child_webview_idle_suspend,
+            get_child_webview_idle_suspend,
+            capture_child_webview_full_page,
+            validate_config,
+            set_child_webview_proxy,
+            get_supported_proxy_types,
+            self_test_selection_pipeline,
+            get_raw_latest_release,
+            prewarm_child_webview,
+            set_selection_toolbar_window_level,
+            get_shortcut_registration_status,
+            retry_shortcut_registration,
+            get_linux_package_format,
+            stop_selection_monitor,
+            restart_selection_monitor,
+            get_monitor_health,
+            set_next_close_quits,
+            get_main_window_state,
+            dismiss_selection_toolbar,
+            focus_selection_toolbar,
+            list_cached_updates,
+            delete_cached_update,
+            hash_cached_update,
+            set_active_proxy,
+            recreate_selection_toolbar,
+            list_child_webviews,
+            get_child_webview_devtools_info,
+            set_all_child_webviews_zoom,
+            bypass_ignore_list_once,
+            set_selection_capture_history,
+            get_selection_capture_history_enabled,
+            get_capture_history,
+            clear_capture_history,
+            set_selection_toolbar_default_action,
+            set_selection_max_capture_length,
+            get_selection_max_capture_length,
+            get_toolbar_behavior,
+            set_toolbar_behavior,
+            test_launch_installer,
+            simulate_selection,
+            get_system_theme,
+            set_toolbar_theme,
+            set_tray_click_action,
+            get_tray_click_action,
+            get_tray_click_actions,
+            set_tray_menu_labels,
+            set_backend_locale,
+            get_backend_locale,
+            set_selection_toolbar_fullscreen_boost,
+            set_selection_toolbar_size_bounds
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                // 所有退出路径（托盘退出、Cmd+Q、注销、安装更新后退出）都会经过这里
+                window_control::flush_main_window_geometry(app);
+                update::shutdown();
+            }
+        });
 
     log::info!("AI Ask desktop application exited");
 }
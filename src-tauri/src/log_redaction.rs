@@ -0,0 +1,123 @@
+//! 划词文本日志脱敏模块
+//!
+//! 划词监听与工具栏在 debug 日志中输出选中文本预览，便于排查捕获问题。为避免用户开启
+//! debug 日志排查其他问题时把选中内容写入日志文件，默认（release 构建）仅记录字符数与
+//! 主要文字体系；只有显式关闭脱敏时才输出截断后的文本预览。
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tauri::AppHandle;
+
+use crate::settings;
+
+/// 脱敏开关的持久化键
+const SETTING_REDACT_CAPTURE_LOGS: &str = "redactCaptureLogs";
+
+/// 是否脱敏划词文本日志（release 构建默认开启，debug 构建默认关闭）
+static REDACT_CAPTURE_LOGS: AtomicBool = AtomicBool::new(!cfg!(debug_assertions));
+
+/// 启动时加载持久化的脱敏开关
+pub(crate) fn load_capture_log_redaction(app: &AppHandle) {
+    if let Some(enabled) = settings::load_setting::<bool>(app, SETTING_REDACT_CAPTURE_LOGS) {
+        REDACT_CAPTURE_LOGS.store(enabled, Ordering::Relaxed);
+    }
+}
+
+/// 生成划词文本的日志描述（遵循当前脱敏开关）
+pub(crate) fn capture_log_preview(text: &str, max_chars: usize) -> String {
+    format_capture_preview(text, max_chars, REDACT_CAPTURE_LOGS.load(Ordering::Relaxed))
+}
+
+/// 脱敏时仅输出字符数与主要文字体系，否则输出合并空白后的单行截断预览
+fn format_capture_preview(text: &str, max_chars: usize, redact: bool) -> String {
+    let length = text.chars().count();
+    if redact {
+        return format!(
+            "{} characters (script: {}, preview redacted)",
+            length,
+            detect_script(text)
+        );
+    }
+
+    let preview: String = text
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .take(max_chars)
+        .collect();
+    format!("{} characters (preview: \"{}\")", length, preview)
+}
+
+/// 按字符数统计文本中占比最高的文字体系（仅统计字母类字符）
+fn detect_script(text: &str) -> &'static str {
+    const SCRIPTS: [&str; 8] = [
+        "Latin", "Han", "Kana", "Hangul", "Cyrillic", "Greek", "Arabic", "Other",
+    ];
+    let mut counts = [0usize; SCRIPTS.len()];
+
+    for ch in text.chars().filter(|ch| ch.is_alphabetic()) {
+        let index = match u32::from(ch) {
+            0x0041..=0x024F => 0,
+            0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF => 1,
+            0x3040..=0x30FF => 2,
+            0x1100..=0x11FF | 0x3130..=0x318F | 0xAC00..=0xD7AF => 3,
+            0x0400..=0x04FF => 4,
+            0x0370..=0x03FF => 5,
+            0x0600..=0x06FF => 6,
+            _ => 7,
+        };
+        counts[index] += 1;
+    }
+
+    counts
+        .iter()
+        .enumerate()
+        .filter(|(_, count)| **count > 0)
+        .max_by_key(|(_, count)| **count)
+        .map(|(index, _)| SCRIPTS[index])
+        .unwrap_or("Unknown")
+}
+
+/// 设置是否脱敏划词文本日志
+#[tauri::command]
+pub(crate) async fn set_redact_capture_logs(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::save_setting(&app, SETTING_REDACT_CAPTURE_LOGS, &enabled)?;
+    REDACT_CAPTURE_LOGS.store(enabled, Ordering::Relaxed);
+    log::info!("Capture log redaction set to {}", enabled);
+    Ok(())
+}
+
+/// 获取划词文本日志是否脱敏
+#[tauri::command]
+pub(crate) async fn get_redact_capture_logs() -> Result<bool, String> {
+    Ok(REDACT_CAPTURE_LOGS.load(Ordering::Relaxed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{detect_script, format_capture_preview};
+
+    #[test]
+    fn redacted_preview_hides_content() {
+        let formatted = format_capture_preview("secret password", 50, true);
+        assert_eq!(formatted, "15 characters (script: Latin, preview redacted)");
+        assert!(!formatted.contains("secret"));
+    }
+
+    #[test]
+    fn plain_preview_truncates_and_flattens_whitespace() {
+        assert_eq!(
+            format_capture_preview("hello\n  world\r\nagain", 11, false),
+            "20 characters (preview: \"hello world\")"
+        );
+    }
+
+    #[test]
+    fn detects_dominant_script() {
+        assert_eq!(detect_script("你好，世界 hi"), "Han");
+        assert_eq!(detect_script("Привет"), "Cyrillic");
+        assert_eq!(detect_script("안녕하세요"), "Hangul");
+        assert_eq!(detect_script("123 !?"), "Unknown");
+    }
+}
@@ -0,0 +1,307 @@
+//! 进程资源占用统计模块
+//!
+//! 子 WebView 由独立的浏览器进程承载（WebView2 的 msedgewebview2.exe、WebKitGTK 的
+//! WebKitWebProcess），这些进程是应用进程的子孙进程。本模块枚举应用进程树并统计每个进程的
+//! 常驻内存与累计 CPU 时间，供资源占用面板展示。macOS 的 WKWebView 进程由 launchd 拉起，
+//! 不在应用进程树内，因此只能统计应用主进程。
+
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+
+/// 单个进程的资源占用
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProcessUsage {
+    pub pid: u32,
+    pub parent_pid: u32,
+    pub name: String,
+    /// 常驻内存（工作集）字节数；无权限读取时为 `None`
+    pub memory_bytes: Option<u64>,
+    /// 进程启动以来累计的 CPU 时间（用户态 + 内核态），前端可两次采样求占用率
+    pub cpu_time_ms: Option<u64>,
+}
+
+/// 当前平台的应用进程树是否包含 WebView 渲染进程
+pub(crate) const PROCESS_TREE_INCLUDES_WEBVIEWS: bool = !cfg!(target_os = "macos");
+
+/// 统计当前应用进程及其所有子孙进程的资源占用（第一项为应用主进程）
+pub(crate) fn app_process_tree_usage() -> Result<Vec<ProcessUsage>, String> {
+    let processes = snapshot_processes()?;
+    let mut tree = descendant_processes(&processes, std::process::id());
+    if tree.is_empty() {
+        return Err("current process not found in process snapshot".to_string());
+    }
+    fill_resource_usage(&mut tree);
+    Ok(tree)
+}
+
+/// 从进程快照中取出根进程及其所有子孙进程（根进程在前，其余按发现顺序）
+fn descendant_processes(processes: &[ProcessUsage], root_pid: u32) -> Vec<ProcessUsage> {
+    let mut children: HashMap<u32, Vec<&ProcessUsage>> = HashMap::new();
+    let mut root = None;
+    for process in processes {
+        if process.pid == root_pid {
+            root = Some(process);
+        } else {
+            children
+                .entry(process.parent_pid)
+                .or_default()
+                .push(process);
+        }
+    }
+
+    let Some(root) = root else {
+        return Vec::new();
+    };
+
+    let mut result = vec![root.clone()];
+    let mut visited = HashSet::from([root_pid]);
+    let mut index = 0;
+    while index < result.len() {
+        let pid = result[index].pid;
+        for child in children.get(&pid).into_iter().flatten() {
+            // 进程号可能被复用，避免父子关系成环
+            if visited.insert(child.pid) {
+                result.push((*child).clone());
+            }
+        }
+        index += 1;
+    }
+    result
+}
+
+/// 汇总内存与 CPU 时间（缺失的数据按 0 计）
+pub(crate) fn total_usage(processes: &[ProcessUsage]) -> (u64, u64) {
+    processes.iter().fold((0, 0), |(memory, cpu), process| {
+        (
+            memory + process.memory_bytes.unwrap_or(0),
+            cpu + process.cpu_time_ms.unwrap_or(0),
+        )
+    })
+}
+
+/// 汇总某个进程及其子孙进程的内存与 CPU 时间；该进程不在列表中时返回 `None`
+pub(crate) fn subtree_usage(processes: &[ProcessUsage], root_pid: u32) -> Option<(u64, u64)> {
+    let subtree = descendant_processes(processes, root_pid);
+    (!subtree.is_empty()).then(|| total_usage(&subtree))
+}
+
+/// 通过 `ps` 获取全部进程（Linux / macOS 均支持以下输出格式）
+#[cfg(unix)]
+fn snapshot_processes() -> Result<Vec<ProcessUsage>, String> {
+    let output = std::process::Command::new("ps")
+        .args(["-A", "-o", "pid=,ppid=,rss=,time=,comm="])
+        .output()
+        .map_err(|e| format!("failed to run ps: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("ps exited with status {}", output.status));
+    }
+    Ok(parse_ps_output(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// `ps` 输出已包含内存与 CPU 时间，无需额外查询
+#[cfg(unix)]
+fn fill_resource_usage(_processes: &mut [ProcessUsage]) {}
+
+/// 解析 `ps -o pid=,ppid=,rss=,time=,comm=` 的输出（rss 单位为 KiB，comm 可能含空格）
+#[cfg(unix)]
+fn parse_ps_output(output: &str) -> Vec<ProcessUsage> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let pid = fields.next()?.parse().ok()?;
+            let parent_pid = fields.next()?.parse().ok()?;
+            let rss_kib = fields.next()?.parse::<u64>().ok();
+            let cpu_time_ms = fields.next().and_then(parse_cpu_time);
+            let name = fields.collect::<Vec<_>>().join(" ");
+            Some(ProcessUsage {
+                pid,
+                parent_pid,
+                name,
+                memory_bytes: rss_kib.map(|kib| kib * 1024),
+                cpu_time_ms,
+            })
+        })
+        .collect()
+}
+
+/// 解析 `ps` 的 CPU 时间：Linux 为 `[dd-]hh:mm:ss`，macOS 为 `[hh:]mm:ss.cc`
+#[cfg(unix)]
+fn parse_cpu_time(value: &str) -> Option<u64> {
+    let (days, clock) = match value.split_once('-') {
+        Some((days, clock)) => (days.parse::<u64>().ok()?, clock),
+        None => (0, value),
+    };
+
+    let mut parts: Vec<&str> = clock.split(':').collect();
+    if parts.is_empty() || parts.len() > 3 {
+        return None;
+    }
+    let seconds: f64 = parts.pop()?.parse().ok()?;
+    if !seconds.is_finite() || seconds < 0.0 {
+        return None;
+    }
+    let mut whole_minutes = 0u64;
+    for part in parts {
+        whole_minutes = whole_minutes * 60 + part.parse::<u64>().ok()?;
+    }
+
+    let total_seconds = days * 86_400 + whole_minutes * 60;
+    Some(total_seconds * 1000 + (seconds * 1000.0).round() as u64)
+}
+
+/// 通过 ToolHelp 快照获取全部进程（仅包含进程关系，资源数据随后按需查询）
+#[cfg(target_os = "windows")]
+fn snapshot_processes() -> Result<Vec<ProcessUsage>, String> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W,
+        TH32CS_SNAPPROCESS,
+    };
+
+    let mut processes = Vec::new();
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0)
+            .map_err(|e| format!("failed to create process snapshot: {}", e))?;
+
+        let mut entry = PROCESSENTRY32W {
+            dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+            ..Default::default()
+        };
+        let mut has_entry = Process32FirstW(snapshot, &mut entry).is_ok();
+        while has_entry {
+            let name_len = entry
+                .szExeFile
+                .iter()
+                .position(|&ch| ch == 0)
+                .unwrap_or(entry.szExeFile.len());
+            processes.push(ProcessUsage {
+                pid: entry.th32ProcessID,
+                parent_pid: entry.th32ParentProcessID,
+                name: String::from_utf16_lossy(&entry.szExeFile[..name_len]),
+                memory_bytes: None,
+                cpu_time_ms: None,
+            });
+            has_entry = Process32NextW(snapshot, &mut entry).is_ok();
+        }
+
+        let _ = CloseHandle(snapshot);
+    }
+    Ok(processes)
+}
+
+/// 逐个打开进程查询工作集与 CPU 时间
+#[cfg(target_os = "windows")]
+fn fill_resource_usage(processes: &mut [ProcessUsage]) {
+    use windows::Win32::Foundation::{CloseHandle, FILETIME};
+    use windows::Win32::System::ProcessStatus::{GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS};
+    use windows::Win32::System::Threading::{
+        GetProcessTimes, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    fn filetime_to_ms(time: FILETIME) -> u64 {
+        // FILETIME 单位为 100 纳秒
+        ((u64::from(time.dwHighDateTime) << 32) | u64::from(time.dwLowDateTime)) / 10_000
+    }
+
+    for process in processes.iter_mut() {
+        unsafe {
+            let Ok(handle) = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, process.pid)
+            else {
+                continue;
+            };
+
+            let mut counters = PROCESS_MEMORY_COUNTERS {
+                cb: std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32,
+                ..Default::default()
+            };
+            if GetProcessMemoryInfo(handle, &mut counters, counters.cb).is_ok() {
+                process.memory_bytes = Some(counters.WorkingSetSize as u64);
+            }
+
+            let mut creation = FILETIME::default();
+            let mut exit = FILETIME::default();
+            let mut kernel = FILETIME::default();
+            let mut user = FILETIME::default();
+            if GetProcessTimes(handle, &mut creation, &mut exit, &mut kernel, &mut user).is_ok() {
+                process.cpu_time_ms = Some(filetime_to_ms(kernel) + filetime_to_ms(user));
+            }
+
+            let _ = CloseHandle(handle);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, parent_pid: u32) -> ProcessUsage {
+        ProcessUsage {
+            pid,
+            parent_pid,
+            name: format!("p{pid}"),
+            memory_bytes: Some(u64::from(pid) * 1024),
+            cpu_time_ms: Some(10),
+        }
+    }
+
+    #[test]
+    fn collects_root_and_descendants() {
+        let processes = vec![
+            process(1, 0),
+            process(10, 1),
+            process(11, 10),
+            process(12, 11),
+            process(20, 1),
+            process(13, 10),
+        ];
+        let pids: Vec<u32> = descendant_processes(&processes, 10)
+            .iter()
+            .map(|p| p.pid)
+            .collect();
+        assert_eq!(pids, vec![10, 11, 13, 12]);
+        assert!(descendant_processes(&processes, 99).is_empty());
+    }
+
+    #[test]
+    fn totals_skip_missing_values() {
+        let mut processes = vec![process(1, 0), process(2, 1)];
+        processes[1].memory_bytes = None;
+        assert_eq!(total_usage(&processes), (1024, 20));
+    }
+
+    #[test]
+    fn subtree_usage_sums_process_and_descendants() {
+        let processes = vec![
+            process(1, 0),
+            process(10, 1),
+            process(11, 10),
+            process(20, 1),
+        ];
+        assert_eq!(subtree_usage(&processes, 10), Some((21 * 1024, 20)));
+        assert_eq!(subtree_usage(&processes, 99), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn parses_ps_output_with_spaces_in_name() {
+        let output = "  100     1  2048 00:01:05 ai-ask\n  \
+                      101   100   512 0:02.50 WebKit Web Content\ninvalid line\n";
+        let processes = parse_ps_output(output);
+        assert_eq!(processes.len(), 2);
+        assert_eq!(processes[0].memory_bytes, Some(2048 * 1024));
+        assert_eq!(processes[0].cpu_time_ms, Some(65_000));
+        assert_eq!(processes[1].name, "WebKit Web Content");
+        assert_eq!(processes[1].cpu_time_ms, Some(2_500));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn parses_cpu_time_with_days() {
+        assert_eq!(parse_cpu_time("1-02:00:00"), Some((86_400 + 7_200) * 1000));
+        assert_eq!(parse_cpu_time("bad"), None);
+    }
+}
@@ -6,22 +6,36 @@
 //! - 解析外部 URL 与代理 URL
 //! - 为不同代理配置生成独立的数据目录
 //! - 测试代理连通性
+//! - 维护全局生效的代理配置（更新检查、下载与连通性测试共用）
 
 use std::fs;
 use std::path::PathBuf;
+use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
 use reqwest::redirect::Policy;
 use serde::{Deserialize, Serialize};
-use tauri::{Manager, Url, Window};
+use tauri::{AppHandle, Manager, State, Url, Window};
+
+use crate::i18n::Message;
+use crate::utils::lock_or_recover;
+use crate::{app_paths, settings};
+
+const SETTING_ACTIVE_PROXY: &str = "activeProxy";
 
 /// 代理测试配置
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct ProxyTestConfig {
     #[serde(rename = "type")]
     pub proxy_type: String,
     pub host: Option<String>,
     pub port: Option<String>,
+    /// 绕过代理的地址列表原始输入（逗号、分号或换行分隔）
+    #[serde(default)]
+    pub bypass: Option<String>,
+    /// 自定义 User-Agent；为空时使用更新配置中的 UA
+    #[serde(default, alias = "userAgent")]
+    pub user_agent: Option<String>,
 }
 
 /// 代理测试结果
@@ -32,6 +46,62 @@ pub(crate) struct ProxyTestResult {
     pub latency: Option<u128>,
 }
 
+/// 代理 URL 校验结果
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub(crate) struct ProxyUrlValidation {
+    pub valid: bool,
+    pub scheme: Option<String>,
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub error: Option<String>,
+}
+
+/// 代理类型能力描述（供前端渲染代理设置表单）
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProxyTypeInfo {
+    /// 代理类型标识（与 `ProxyTestConfig.proxy_type` 取值一致）
+    pub id: &'static str,
+    pub requires_host: bool,
+    pub requires_port: bool,
+    /// 是否支持在地址中携带认证信息
+    pub supports_auth: bool,
+    /// 是否支持绕过列表
+    pub supports_bypass: bool,
+    /// 可在主机地址中携带的协议（如 `socks5://`）
+    pub schemes: &'static [&'static str],
+}
+
+/// 当前构建支持的代理类型
+static SUPPORTED_PROXY_TYPES: [ProxyTypeInfo; 3] = [
+    ProxyTypeInfo {
+        id: "none",
+        requires_host: false,
+        requires_port: false,
+        supports_auth: false,
+        supports_bypass: false,
+        schemes: &[],
+    },
+    ProxyTypeInfo {
+        id: "system",
+        requires_host: false,
+        requires_port: false,
+        supports_auth: false,
+        supports_bypass: false,
+        schemes: &[],
+    },
+    // 认证信息仅更新下载请求可用，子 WebView 的代理设置不支持认证；
+    // 主机不带协议时必须填写端口（见 `custom_proxy_url`）
+    ProxyTypeInfo {
+        id: "custom",
+        requires_host: true,
+        requires_port: true,
+        supports_auth: false,
+        supports_bypass: true,
+        schemes: &["http", "socks5"],
+    },
+];
+
 /// 解析外部 URL
 pub(crate) fn parse_external_url(url: &str) -> Result<Url, String> {
     Url::parse(url).map_err(|err| {
@@ -51,11 +121,64 @@ pub(crate) fn parse_proxy_url(url: &str) -> Result<Url, String> {
         "http" | "socks5" => Ok(parsed),
         scheme => {
             log::error!("Unsupported proxy protocol: {}", scheme);
-            Err(format!("Unsupported proxy protocol: {scheme}"))
+            Err(Message::UnsupportedProxyProtocol(scheme).to_string())
         }
     }
 }
 
+/// 解析代理绕过列表
+///
+/// 支持逗号、分号与换行混合分隔；去除首尾空白与空项，主机名统一转小写。
+/// 通配符（`*.example.com`）与 CIDR（`10.0.0.0/8`）原样保留，交由 reqwest 解释。
+pub(crate) fn parse_bypass_list(raw: &str) -> Vec<String> {
+    raw.split([',', ';', '\n', '\r'])
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// 将绕过列表转换为 reqwest 的 `NoProxy` 规则
+fn build_no_proxy(bypass: Option<&str>) -> Option<reqwest::NoProxy> {
+    let entries = parse_bypass_list(bypass?);
+    if entries.is_empty() {
+        return None;
+    }
+    reqwest::NoProxy::from_string(&entries.join(","))
+}
+
+/// 校验代理 URL 并拆分出协议、主机与端口
+fn inspect_proxy_url(url: &str) -> ProxyUrlValidation {
+    match parse_proxy_url(url.trim()) {
+        Ok(parsed) => ProxyUrlValidation {
+            valid: true,
+            scheme: Some(parsed.scheme().to_string()),
+            host: parsed.host_str().map(str::to_string),
+            port: parsed.port_or_known_default(),
+            error: None,
+        },
+        Err(error) => ProxyUrlValidation {
+            valid: false,
+            scheme: None,
+            host: None,
+            port: None,
+            error: Some(error),
+        },
+    }
+}
+
+/// 获取当前构建支持的代理类型及其表单要求
+#[tauri::command]
+pub(crate) async fn get_supported_proxy_types() -> Result<&'static [ProxyTypeInfo], String> {
+    Ok(&SUPPORTED_PROXY_TYPES)
+}
+
+/// 校验代理 URL（不发起网络请求），供前端表单即时反馈
+#[tauri::command]
+pub(crate) async fn validate_proxy_url(url: String) -> Result<ProxyUrlValidation, String> {
+    Ok(inspect_proxy_url(&url))
+}
+
 /// 为代理配置生成数据目录路径
 ///
 /// Windows WebView2 在不同代理配置下需要使用隔离的数据目录，
@@ -65,11 +188,7 @@ pub(crate) fn resolve_proxy_data_directory(
     proxy: Option<&str>,
 ) -> Option<PathBuf> {
     let proxy = proxy?;
-    let resolver = window.app_handle().path();
-    let base_dir = resolver
-        .app_data_dir()
-        .or_else(|_| resolver.app_cache_dir())
-        .ok()?;
+    let base_dir = app_paths::data_or_cache_dir(window.app_handle())?;
 
     let dir = base_dir
         .join("webview-proxies")
@@ -91,16 +210,121 @@ fn sanitize_for_directory(input: &str) -> String {
         .collect()
 }
 
+/// 校验并规范化 User-Agent（去除首尾空白，必须是合法的 HTTP 头部值）
+pub(crate) fn normalize_user_agent(value: &str) -> Result<String, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(Message::UserAgentEmpty.to_string());
+    }
+    reqwest::header::HeaderValue::from_str(value)
+        .map_err(|_| Message::UserAgentInvalid.to_string())?;
+    Ok(value.to_string())
+}
+
+/// 全局生效的代理配置
+///
+/// 前端配置存储在启动、修改或重置代理设置时通过 `set_active_proxy` 将 `app_config.proxy`
+/// 同步过来并持久化，二者始终一致；未设置时回退到 `app_config` 中的代理
+#[derive(Default)]
+pub(crate) struct ActiveProxy {
+    config: Mutex<Option<ProxyTestConfig>>,
+}
+
+impl ActiveProxy {
+    /// 从持久化设置加载
+    pub(crate) fn load(app: &AppHandle) -> Self {
+        let config = settings::load_setting::<Option<ProxyTestConfig>>(app, SETTING_ACTIVE_PROXY)
+            .flatten()
+            .filter(|config| match validate_proxy_config(config) {
+                Ok(()) => true,
+                Err(err) => {
+                    log::warn!("Ignoring saved active proxy: {}", err);
+                    false
+                }
+            });
+        log::debug!(
+            "Active proxy loaded: {:?}",
+            config.as_ref().map(|config| &config.proxy_type)
+        );
+        Self {
+            config: Mutex::new(config),
+        }
+    }
+
+    fn get(&self) -> Option<ProxyTestConfig> {
+        lock_or_recover(&self.config, "active proxy").clone()
+    }
+}
+
+/// 读取通过 `set_active_proxy` 设置的代理，未设置时返回 `None`
+pub(crate) fn active_proxy(app: &AppHandle) -> Option<ProxyTestConfig> {
+    app.try_state::<ActiveProxy>()?.get()
+}
+
+/// 校验代理配置：类型必须受支持，`custom` 类型需能构造出合法的代理地址
+pub(crate) fn validate_proxy_config(config: &ProxyTestConfig) -> Result<(), String> {
+    let info = SUPPORTED_PROXY_TYPES
+        .iter()
+        .find(|info| info.id == config.proxy_type)
+        .ok_or_else(|| Message::UnsupportedProxyType(&config.proxy_type).to_string())?;
+    if info.requires_host {
+        custom_proxy_url(config)?;
+    }
+    if let Some(user_agent) = config.user_agent.as_deref() {
+        normalize_user_agent(user_agent)?;
+    }
+    Ok(())
+}
+
+/// 设置全局生效的代理并持久化，更新检查、下载与未传入配置的连通性测试都会使用它
+///
+/// 传入 `null` 时清除，恢复使用 `app_config` 中的代理配置
+#[tauri::command]
+pub(crate) async fn set_active_proxy(
+    app: AppHandle,
+    state: State<'_, ActiveProxy>,
+    config: Option<ProxyTestConfig>,
+) -> Result<(), String> {
+    if let Some(config) = &config {
+        validate_proxy_config(config)?;
+    }
+    settings::save_setting(&app, SETTING_ACTIVE_PROXY, &config)?;
+    log::info!(
+        "Active proxy updated: {:?}",
+        config.as_ref().map(|config| &config.proxy_type)
+    );
+    *lock_or_recover(&state.config, "active proxy") = config;
+    Ok(())
+}
+
 /// 测试代理连通性
+///
+/// 未传入配置时测试当前生效的代理
 #[tauri::command]
 pub(crate) async fn test_proxy_connection(
-    config: ProxyTestConfig,
+    app: tauri::AppHandle,
+    config: Option<ProxyTestConfig>,
 ) -> Result<ProxyTestResult, String> {
+    let config = match config {
+        Some(config) => config,
+        None => crate::update::configured_proxy(&app).unwrap_or_else(|| ProxyTestConfig {
+            proxy_type: "system".into(),
+            host: None,
+            port: None,
+            bypass: None,
+            user_agent: None,
+        }),
+    };
     log::debug!("Testing proxy connection: type={}", config.proxy_type);
 
+    let user_agent = match config.user_agent.as_deref() {
+        Some(value) => normalize_user_agent(value)?,
+        None => crate::update::configured_user_agent(&app),
+    };
     let mut client_builder = reqwest::Client::builder()
         .timeout(Duration::from_secs(10))
-        .redirect(Policy::limited(5));
+        .redirect(Policy::limited(5))
+        .user_agent(user_agent);
 
     match config.proxy_type.as_str() {
         "custom" => {
@@ -111,7 +335,7 @@ pub(crate) async fn test_proxy_connection(
                 .filter(|value| !value.is_empty())
                 .ok_or_else(|| {
                     log::error!("Proxy host is empty");
-                    "Proxy host cannot be empty".to_string()
+                    Message::ProxyHostRequired.to_string()
                 })?;
 
             let port = config
@@ -121,7 +345,7 @@ pub(crate) async fn test_proxy_connection(
                 .filter(|value| !value.is_empty())
                 .ok_or_else(|| {
                     log::error!("Proxy port is empty");
-                    "Proxy port cannot be empty".to_string()
+                    Message::ProxyPortRequired.to_string()
                 })?;
 
             let proxy_url = if host.contains("://") {
@@ -132,10 +356,12 @@ pub(crate) async fn test_proxy_connection(
 
             log::debug!("Using custom proxy: {}", proxy_url);
 
-            let proxy = reqwest::Proxy::all(&proxy_url).map_err(|err| {
-                log::error!("Failed to create proxy config: {}", err);
-                err.to_string()
-            })?;
+            let proxy = reqwest::Proxy::all(&proxy_url)
+                .map_err(|err| {
+                    log::error!("Failed to create proxy config: {}", err);
+                    err.to_string()
+                })?
+                .no_proxy(build_no_proxy(config.bypass.as_deref()));
             client_builder = client_builder.proxy(proxy);
         }
         "system" => {
@@ -146,7 +372,7 @@ pub(crate) async fn test_proxy_connection(
         }
         other => {
             log::error!("Unsupported proxy type: {}", other);
-            return Err(format!("Unsupported proxy type: {other}"));
+            return Err(Message::UnsupportedProxyType(other).to_string());
         }
     }
 
@@ -196,40 +422,83 @@ pub(crate) async fn test_proxy_connection(
     }
 }
 
+/// 根据自定义代理配置构造代理地址
+///
+/// `host` 可直接携带协议（如 `socks5://127.0.0.1:1080`），此时按 `parse_proxy_url`
+/// 的规则校验协议；未携带端口时补上配置的端口。不带协议时视为 HTTP 代理。
+fn custom_proxy_url(config: &ProxyTestConfig) -> Result<String, String> {
+    let host = config
+        .host
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .ok_or_else(|| Message::ProxyHostRequired.to_string())?;
+    let port = config
+        .port
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty());
+
+    if host.contains("://") {
+        let mut url = parse_proxy_url(host)?;
+        if url.port().is_none() {
+            if let Some(port) = port {
+                let port = port
+                    .parse::<u16>()
+                    .map_err(|_| Message::InvalidProxyPort(port).to_string())?;
+                url.set_port(Some(port))
+                    .map_err(|_| Message::InvalidProxyUrl.to_string())?;
+            }
+        }
+        return Ok(url.to_string());
+    }
+
+    let port = port.ok_or_else(|| Message::ProxyPortRequired.to_string())?;
+    Ok(format!("http://{}:{}", host, port))
+}
+
+/// 构造自定义代理（支持 HTTP 与 SOCKS5），并应用绕过列表
+fn build_custom_proxy(config: &ProxyTestConfig) -> Result<reqwest::Proxy, String> {
+    let proxy_url = custom_proxy_url(config)?;
+    Ok(reqwest::Proxy::all(&proxy_url)
+        .map_err(|e| e.to_string())?
+        .no_proxy(build_no_proxy(config.bypass.as_deref())))
+}
+
+/// 将全局代理配置转换为子 WebView 可用的代理地址
+///
+/// 仅 `custom` 类型需要显式代理；`system` 与 `none` 交由 WebView 引擎自身处理，返回 `None`
+pub(crate) fn webview_proxy_url(config: &ProxyTestConfig) -> Option<String> {
+    if config.proxy_type != "custom" {
+        return None;
+    }
+    custom_proxy_url(config)
+        .map_err(|err| log::warn!("Ignoring invalid global proxy for webview: {}", err))
+        .ok()
+}
+
+/// 下载客户端允许跟随的最大重定向次数（GitHub Release 资源会 302 跳转到存储 CDN）
+pub(crate) const DOWNLOAD_MAX_REDIRECTS: usize = 10;
+
 /// 根据代理配置构建 reqwest 客户端
 pub fn build_client_with_proxy(config: &ProxyTestConfig) -> Result<reqwest::Client, String> {
-    use reqwest::redirect::Policy;
     // 下载大文件需要更长的超时时间
     let mut builder = reqwest::Client::builder()
         .connect_timeout(std::time::Duration::from_secs(30))
         .timeout(std::time::Duration::from_secs(30 * 60)) // 30 minutes for large downloads
-        .redirect(Policy::limited(10)); // 增加重定向次数，GitHub 下载会多次重定向
+        .redirect(Policy::limited(DOWNLOAD_MAX_REDIRECTS)); // 增加重定向次数，GitHub 下载会多次重定向
+
+    if let Some(user_agent) = config.user_agent.as_deref() {
+        builder = builder.user_agent(normalize_user_agent(user_agent)?);
+    }
 
     match config.proxy_type.as_str() {
         "custom" => {
-            let host = config
-                .host
-                .as_deref()
-                .map(str::trim)
-                .filter(|v| !v.is_empty())
-                .ok_or_else(|| "Proxy host is required".to_string())?;
-            let port = config
-                .port
-                .as_deref()
-                .map(str::trim)
-                .filter(|v| !v.is_empty())
-                .ok_or_else(|| "Proxy port is required".to_string())?;
-            let proxy_url = if host.contains("://") {
-                host.to_string()
-            } else {
-                format!("http://{}:{}", host, port)
-            };
-            let proxy = reqwest::Proxy::all(&proxy_url).map_err(|e| e.to_string())?;
-            builder = builder.proxy(proxy);
+            builder = builder.proxy(build_custom_proxy(config)?);
         }
         "system" => { /* no explicit proxy; reqwest picks env/system if set */ }
         "none" => { /* no proxy */ }
-        other => return Err(format!("Unsupported proxy type: {}", other)),
+        other => return Err(Message::UnsupportedProxyType(other).to_string()),
     }
 
     builder.build().map_err(|e| e.to_string())
@@ -289,4 +558,137 @@ mod tests {
         assert_eq!(parsed.host_str(), Some("localhost"));
         assert_eq!(parsed.port_or_known_default(), Some(8080));
     }
+
+    #[test]
+    fn inspect_proxy_url_splits_valid_url() {
+        let result = inspect_proxy_url(" socks5://127.0.0.1:1080 ");
+        assert_eq!(
+            result,
+            ProxyUrlValidation {
+                valid: true,
+                scheme: Some("socks5".into()),
+                host: Some("127.0.0.1".into()),
+                port: Some(1080),
+                error: None,
+            }
+        );
+    }
+
+    #[test]
+    fn inspect_proxy_url_reports_error() {
+        let result = inspect_proxy_url("ftp://proxy:21");
+        assert!(!result.valid);
+        assert!(result.scheme.is_none());
+        assert!(result
+            .error
+            .as_deref()
+            .is_some_and(|e| e.contains("Unsupported proxy protocol")));
+    }
+
+    #[test]
+    fn parse_bypass_list_handles_mixed_separators() {
+        let entries = parse_bypass_list("Example.COM, localhost;\n internal.lan\r\n,,; ");
+        assert_eq!(entries, vec!["example.com", "localhost", "internal.lan"]);
+    }
+
+    #[test]
+    fn parse_bypass_list_keeps_wildcard_and_cidr_entries() {
+        let entries = parse_bypass_list("*.corp.example.com\n10.0.0.0/8;192.168.1.1,::1");
+        assert_eq!(
+            entries,
+            vec!["*.corp.example.com", "10.0.0.0/8", "192.168.1.1", "::1"]
+        );
+    }
+
+    #[test]
+    fn build_no_proxy_skips_empty_input() {
+        assert!(build_no_proxy(None).is_none());
+        assert!(build_no_proxy(Some(" ,;\n")).is_none());
+        assert!(build_no_proxy(Some("localhost")).is_some());
+    }
+
+    #[test]
+    fn validate_proxy_config_checks_type_and_address() {
+        assert!(validate_proxy_config(&custom_config("127.0.0.1", "7890")).is_ok());
+        assert!(validate_proxy_config(&custom_config("", "7890")).is_err());
+        assert!(validate_proxy_config(&custom_config("ftp://proxy", "21")).is_err());
+
+        let mut config = custom_config("127.0.0.1", "7890");
+        config.proxy_type = "pac".into();
+        assert!(
+            validate_proxy_config(&config).is_err_and(|err| err.contains("Unsupported proxy type"))
+        );
+        config.proxy_type = "none".into();
+        assert!(validate_proxy_config(&config).is_ok());
+    }
+
+    fn custom_config(host: &str, port: &str) -> ProxyTestConfig {
+        ProxyTestConfig {
+            proxy_type: "custom".into(),
+            host: Some(host.into()),
+            port: Some(port.into()),
+            bypass: None,
+            user_agent: None,
+        }
+    }
+
+    #[test]
+    fn normalize_user_agent_validates_header_value() {
+        assert_eq!(
+            normalize_user_agent("  Mirror/1.0  ").unwrap(),
+            "Mirror/1.0"
+        );
+        assert!(normalize_user_agent("   ").is_err());
+        assert!(normalize_user_agent("bad\nagent").is_err());
+    }
+
+    #[test]
+    fn custom_proxy_url_defaults_to_http() {
+        let url = custom_proxy_url(&custom_config("127.0.0.1", "7890")).unwrap();
+        assert_eq!(url, "http://127.0.0.1:7890");
+    }
+
+    #[test]
+    fn custom_proxy_url_keeps_embedded_scheme_and_fills_port() {
+        let url = custom_proxy_url(&custom_config("socks5://127.0.0.1", "1080")).unwrap();
+        assert_eq!(url, "socks5://127.0.0.1:1080");
+
+        let url = custom_proxy_url(&custom_config("socks5://127.0.0.1:9050", "1080")).unwrap();
+        assert_eq!(url, "socks5://127.0.0.1:9050");
+    }
+
+    #[test]
+    fn custom_proxy_type_requires_port_for_bare_host() {
+        let info = SUPPORTED_PROXY_TYPES
+            .iter()
+            .find(|info| info.id == "custom")
+            .unwrap();
+        assert!(info.requires_port);
+        assert!(custom_proxy_url(&custom_config("127.0.0.1", "")).is_err());
+    }
+
+    #[test]
+    fn custom_proxy_url_rejects_unsupported_scheme() {
+        assert!(custom_proxy_url(&custom_config("ftp://proxy", "21")).is_err());
+    }
+
+    #[test]
+    fn supported_proxy_types_are_accepted_by_client_builder() {
+        for info in SUPPORTED_PROXY_TYPES.iter() {
+            let mut config = custom_config("127.0.0.1", "7890");
+            config.proxy_type = info.id.to_string();
+            assert!(build_client_with_proxy(&config).is_ok(), "{}", info.id);
+        }
+        for scheme in SUPPORTED_PROXY_TYPES[2].schemes {
+            assert!(parse_proxy_url(&format!("{scheme}://127.0.0.1:1080")).is_ok());
+        }
+    }
+
+    #[test]
+    fn socks5_host_builds_socks_proxy_client() {
+        let config = custom_config("socks5://127.0.0.1:1080", "");
+        let proxy = build_custom_proxy(&config).expect("socks5 proxy");
+        assert!(format!("{:?}", proxy).contains("socks5"));
+        assert!(build_client_with_proxy(&config).is_ok());
+    }
 }
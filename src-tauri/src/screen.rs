@@ -0,0 +1,215 @@
+//! 屏幕几何辅助模块
+//!
+//! 统一“查找坐标所在显示器”和“获取显示器工作区”的逻辑，供工具栏定位、窗口吸附、
+//! 光标与显示器映射等功能复用。显示器信息以纯数据结构表示，便于基于模拟的显示器集合测试。
+
+use serde::Serialize;
+use tauri::{AppHandle, Monitor};
+
+use crate::selection_toolbar::platform_cursor_position;
+
+/// 矩形区域
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub(crate) struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Rect {
+    pub fn right(&self) -> f64 {
+        self.x + self.width
+    }
+
+    pub fn bottom(&self) -> f64 {
+        self.y + self.height
+    }
+
+    /// 判断点是否位于矩形内（左上闭、右下开）
+    pub fn contains(&self, point: (f64, f64)) -> bool {
+        point.0 >= self.x && point.0 < self.right() && point.1 >= self.y && point.1 < self.bottom()
+    }
+
+    /// 点到矩形的距离平方（点在矩形内时为 0）
+    fn distance_squared_to(&self, point: (f64, f64)) -> f64 {
+        let dx = (self.x - point.0).max(point.0 - self.right()).max(0.0);
+        let dy = (self.y - point.1).max(point.1 - self.bottom()).max(0.0);
+        dx * dx + dy * dy
+    }
+
+    fn to_logical(self, scale_factor: f64) -> Rect {
+        let scale = if scale_factor > 0.0 {
+            scale_factor
+        } else {
+            1.0
+        };
+        Rect {
+            x: self.x / scale,
+            y: self.y / scale,
+            width: self.width / scale,
+            height: self.height / scale,
+        }
+    }
+}
+
+/// 显示器几何信息（`bounds`/`work_area` 为物理像素，`logical_*` 为逻辑像素）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MonitorInfo {
+    pub name: Option<String>,
+    pub scale_factor: f64,
+    pub bounds: Rect,
+    pub work_area: Rect,
+    pub logical_bounds: Rect,
+    pub logical_work_area: Rect,
+}
+
+impl MonitorInfo {
+    pub fn new(name: Option<String>, scale_factor: f64, bounds: Rect, work_area: Rect) -> Self {
+        Self {
+            name,
+            scale_factor,
+            bounds,
+            work_area,
+            logical_bounds: bounds.to_logical(scale_factor),
+            logical_work_area: work_area.to_logical(scale_factor),
+        }
+    }
+
+    fn from_monitor(monitor: &Monitor) -> Self {
+        let position = monitor.position();
+        let size = monitor.size();
+        let area = monitor.work_area();
+
+        Self::new(
+            monitor.name().cloned(),
+            monitor.scale_factor(),
+            Rect {
+                x: f64::from(position.x),
+                y: f64::from(position.y),
+                width: f64::from(size.width),
+                height: f64::from(size.height),
+            },
+            Rect {
+                x: f64::from(area.position.x),
+                y: f64::from(area.position.y),
+                width: f64::from(area.size.width),
+                height: f64::from(area.size.height),
+            },
+        )
+    }
+}
+
+/// 枚举当前所有显示器
+pub(crate) fn available_monitors(app: &AppHandle) -> Vec<MonitorInfo> {
+    match app.available_monitors() {
+        Ok(monitors) => monitors.iter().map(MonitorInfo::from_monitor).collect(),
+        Err(err) => {
+            log::warn!("Failed to enumerate monitors: {}", err);
+            Vec::new()
+        }
+    }
+}
+
+/// 查找包含指定物理坐标的显示器；若点不在任何显示器内，返回距离最近的显示器
+pub(crate) fn find_monitor_containing(
+    monitors: &[MonitorInfo],
+    point: (f64, f64),
+) -> Option<&MonitorInfo> {
+    monitors
+        .iter()
+        .find(|monitor| monitor.bounds.contains(point))
+        .or_else(|| {
+            monitors.iter().min_by(|a, b| {
+                a.bounds
+                    .distance_squared_to(point)
+                    .total_cmp(&b.bounds.distance_squared_to(point))
+            })
+        })
+}
+
+/// 获取包含指定物理坐标的显示器信息
+pub(crate) fn monitor_containing(app: &AppHandle, point: (f64, f64)) -> Option<MonitorInfo> {
+    find_monitor_containing(&available_monitors(app), point).cloned()
+}
+
+/// 显示器工作区（不含任务栏/Dock），物理像素
+pub(crate) fn work_area(monitor: &MonitorInfo) -> Rect {
+    monitor.work_area
+}
+
+/// 获取光标当前所在显示器的几何信息
+#[tauri::command]
+pub(crate) async fn get_monitor_at_cursor(app: AppHandle) -> Result<MonitorInfo, String> {
+    let point = platform_cursor_position()?;
+    monitor_containing(&app, point).ok_or_else(|| "No monitor available".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_monitor_containing, work_area, MonitorInfo, Rect};
+
+    fn rect(x: f64, y: f64, width: f64, height: f64) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    fn mock_monitors() -> Vec<MonitorInfo> {
+        vec![
+            MonitorInfo::new(
+                Some("primary".into()),
+                1.0,
+                rect(0.0, 0.0, 1920.0, 1080.0),
+                rect(0.0, 0.0, 1920.0, 1040.0),
+            ),
+            MonitorInfo::new(
+                Some("secondary".into()),
+                2.0,
+                rect(1920.0, 0.0, 2560.0, 1440.0),
+                rect(1920.0, 50.0, 2560.0, 1390.0),
+            ),
+        ]
+    }
+
+    #[test]
+    fn finds_monitor_containing_point() {
+        let monitors = mock_monitors();
+        let found = find_monitor_containing(&monitors, (100.0, 100.0)).unwrap();
+        assert_eq!(found.name.as_deref(), Some("primary"));
+
+        let found = find_monitor_containing(&monitors, (1920.0, 10.0)).unwrap();
+        assert_eq!(found.name.as_deref(), Some("secondary"));
+    }
+
+    #[test]
+    fn falls_back_to_nearest_monitor() {
+        let monitors = mock_monitors();
+        let found = find_monitor_containing(&monitors, (5000.0, 200.0)).unwrap();
+        assert_eq!(found.name.as_deref(), Some("secondary"));
+
+        let found = find_monitor_containing(&monitors, (-50.0, -50.0)).unwrap();
+        assert_eq!(found.name.as_deref(), Some("primary"));
+    }
+
+    #[test]
+    fn returns_none_without_monitors() {
+        assert!(find_monitor_containing(&[], (0.0, 0.0)).is_none());
+    }
+
+    #[test]
+    fn computes_logical_geometry_from_scale_factor() {
+        let monitors = mock_monitors();
+        let secondary = &monitors[1];
+        assert_eq!(secondary.logical_bounds, rect(960.0, 0.0, 1280.0, 720.0));
+        assert_eq!(work_area(secondary), rect(1920.0, 50.0, 2560.0, 1390.0));
+        assert_eq!(
+            secondary.logical_work_area,
+            rect(960.0, 25.0, 1280.0, 695.0)
+        );
+    }
+}
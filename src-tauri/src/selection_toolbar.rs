@@ -3,18 +3,27 @@
 //! 提供系统级文本选择监听和浮动工具栏窗口管理功能
 //! 以及浮动结果窗口（用于显示翻译/解释结果）
 
+use crate::content_type::{classify_selection, SelectionContentType};
+use crate::log_redaction::capture_log_preview;
+use crate::screen::{self, Rect};
+use crate::settings;
 use serde::{Deserialize, Serialize};
 use std::convert::TryInto;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tauri::{
-    AppHandle, Emitter, Manager, PhysicalPosition, Position, WebviewUrl, WebviewWindow,
-    WebviewWindowBuilder,
+    AppHandle, Emitter, LogicalSize, Manager, PhysicalPosition, Position, Size, WebviewUrl,
+    WebviewWindow, WebviewWindowBuilder,
 };
 
 const TOOLBAR_WIDTH: f64 = 80.0;
 const TOOLBAR_HEIGHT: f64 = 35.0;
 const TOOLBAR_VERTICAL_OFFSET: f64 = 10.0;
+// 前端测量内容后允许调整的默认尺寸范围（逻辑像素），未保存尺寸范围偏好时使用
+const TOOLBAR_MIN_WIDTH: f64 = 32.0;
+const TOOLBAR_MIN_HEIGHT: f64 = 24.0;
+const TOOLBAR_MAX_WIDTH: f64 = 480.0;
+const TOOLBAR_MAX_HEIGHT: f64 = 160.0;
 
 // 浮动结果窗口常量
 const RESULT_WINDOW_WIDTH: f64 = 360.0;
@@ -22,6 +31,187 @@ const RESULT_WINDOW_HEIGHT: f64 = 240.0;
 const RESULT_WINDOW_MIN_HEIGHT: f64 = 120.0;
 const RESULT_WINDOW_VERTICAL_GAP: f64 = 8.0;
 
+/// 工具栏垂直锚定偏好的旧持久化键（已并入 `ToolbarBehavior`，仅用于迁移）
+const SETTING_TOOLBAR_ANCHOR: &str = "selectionToolbarAnchor";
+
+/// 工具栏行为偏好的持久化键
+const SETTING_TOOLBAR_BEHAVIOR: &str = "selectionToolbarBehavior";
+/// 显示延迟上限（毫秒）
+const MAX_TOOLBAR_SHOW_DELAY_MS: u64 = 1_000;
+/// 自动隐藏的最短时间（毫秒），避免工具栏一闪而过
+const MIN_TOOLBAR_AUTO_HIDE_MS: u64 = 1_000;
+
+/// 工具栏窗口层级偏好的持久化键
+const SETTING_TOOLBAR_WINDOW_LEVEL: &str = "selectionToolbarWindowLevel";
+/// 工具栏默认动作的持久化键
+const SETTING_TOOLBAR_DEFAULT_ACTION: &str = "selectionToolbarDefaultAction";
+/// 工具栏主题偏好的持久化键
+const SETTING_TOOLBAR_THEME: &str = "selectionToolbarTheme";
+/// 全屏应用前台时提升工具栏层级的持久化键
+const SETTING_TOOLBAR_FULLSCREEN_BOOST: &str = "selectionToolbarFullscreenBoost";
+/// 工具栏可调整尺寸范围的持久化键
+const SETTING_TOOLBAR_SIZE_BOUNDS: &str = "selectionToolbarSizeBounds";
+
+/// 工具栏即将关闭的事件，前端据此播放淡出动画
+const EVENT_TOOLBAR_DISMISSING: &str = "toolbar-dismissing";
+/// 发出关闭事件后等待淡出动画完成的时间
+const TOOLBAR_DISMISS_ANIMATION_MS: u64 = 150;
+
+/// 临时禁用到期后通知前端的事件
+const EVENT_TOOLBAR_REENABLED: &str = "selection-toolbar-reenabled";
+
+/// 工具栏相对选区的垂直锚定偏好
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ToolbarAnchor {
+    /// 始终显示在选区上方
+    Above,
+    /// 始终显示在选区下方
+    Below,
+    /// 优先显示在上方，上方空间不足时改为下方
+    #[default]
+    Auto,
+}
+
+/// 工具栏窗口层级
+///
+/// macOS 对应 NSWindow 的 normal / floating / screen-saver 层级；
+/// Windows 与 Linux 对应非置顶、置顶以及每次显示时重新抢占置顶层顶部
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ToolbarWindowLevel {
+    /// 普通窗口层级，可被其他窗口遮挡
+    Normal,
+    /// 置顶（默认行为）
+    #[default]
+    Floating,
+    /// 最高层级，可显示在全屏应用之上
+    ScreenSaver,
+}
+
+/// 工具栏显示与关闭行为偏好
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolbarBehavior {
+    /// 划词后延迟显示的时间（毫秒），延迟期间出现新的划词则以新的为准
+    pub show_delay_ms: u64,
+    /// 显示后无操作自动隐藏的时间（毫秒），`None` 表示不自动隐藏
+    pub auto_hide_ms: Option<u64>,
+    /// 执行动作后关闭工具栏时是否播放淡出动画（`dismiss_selection_toolbar` 未指定时使用）
+    pub animate_dismiss: bool,
+    /// 是否让前端在显示时播放出现提示动画
+    pub appear_cue: bool,
+    /// 垂直锚定偏好
+    pub anchor: ToolbarAnchor,
+}
+
+impl Default for ToolbarBehavior {
+    fn default() -> Self {
+        Self {
+            show_delay_ms: 0,
+            auto_hide_ms: None,
+            animate_dismiss: true,
+            appear_cue: false,
+            anchor: ToolbarAnchor::default(),
+        }
+    }
+}
+
+impl ToolbarBehavior {
+    fn validate(&self) -> Result<(), String> {
+        if self.show_delay_ms > MAX_TOOLBAR_SHOW_DELAY_MS {
+            return Err(format!(
+                "show delay must not exceed {MAX_TOOLBAR_SHOW_DELAY_MS} ms"
+            ));
+        }
+        if self
+            .auto_hide_ms
+            .is_some_and(|ms| ms < MIN_TOOLBAR_AUTO_HIDE_MS)
+        {
+            return Err(format!(
+                "auto hide delay must be at least {MIN_TOOLBAR_AUTO_HIDE_MS} ms"
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 前端测量内容后允许调整的工具栏尺寸范围（逻辑像素）
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolbarSizeBounds {
+    pub min_width: f64,
+    pub min_height: f64,
+    pub max_width: f64,
+    pub max_height: f64,
+}
+
+impl Default for ToolbarSizeBounds {
+    fn default() -> Self {
+        Self {
+            min_width: TOOLBAR_MIN_WIDTH,
+            min_height: TOOLBAR_MIN_HEIGHT,
+            max_width: TOOLBAR_MAX_WIDTH,
+            max_height: TOOLBAR_MAX_HEIGHT,
+        }
+    }
+}
+
+impl ToolbarSizeBounds {
+    fn validate(&self) -> Result<(), String> {
+        let values = [
+            self.min_width,
+            self.min_height,
+            self.max_width,
+            self.max_height,
+        ];
+        if values
+            .iter()
+            .any(|value| !value.is_finite() || *value <= 0.0)
+        {
+            return Err("toolbar size bounds must be positive numbers".to_string());
+        }
+        if self.min_width > self.max_width || self.min_height > self.max_height {
+            return Err("toolbar minimum size must not exceed the maximum size".to_string());
+        }
+        Ok(())
+    }
+
+    fn clamp(&self, width: f64, height: f64) -> (f64, f64) {
+        (
+            width.clamp(self.min_width, self.max_width),
+            height.clamp(self.min_height, self.max_height),
+        )
+    }
+}
+
+/// 工具栏主题偏好
+///
+/// `System` 时工具栏跟随 `get_system_theme` 的结果与 `system-theme-changed` 事件
+///
+/// 以小写形式序列化，与前端主题取值一致；别名用于读取旧版本保存的首字母大写取值
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolbarTheme {
+    #[default]
+    #[serde(alias = "System")]
+    System,
+    #[serde(alias = "Light")]
+    Light,
+    #[serde(alias = "Dark")]
+    Dark,
+}
+
+/// 工具栏动作，用于指定默认动作供工具栏预先高亮或自动触发
+///
+/// 与工具栏按钮（翻译、解释、收藏）一一对应
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ToolbarAction {
+    Translate,
+    Explain,
+    Collect,
+}
+
 /// 工具栏窗口状态
 ///
 /// 记录最近一次展示时间、文本内容以及整体启用状态。
@@ -32,7 +222,30 @@ pub struct ToolbarState {
     last_text: Option<String>,
     enabled: bool,
     temporary_disabled_until: Option<SystemTime>,
+    /// 忽略列表，可匹配进程名、窗口类名或窗口标题
     ignored_apps: Vec<String>,
+    /// 最近一次展示时的锚点（光标位置），调整尺寸时保持锚定
+    last_anchor: Option<CursorPosition>,
+    /// 工具栏当前尺寸（逻辑像素），由前端测量内容后更新
+    toolbar_size: (f64, f64),
+    /// 前端测量内容后允许调整的尺寸范围
+    size_bounds: ToolbarSizeBounds,
+    /// 显示延迟、自动隐藏、动画与锚定等行为偏好
+    behavior: ToolbarBehavior,
+    /// 窗口层级偏好
+    window_level: ToolbarWindowLevel,
+    /// 默认动作，未设置时工具栏不预选任何动作
+    default_action: Option<ToolbarAction>,
+    /// 主题偏好
+    theme: ToolbarTheme,
+    /// 前台应用全屏时临时以最高层级显示工具栏（可能遮挡全屏内容，默认关闭）
+    fullscreen_boost: bool,
+    /// 临时禁用恢复定时器的代数，每次重新设置截止时间都会递增以使旧定时器失效
+    reenable_timer_generation: u64,
+    /// 一次性绕过忽略列表，下一次触发展示时消费
+    bypass_ignore_once: bool,
+    /// 待展示的文本是否因超过最大捕获长度被截断，下一次触发展示时消费
+    capture_truncated: bool,
 }
 
 impl Default for ToolbarState {
@@ -43,6 +256,17 @@ impl Default for ToolbarState {
             enabled: true,
             temporary_disabled_until: None,
             ignored_apps: Vec::new(),
+            last_anchor: None,
+            toolbar_size: (TOOLBAR_WIDTH, TOOLBAR_HEIGHT),
+            size_bounds: ToolbarSizeBounds::default(),
+            behavior: ToolbarBehavior::default(),
+            window_level: ToolbarWindowLevel::default(),
+            default_action: None,
+            theme: ToolbarTheme::default(),
+            fullscreen_boost: false,
+            reenable_timer_generation: 0,
+            bypass_ignore_once: false,
+            capture_truncated: false,
         }
     }
 }
@@ -78,6 +302,17 @@ impl ToolbarState {
         }
     }
 
+    /// 使之前的恢复定时器失效并返回新的定时器代数
+    fn next_reenable_timer_generation(&mut self) -> u64 {
+        self.reenable_timer_generation = self.reenable_timer_generation.wrapping_add(1);
+        self.reenable_timer_generation
+    }
+
+    /// 清空缓存的选中文本
+    pub fn clear_last_text(&mut self) {
+        self.last_text = None;
+    }
+
     pub fn set_ignored_apps(&mut self, apps: Vec<String>) {
         self.ignored_apps = apps
             .into_iter()
@@ -90,6 +325,20 @@ impl ToolbarState {
         &self.ignored_apps
     }
 
+    /// 标记下一次展示的文本已被截断
+    pub fn mark_capture_truncated(&mut self) {
+        self.capture_truncated = true;
+    }
+
+    /// 下一次触发是否会绕过忽略列表（不消费标记）
+    pub fn bypass_ignore_once(&self) -> bool {
+        self.bypass_ignore_once
+    }
+
+    /// 判断标识是否命中忽略列表
+    ///
+    /// 标识可以是进程名、窗口类名或窗口标题（均为小写），`ignored_apps` 中的条目按
+    /// 完全匹配、后缀或子串匹配，因此也可以用标题片段忽略特定窗口。
     pub fn should_ignore_app(&self, identifier: &str) -> bool {
         if self.ignored_apps.is_empty() {
             return false;
@@ -119,6 +368,12 @@ pub struct SelectionToolbarSnapshot {
     pub enabled: bool,
     pub temporary_disabled_until_ms: Option<u64>,
     pub ignored_apps: Vec<String>,
+    pub anchor: ToolbarAnchor,
+    pub behavior: ToolbarBehavior,
+    pub window_level: ToolbarWindowLevel,
+    pub default_toolbar_action: Option<ToolbarAction>,
+    pub theme: ToolbarTheme,
+    pub fullscreen_boost: bool,
 }
 
 fn system_time_to_millis(time: SystemTime) -> Option<u64> {
@@ -131,6 +386,23 @@ fn millis_to_system_time(ms: u64) -> Option<SystemTime> {
     UNIX_EPOCH.checked_add(Duration::from_millis(ms))
 }
 
+/// `toolbar-text-selected` 事件负载
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ToolbarTextSelectedPayload {
+    text: String,
+    /// 启发式识别的内容类型，供工具栏优先展示相关动作
+    content_type: SelectionContentType,
+    /// 用户设置的默认动作
+    default_toolbar_action: Option<ToolbarAction>,
+    /// 文本是否因超过最大捕获长度被截断
+    truncated: bool,
+    /// 是否播放出现提示动画
+    appear_cue: bool,
+    /// 主题偏好
+    theme: ToolbarTheme,
+}
+
 /// 光标位置信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CursorPosition {
@@ -154,16 +426,20 @@ pub async fn show_selection_toolbar(
     position: CursorPosition,
     toolbar_state: tauri::State<'_, ToolbarManager>,
 ) -> Result<(), String> {
-    show_toolbar_internal(&app, text, position, toolbar_state.inner().clone()).await
+    show_toolbar_internal(&app, text, position, toolbar_state.inner().clone())
+        .await
+        .map(|_| ())
 }
 
 /// 外部调用接口 (例如全局监听器) —— 直接使用工具栏管理器实例
+///
+/// 返回工具栏是否接受了该文本（未被禁用状态、忽略列表或节流拦截）
 pub async fn show_selection_toolbar_with_manager(
     app: AppHandle,
     text: String,
     position: CursorPosition,
     toolbar_manager: ToolbarManager,
-) -> Result<(), String> {
+) -> Result<bool, String> {
     show_toolbar_internal(&app, text, position, toolbar_manager).await
 }
 
@@ -191,12 +467,14 @@ pub async fn show_selection_toolbar_with_manager(
 ///
 /// - 用户按下划词快捷键（Ctrl/Cmd+Shift+S）
 /// - 即使工具栏处于临时禁用倒计时中，也应响应快捷键
+///
+/// 返回值含义同 `show_selection_toolbar_with_manager`
 pub async fn show_selection_toolbar_force_with_manager(
     app: AppHandle,
     text: String,
     position: CursorPosition,
     toolbar_manager: ToolbarManager,
-) -> Result<(), String> {
+) -> Result<bool, String> {
     // 步骤 1: 获取并保存当前的临时禁用截止时间
     let original_disable_until = {
         let mut state = toolbar_manager
@@ -238,6 +516,105 @@ pub async fn hide_selection_toolbar(
     hide_toolbar_internal(&app, toolbar_state.inner()).await
 }
 
+/// 执行动作后关闭工具栏
+///
+/// `animate` 为 true 时先发送 `toolbar-dismissing` 事件供前端淡出，固定延迟后再隐藏；
+/// 未指定时使用行为偏好中的 `animate_dismiss`。
+/// 延迟期间若工具栏因新的划词重新显示，则不再隐藏。
+#[tauri::command]
+pub async fn dismiss_selection_toolbar(
+    app: AppHandle,
+    animate: Option<bool>,
+    toolbar_state: tauri::State<'_, ToolbarManager>,
+) -> Result<(), String> {
+    let animate = match animate {
+        Some(animate) => animate,
+        None => {
+            toolbar_state
+                .lock()
+                .map_err(|e| format!("Failed to lock toolbar state: {}", e))?
+                .behavior
+                .animate_dismiss
+        }
+    };
+    if !animate {
+        return hide_toolbar_internal(&app, toolbar_state.inner()).await;
+    }
+
+    let Some(window) = app.get_webview_window("selection-toolbar") else {
+        return Ok(());
+    };
+    let shown_at = toolbar_state
+        .lock()
+        .map_err(|e| format!("Failed to lock toolbar state: {}", e))?
+        .last_shown_at;
+
+    if let Err(error) = window.emit(EVENT_TOOLBAR_DISMISSING, ()) {
+        log::warn!("Failed to emit toolbar dismissing event: {}", error);
+    }
+    tokio::time::sleep(Duration::from_millis(TOOLBAR_DISMISS_ANIMATION_MS)).await;
+
+    let reshown = toolbar_state
+        .lock()
+        .map_err(|e| format!("Failed to lock toolbar state: {}", e))?
+        .last_shown_at
+        != shown_at;
+    if reshown {
+        log::debug!("Toolbar re-shown during dismiss animation, keeping it visible");
+        return Ok(());
+    }
+
+    hide_toolbar_internal(&app, toolbar_state.inner()).await
+}
+
+/// 让工具栏窗口获得键盘焦点，便于用方向键在动作之间切换
+///
+/// 工具栏创建时不抢占焦点；选中文本在显示前已捕获，聚焦工具栏不影响本次动作，
+/// 工具栏隐藏后系统会把焦点交还给之前的应用。
+#[tauri::command]
+pub async fn focus_selection_toolbar(app: AppHandle) -> Result<(), String> {
+    let window = app
+        .get_webview_window("selection-toolbar")
+        .ok_or_else(|| "Selection toolbar window not found".to_string())?;
+    if !window.is_visible().unwrap_or(false) {
+        return Err("Selection toolbar is not visible".to_string());
+    }
+
+    window
+        .set_focus()
+        .map_err(|e| format!("Failed to focus toolbar window: {}", e))?;
+    log::debug!("Selection toolbar focused for keyboard navigation");
+    Ok(())
+}
+
+/// 销毁工具栏窗口并清除缓存的展示状态，下一次显示时重新创建
+///
+/// 用于工具栏 WebView 白屏或卡死时的恢复；启用状态、忽略列表等偏好保持不变。
+#[tauri::command]
+pub async fn recreate_selection_toolbar(
+    app: AppHandle,
+    toolbar_state: tauri::State<'_, ToolbarManager>,
+) -> Result<(), String> {
+    {
+        let mut state = toolbar_state
+            .lock()
+            .map_err(|e| format!("Failed to lock toolbar state: {}", e))?;
+        state.last_shown_at = None;
+        state.last_text = None;
+        state.last_anchor = None;
+        state.toolbar_size = (TOOLBAR_WIDTH, TOOLBAR_HEIGHT);
+    }
+
+    if let Some(window) = app.get_webview_window("selection-toolbar") {
+        log::info!("Destroying selection toolbar window for recreation");
+        window
+            .destroy()
+            .map_err(|e| format!("Failed to destroy toolbar window: {}", e))?;
+    }
+
+    Ok(())
+}
+
 pub async fn hide_selection_toolbar_with_manager(
     app: AppHandle,
     toolbar_manager: ToolbarManager,
@@ -269,6 +646,8 @@ pub async fn set_selection_toolbar_enabled(
             "Selection toolbar {}",
             if enabled { "enabled" } else { "disabled" }
         );
+        // 系统级划词钩子的生命周期跟随工具栏启用状态
+        crate::global_selection::set_global_selection_monitor_active(&app, enabled);
     } else {
         log::debug!(
             "Selection toolbar enable request ignored because state unchanged: {}",
@@ -279,6 +658,19 @@ pub async fn set_selection_toolbar_enabled(
     Ok(())
 }
 
+/// 允许下一次划词或快捷键触发无视忽略列表展示工具栏，触发后自动失效
+#[tauri::command]
+pub async fn bypass_ignore_list_once(
+    toolbar_state: tauri::State<'_, ToolbarManager>,
+) -> Result<(), String> {
+    toolbar_state
+        .lock()
+        .map_err(|e| format!("Failed to lock toolbar state: {}", e))?
+        .bypass_ignore_once = true;
+    log::info!("Selection toolbar will bypass the ignore list for the next trigger");
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn set_selection_toolbar_ignored_apps(
     apps: Vec<String>,
@@ -305,14 +697,16 @@ pub async fn set_selection_toolbar_temporary_disabled_until(
 ) -> Result<(), String> {
     let resolved = until.and_then(millis_to_system_time);
 
-    {
+    let generation = {
         let mut state = toolbar_state
             .lock()
             .map_err(|e| format!("Failed to lock toolbar state: {}", e))?;
         state.set_temporary_disabled_until(resolved);
-    }
+        state.next_reenable_timer_generation()
+    };
 
     if let Some(target) = resolved {
+        spawn_reenable_timer(&app, toolbar_state.inner().clone(), target, generation);
         if let Some(ms) = system_time_to_millis(target) {
             log::info!(
                 "Selection toolbar temporarily disabled (restore at {} ms since epoch)",
@@ -329,6 +723,447 @@ pub async fn set_selection_toolbar_temporary_disabled_until(
     Ok(())
 }
 
+/// 在临时禁用截止时间到达时发送 `selection-toolbar-reenabled` 事件
+///
+/// 若期间截止时间被修改或清除，定时器代数会变化，旧定时器到期后直接退出。
+fn spawn_reenable_timer(
+    app: &AppHandle,
+    manager: ToolbarManager,
+    target: SystemTime,
+    generation: u64,
+) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let delay = target
+            .duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO);
+        tokio::time::sleep(delay).await;
+
+        {
+            let Ok(mut state) = manager.lock() else {
+                log::warn!("Failed to lock toolbar state for re-enable timer");
+                return;
+            };
+            if state.reenable_timer_generation != generation {
+                return;
+            }
+            if state.temporary_disabled_until() == Some(target) {
+                state.set_temporary_disabled_until(None);
+            }
+        }
+
+        log::info!("Selection toolbar temporary disable elapsed");
+        if let Err(error) = app.emit(EVENT_TOOLBAR_REENABLED, ()) {
+            log::warn!("Failed to emit toolbar re-enabled event: {}", error);
+        }
+    });
+}
+
+/// 设置工具栏垂直锚定偏好并持久化
+#[tauri::command]
+pub async fn set_selection_toolbar_anchor(
+    app: AppHandle,
+    anchor: ToolbarAnchor,
+    toolbar_state: tauri::State<'_, ToolbarManager>,
+) -> Result<(), String> {
+    let behavior = ToolbarBehavior {
+        anchor,
+        ..toolbar_state
+            .lock()
+            .map_err(|e| format!("Failed to lock toolbar state: {}", e))?
+            .behavior
+    };
+    save_toolbar_behavior(&app, toolbar_state.inner(), behavior)?;
+
+    log::info!("Selection toolbar anchor set to {:?}", anchor);
+    Ok(())
+}
+
+/// 获取工具栏行为偏好
+#[tauri::command]
+pub async fn get_toolbar_behavior(
+    toolbar_state: tauri::State<'_, ToolbarManager>,
+) -> Result<ToolbarBehavior, String> {
+    Ok(toolbar_state
+        .lock()
+        .map_err(|e| format!("Failed to lock toolbar state: {}", e))?
+        .behavior)
+}
+
+/// 整体设置工具栏行为偏好并持久化
+#[tauri::command]
+pub async fn set_toolbar_behavior(
+    app: AppHandle,
+    behavior: ToolbarBehavior,
+    toolbar_state: tauri::State<'_, ToolbarManager>,
+) -> Result<(), String> {
+    behavior.validate()?;
+    save_toolbar_behavior(&app, toolbar_state.inner(), behavior)?;
+
+    log::info!("Selection toolbar behavior updated: {:?}", behavior);
+    Ok(())
+}
+
+fn save_toolbar_behavior(
+    app: &AppHandle,
+    toolbar_manager: &ToolbarManager,
+    behavior: ToolbarBehavior,
+) -> Result<(), String> {
+    settings::save_setting(app, SETTING_TOOLBAR_BEHAVIOR, &behavior)?;
+    toolbar_manager
+        .lock()
+        .map_err(|e| format!("Failed to lock toolbar state: {}", e))?
+        .behavior = behavior;
+    Ok(())
+}
+
+/// 设置工具栏默认动作并持久化，传入 `null` 清除
+#[tauri::command]
+pub async fn set_selection_toolbar_default_action(
+    app: AppHandle,
+    action: Option<ToolbarAction>,
+    toolbar_state: tauri::State<'_, ToolbarManager>,
+) -> Result<(), String> {
+    settings::save_setting(&app, SETTING_TOOLBAR_DEFAULT_ACTION, &action)?;
+
+    toolbar_state
+        .lock()
+        .map_err(|e| format!("Failed to lock toolbar state: {}", e))?
+        .default_action = action;
+
+    log::info!("Selection toolbar default action set to {:?}", action);
+    Ok(())
+}
+
+/// 设置工具栏主题偏好并持久化，下一次展示时随 `toolbar-text-selected` 事件下发
+#[tauri::command]
+pub async fn set_toolbar_theme(
+    app: AppHandle,
+    theme: ToolbarTheme,
+    toolbar_state: tauri::State<'_, ToolbarManager>,
+) -> Result<(), String> {
+    settings::save_setting(&app, SETTING_TOOLBAR_THEME, &theme)?;
+
+    toolbar_state
+        .lock()
+        .map_err(|e| format!("Failed to lock toolbar state: {}", e))?
+        .theme = theme;
+
+    log::info!("Selection toolbar theme set to {:?}", theme);
+    Ok(())
+}
+
+/// 设置工具栏窗口层级并持久化，工具栏窗口已存在时立即生效
+#[tauri::command]
+pub async fn set_selection_toolbar_window_level(
+    app: AppHandle,
+    level: ToolbarWindowLevel,
+    toolbar_state: tauri::State<'_, ToolbarManager>,
+) -> Result<(), String> {
+    settings::save_setting(&app, SETTING_TOOLBAR_WINDOW_LEVEL, &level)?;
+
+    toolbar_state
+        .lock()
+        .map_err(|e| format!("Failed to lock toolbar state: {}", e))?
+        .window_level = level;
+
+    if let Some(window) = app.get_webview_window("selection-toolbar") {
+        apply_toolbar_window_level(&window, level);
+    }
+
+    log::info!("Selection toolbar window level set to {:?}", level);
+    Ok(())
+}
+
+/// 启动时从后端偏好恢复工具栏设置
+pub(crate) fn load_toolbar_preferences(app: &AppHandle) {
+    let behavior = settings::load_setting::<ToolbarBehavior>(app, SETTING_TOOLBAR_BEHAVIOR)
+        .filter(|behavior| match behavior.validate() {
+            Ok(()) => true,
+            Err(err) => {
+                log::warn!("Ignoring saved toolbar behavior: {}", err);
+                false
+            }
+        })
+        .or_else(|| {
+            settings::load_setting::<ToolbarAnchor>(app, SETTING_TOOLBAR_ANCHOR).map(|anchor| {
+                ToolbarBehavior {
+                    anchor,
+                    ..ToolbarBehavior::default()
+                }
+            })
+        });
+    let window_level =
+        settings::load_setting::<ToolbarWindowLevel>(app, SETTING_TOOLBAR_WINDOW_LEVEL);
+    let default_action =
+        settings::load_setting::<Option<ToolbarAction>>(app, SETTING_TOOLBAR_DEFAULT_ACTION)
+            .flatten();
+    let theme = settings::load_setting::<ToolbarTheme>(app, SETTING_TOOLBAR_THEME);
+    let fullscreen_boost = settings::load_setting::<bool>(app, SETTING_TOOLBAR_FULLSCREEN_BOOST);
+    let size_bounds = settings::load_setting::<ToolbarSizeBounds>(app, SETTING_TOOLBAR_SIZE_BOUNDS)
+        .filter(|bounds| match bounds.validate() {
+            Ok(()) => true,
+            Err(err) => {
+                log::warn!("Ignoring saved toolbar size bounds: {}", err);
+                false
+            }
+        });
+    if behavior.is_none()
+        && window_level.is_none()
+        && default_action.is_none()
+        && theme.is_none()
+        && fullscreen_boost.is_none()
+        && size_bounds.is_none()
+    {
+        return;
+    }
+
+    if let Some(manager) = app.try_state::<ToolbarManager>() {
+        match manager.lock() {
+            Ok(mut state) => {
+                if let Some(behavior) = behavior {
+                    state.behavior = behavior;
+                }
+                if let Some(window_level) = window_level {
+                    state.window_level = window_level;
+                }
+                state.default_action = default_action;
+                if let Some(theme) = theme {
+                    state.theme = theme;
+                }
+                if let Some(fullscreen_boost) = fullscreen_boost {
+                    state.fullscreen_boost = fullscreen_boost;
+                }
+                if let Some(size_bounds) = size_bounds {
+                    state.size_bounds = size_bounds;
+                }
+            }
+            Err(e) => log::warn!("Failed to lock toolbar state: {}", e),
+        }
+    }
+}
+
+/// 设置前端测量内容后允许调整的工具栏尺寸范围并持久化，下一次调整尺寸时生效
+#[tauri::command]
+pub async fn set_selection_toolbar_size_bounds(
+    app: AppHandle,
+    bounds: ToolbarSizeBounds,
+    toolbar_state: tauri::State<'_, ToolbarManager>,
+) -> Result<(), String> {
+    bounds.validate()?;
+    settings::save_setting(&app, SETTING_TOOLBAR_SIZE_BOUNDS, &bounds)?;
+
+    toolbar_state
+        .lock()
+        .map_err(|e| format!("Failed to lock toolbar state: {}", e))?
+        .size_bounds = bounds;
+
+    log::info!("Selection toolbar size bounds set to {:?}", bounds);
+    Ok(())
+}
+
+/// 设置前台应用全屏时是否临时提升工具栏层级并持久化
+///
+/// 开启后，若展示时前台窗口处于全屏，本次以最高层级（macOS 加入全屏空间，Windows 置顶且不激活）显示，
+/// 下一次非全屏展示时恢复为层级偏好。
+#[tauri::command]
+pub async fn set_selection_toolbar_fullscreen_boost(
+    app: AppHandle,
+    enabled: bool,
+    toolbar_state: tauri::State<'_, ToolbarManager>,
+) -> Result<(), String> {
+    settings::save_setting(&app, SETTING_TOOLBAR_FULLSCREEN_BOOST, &enabled)?;
+
+    toolbar_state
+        .lock()
+        .map_err(|e| format!("Failed to lock toolbar state: {}", e))?
+        .fullscreen_boost = enabled;
+
+    log::info!("Selection toolbar fullscreen boost set to {}", enabled);
+    Ok(())
+}
+
+/// 计算本次展示实际使用的窗口层级：开启全屏提升且前台应用全屏时使用最高层级
+fn effective_toolbar_window_level(
+    level: ToolbarWindowLevel,
+    fullscreen_boost: bool,
+) -> ToolbarWindowLevel {
+    if !fullscreen_boost || level == ToolbarWindowLevel::ScreenSaver {
+        return level;
+    }
+    if foreground_app_is_fullscreen() {
+        log::debug!("Foreground app is fullscreen, raising toolbar window level");
+        ToolbarWindowLevel::ScreenSaver
+    } else {
+        level
+    }
+}
+
+/// 检测前台应用是否处于全屏状态（Linux 暂不支持，始终返回 `false`）
+fn foreground_app_is_fullscreen() -> bool {
+    #[cfg(target_os = "windows")]
+    {
+        foreground_app_is_fullscreen_windows()
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        foreground_app_is_fullscreen_macos()
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        false
+    }
+}
+
+/// 前台窗口覆盖其所在显示器的完整区域（含任务栏）即视为全屏；桌面与 Shell 窗口除外
+#[cfg(target_os = "windows")]
+fn foreground_app_is_fullscreen_windows() -> bool {
+    use windows::Win32::Foundation::RECT;
+    use windows::Win32::Graphics::Gdi::{
+        GetMonitorInfoW, MonitorFromWindow, MONITORINFO, MONITOR_DEFAULTTONEAREST,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetDesktopWindow, GetForegroundWindow, GetShellWindow, GetWindowRect,
+    };
+
+    unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd.is_invalid() || hwnd == GetDesktopWindow() || hwnd == GetShellWindow() {
+            return false;
+        }
+
+        let mut rect = RECT::default();
+        if GetWindowRect(hwnd, &mut rect).is_err() {
+            return false;
+        }
+
+        let monitor = MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST);
+        let mut info = MONITORINFO {
+            cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+            ..Default::default()
+        };
+        if !GetMonitorInfoW(monitor, &mut info).as_bool() {
+            return false;
+        }
+
+        let screen = info.rcMonitor;
+        rect.left <= screen.left
+            && rect.top <= screen.top
+            && rect.right >= screen.right
+            && rect.bottom >= screen.bottom
+    }
+}
+
+/// 读取前台应用焦点窗口的 `AXFullScreen` 属性
+#[cfg(target_os = "macos")]
+fn foreground_app_is_fullscreen_macos() -> bool {
+    use accessibility::{AXAttribute, AXUIElement};
+    use core_foundation::boolean::CFBoolean;
+    use core_foundation::string::CFString;
+
+    fn element_attribute(element: &AXUIElement, name: &'static str) -> Option<AXUIElement> {
+        let attr = AXAttribute::new(&CFString::from_static_string(name));
+        element.attribute(&attr).ok()?.downcast::<AXUIElement>()
+    }
+
+    let system = AXUIElement::system_wide();
+    let Some(window) = element_attribute(&system, "AXFocusedApplication")
+        .and_then(|application| element_attribute(&application, "AXFocusedWindow"))
+    else {
+        return false;
+    };
+
+    let fullscreen_attr = AXAttribute::new(&CFString::from_static_string("AXFullScreen"));
+    window
+        .attribute(&fullscreen_attr)
+        .ok()
+        .and_then(|value| value.downcast::<CFBoolean>())
+        .map(bool::from)
+        .unwrap_or(false)
+}
+
+/// 按层级偏好设置工具栏窗口的置顶状态
+fn apply_toolbar_window_level(window: &WebviewWindow, level: ToolbarWindowLevel) {
+    let always_on_top = level != ToolbarWindowLevel::Normal;
+    if let Err(error) = window.set_always_on_top(always_on_top) {
+        log::warn!("Failed to set toolbar always-on-top: {}", error);
+    }
+
+    #[cfg(target_os = "macos")]
+    apply_toolbar_window_level_macos(window, level);
+
+    #[cfg(target_os = "windows")]
+    if level == ToolbarWindowLevel::ScreenSaver {
+        raise_toolbar_topmost_windows(window);
+    }
+}
+
+/// 设置 NSWindow 层级；最高层级同时加入所有空间并作为全屏辅助窗口，才能显示在全屏应用之上
+#[cfg(target_os = "macos")]
+fn apply_toolbar_window_level_macos(window: &WebviewWindow, level: ToolbarWindowLevel) {
+    use objc2::msg_send;
+    use objc2::runtime::AnyObject;
+
+    // NSNormalWindowLevel / NSFloatingWindowLevel / NSScreenSaverWindowLevel
+    let ns_level: isize = match level {
+        ToolbarWindowLevel::Normal => 0,
+        ToolbarWindowLevel::Floating => 3,
+        ToolbarWindowLevel::ScreenSaver => 1000,
+    };
+    // NSWindowCollectionBehaviorCanJoinAllSpaces | NSWindowCollectionBehaviorFullScreenAuxiliary
+    const FULL_SCREEN_BEHAVIOR: usize = (1 << 0) | (1 << 8);
+
+    let Ok(ns_window) = window.ns_window() else {
+        log::warn!("Failed to access toolbar NSWindow");
+        return;
+    };
+    let ns_window = ns_window as usize;
+    let result = window.run_on_main_thread(move || unsafe {
+        let ns_window = &*(ns_window as *const AnyObject);
+        let _: () = msg_send![ns_window, setLevel: ns_level];
+        let behavior: usize = msg_send![ns_window, collectionBehavior];
+        let behavior = if level == ToolbarWindowLevel::ScreenSaver {
+            behavior | FULL_SCREEN_BEHAVIOR
+        } else {
+            behavior & !FULL_SCREEN_BEHAVIOR
+        };
+        let _: () = msg_send![ns_window, setCollectionBehavior: behavior];
+    });
+    if let Err(error) = result {
+        log::warn!("Failed to set toolbar window level: {}", error);
+    }
+}
+
+/// 将工具栏重新插入置顶层的最顶部，盖过其他置顶窗口
+#[cfg(target_os = "windows")]
+fn raise_toolbar_topmost_windows(window: &WebviewWindow) {
+    use windows::Win32::UI::WindowsAndMessaging::{
+        SetWindowPos, HWND_TOPMOST, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE,
+    };
+
+    let Ok(hwnd) = window.hwnd() else {
+        log::warn!("Failed to access toolbar window handle");
+        return;
+    };
+    // Tauri 依赖的 windows crate 版本与本项目不同，按原始指针转换句柄
+    let hwnd = windows::Win32::Foundation::HWND(hwnd.0);
+    unsafe {
+        if let Err(error) = SetWindowPos(
+            hwnd,
+            Some(HWND_TOPMOST),
+            0,
+            0,
+            0,
+            0,
+            SWP_NOACTIVATE | SWP_NOMOVE | SWP_NOSIZE,
+        ) {
+            log::warn!("Failed to raise toolbar window: {}", error);
+        }
+    }
+}
+
 /// 获取当前划词工具栏的状态快照
 ///
 /// 主要用于前端在 Webview 首次挂载时同步 Rust 端已经缓存的文本与启用状态，
@@ -354,9 +1189,40 @@ pub async fn get_selection_toolbar_state(
         enabled: state.is_enabled(),
         temporary_disabled_until_ms,
         ignored_apps: state.ignored_apps().to_vec(),
+        anchor: state.behavior.anchor,
+        behavior: state.behavior,
+        window_level: state.window_level,
+        default_toolbar_action: state.default_action,
+        theme: state.theme,
+        fullscreen_boost: state.fullscreen_boost,
     })
 }
 
+/// 获取临时禁用剩余时间（毫秒）
+///
+/// 基于后端时钟计算差值，避免前端与后端时钟偏差导致倒计时不准；未禁用时返回 `None`。
+#[tauri::command]
+pub async fn get_temporary_disable_remaining_ms(
+    toolbar_state: tauri::State<'_, ToolbarManager>,
+) -> Result<Option<u64>, String> {
+    let mut state = toolbar_state
+        .lock()
+        .map_err(|e| format!("Failed to lock toolbar state: {}", e))?;
+
+    if !state.is_temporarily_disabled() {
+        return Ok(None);
+    }
+
+    Ok(state.temporary_disabled_until().map(|until| {
+        until
+            .duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO)
+            .as_millis()
+            .try_into()
+            .unwrap_or(u64::MAX)
+    }))
+}
+
 async fn hide_toolbar_internal(
     app: &AppHandle,
     toolbar_manager: &ToolbarManager,
@@ -382,6 +1248,105 @@ async fn hide_toolbar_internal(
     Ok(())
 }
 
+/// 根据前端测量的内容尺寸调整工具栏窗口
+///
+/// 尺寸会被限制在尺寸范围偏好（未设置时为默认范围）内，并以最近一次展示的光标位置重新定位，避免按钮被裁剪。
+#[tauri::command]
+pub async fn resize_selection_toolbar(
+    app: AppHandle,
+    width: f64,
+    height: f64,
+    toolbar_state: tauri::State<'_, ToolbarManager>,
+) -> Result<(), String> {
+    if !width.is_finite() || !height.is_finite() {
+        return Err("Invalid toolbar size".to_string());
+    }
+    let (width, height, anchor, placement) = {
+        let mut state = toolbar_state
+            .lock()
+            .map_err(|e| format!("Failed to lock toolbar state: {}", e))?;
+        let (width, height) = state.size_bounds.clamp(width, height);
+        state.toolbar_size = (width, height);
+        (
+            width,
+            height,
+            state.last_anchor.clone(),
+            state.behavior.anchor,
+        )
+    };
+
+    let Some(window) = app.get_webview_window("selection-toolbar") else {
+        return Ok(());
+    };
+
+    log::debug!("Resizing selection toolbar to {}x{}", width, height);
+    window
+        .set_size(Size::Logical(LogicalSize::new(width, height)))
+        .map_err(|e| format!("Failed to resize toolbar window: {}", e))?;
+
+    if let Some(anchor) = anchor {
+        let (scale_factor, area) = toolbar_screen_geometry(&app, &window, &anchor);
+        let position =
+            toolbar_position_for_anchor(&anchor, (width, height), scale_factor, area, placement);
+        if let Err(error) = window.set_position(Position::Physical(position)) {
+            log::warn!("Failed to reposition resized toolbar window: {}", error);
+        }
+    }
+
+    Ok(())
+}
+
+/// 解析锚点所在显示器的缩放比例与工作区（物理像素）
+///
+/// 无法获取显示器信息时回退到窗口缩放比例，并仅限制在屏幕左上边界内。
+fn toolbar_screen_geometry(
+    app: &AppHandle,
+    window: &WebviewWindow,
+    anchor: &CursorPosition,
+) -> (f64, Rect) {
+    match screen::monitor_containing(app, (anchor.x, anchor.y)) {
+        Some(monitor) => (monitor.scale_factor, screen::work_area(&monitor)),
+        None => (
+            window.scale_factor().unwrap_or(1.0),
+            Rect {
+                x: 0.0,
+                y: 0.0,
+                width: f64::INFINITY,
+                height: f64::INFINITY,
+            },
+        ),
+    }
+}
+
+/// 计算工具栏位置：水平居中于锚点，按锚定偏好位于锚点上方或下方，并限制在显示器工作区内
+fn toolbar_position_for_anchor(
+    anchor: &CursorPosition,
+    size: (f64, f64),
+    scale_factor: f64,
+    area: Rect,
+    placement: ToolbarAnchor,
+) -> PhysicalPosition<i32> {
+    let toolbar_width = size.0 * scale_factor;
+    let toolbar_height = size.1 * scale_factor;
+    let offset_y = TOOLBAR_VERTICAL_OFFSET * scale_factor;
+
+    let above_y = anchor.y - toolbar_height - offset_y;
+    let below_y = anchor.y + offset_y;
+    let toolbar_y = match placement {
+        ToolbarAnchor::Above => above_y,
+        ToolbarAnchor::Below => below_y,
+        ToolbarAnchor::Auto if above_y < area.y => below_y,
+        ToolbarAnchor::Auto => above_y,
+    }
+    .min(area.bottom() - toolbar_height)
+    .max(area.y);
+    let toolbar_x = (anchor.x - toolbar_width / 2.0)
+        .min(area.right() - toolbar_width)
+        .max(area.x);
+
+    PhysicalPosition::new(toolbar_x.round() as i32, toolbar_y.round() as i32)
+}
+
 /// 获取鼠标光标位置
 ///
 /// 返回屏幕坐标系下的光标位置
@@ -399,110 +1364,136 @@ pub async fn get_cursor_position() -> Result<CursorPosition, String> {
     }
 }
 
+/// 展示工具栏；返回 `false` 表示文本被启用状态、临时禁用、忽略列表或节流检查拒绝
 async fn show_toolbar_internal(
     app: &AppHandle,
     text: String,
     position: CursorPosition,
     toolbar_manager: ToolbarManager,
-) -> Result<(), String> {
+) -> Result<bool, String> {
     let trimmed_text = text.trim();
     if trimmed_text.is_empty() {
         log::debug!("Selection toolbar suppressed due to empty text");
-        return Ok(());
+        return Ok(false);
     }
-    let preview: String = trimmed_text
-        .split_whitespace()
-        .collect::<Vec<_>>()
-        .join(" ")
-        .chars()
-        .take(80)
-        .collect();
-    log::debug!("Selection toolbar text preview: \"{}\"", preview);
-
-    let mut state = toolbar_manager
-        .lock()
-        .map_err(|e| format!("Failed to lock toolbar state: {}", e))?;
+    log::debug!(
+        "Selection toolbar text: {}",
+        capture_log_preview(trimmed_text, 80)
+    );
 
-    if !state.enabled {
-        log::debug!("Selection toolbar suppressed because feature is disabled");
-        return Ok(());
-    }
+    let (
+        now,
+        truncated,
+        toolbar_size,
+        behavior,
+        window_level,
+        fullscreen_boost,
+        default_action,
+        theme,
+    ) = {
+        let mut state = toolbar_manager
+            .lock()
+            .map_err(|e| format!("Failed to lock toolbar state: {}", e))?;
+        // 截断标记只对应本次文本，无论是否展示都先消费
+        let truncated = std::mem::take(&mut state.capture_truncated);
 
-    if state.is_temporarily_disabled() {
-        log::debug!("Selection toolbar suppressed because feature is temporarily disabled");
-        return Ok(());
-    }
+        if !state.enabled {
+            log::debug!("Selection toolbar suppressed because feature is disabled");
+            return Ok(false);
+        }
 
-    let active_identifiers = resolve_active_app_identifiers();
-    if let Some(identifier) = active_identifiers
-        .iter()
-        .find(|identifier| state.should_ignore_app(identifier))
-    {
-        log::debug!(
-            "Selection toolbar suppressed due to ignored application identifier: {}",
-            identifier
-        );
-        return Ok(());
-    }
+        if state.is_temporarily_disabled() {
+            log::debug!("Selection toolbar suppressed because feature is temporarily disabled");
+            return Ok(false);
+        }
 
-    let now = Instant::now();
-    if let Some(last) = state.last_shown_at {
-        if now.duration_since(last) < Duration::from_millis(120)
-            && state
-                .last_text
-                .as_ref()
-                .map(|prev| prev == trimmed_text)
-                .unwrap_or(false)
+        // 一次性绕过标记无论本次是否命中忽略列表都会被消费
+        let bypass_ignore = std::mem::take(&mut state.bypass_ignore_once);
+        let active_identifiers = resolve_active_app_identifiers();
+        if let Some(identifier) = active_identifiers
+            .iter()
+            .find(|identifier| state.should_ignore_app(identifier))
         {
-            log::debug!("Selection toolbar suppressed due to throttle");
-            return Ok(());
+            if !bypass_ignore {
+                log::debug!(
+                    "Selection toolbar suppressed due to ignored application identifier: {}",
+                    identifier
+                );
+                return Ok(false);
+            }
+            log::info!(
+                "Selection toolbar bypassing ignore list once for identifier: {}",
+                identifier
+            );
         }
-    }
 
-    state.last_shown_at = Some(now);
-    state.last_text = Some(trimmed_text.to_string());
-
-    drop(state);
-
-    let window = ensure_toolbar_window(app)?;
-
-    let scale_factor = window.scale_factor().unwrap_or(1.0);
-    let toolbar_width = TOOLBAR_WIDTH * scale_factor;
-    let toolbar_height = TOOLBAR_HEIGHT * scale_factor;
-    let offset_y = TOOLBAR_VERTICAL_OFFSET * scale_factor;
+        let now = Instant::now();
+        if let Some(last) = state.last_shown_at {
+            if now.duration_since(last) < Duration::from_millis(120)
+                && state
+                    .last_text
+                    .as_ref()
+                    .map(|prev| prev == trimmed_text)
+                    .unwrap_or(false)
+            {
+                log::debug!("Selection toolbar suppressed due to throttle");
+                return Ok(false);
+            }
+        }
 
-    let mut toolbar_x = position.x - toolbar_width / 2.0;
-    let mut toolbar_y = position.y - toolbar_height - offset_y;
+        state.last_shown_at = Some(now);
+        state.last_text = Some(trimmed_text.to_string());
+        state.last_anchor = Some(position.clone());
+        (
+            now,
+            truncated,
+            state.toolbar_size,
+            state.behavior,
+            state.window_level,
+            state.fullscreen_boost,
+            state.default_action,
+            state.theme,
+        )
+    };
+    let placement = behavior.anchor;
 
-    if toolbar_x < 0.0 {
-        toolbar_x = 0.0;
+    if behavior.show_delay_ms > 0 {
+        tokio::time::sleep(Duration::from_millis(behavior.show_delay_ms)).await;
+        if !is_latest_show(&toolbar_manager, now) {
+            log::debug!("Selection toolbar show superseded during show delay");
+            return Ok(true);
+        }
     }
 
-    if toolbar_y < 0.0 {
-        toolbar_y = 0.0;
-    }
+    let window = ensure_toolbar_window(app)?;
+
+    let (scale_factor, area) = toolbar_screen_geometry(app, &window, &position);
 
     // 性能优化：批量执行窗口操作，减少闪烁
     // 1. 先设置位置（窗口可能不可见，此操作开销小）
-    let toolbar_x_i32 = toolbar_x.round() as i32;
-    let toolbar_y_i32 = toolbar_y.round() as i32;
-
-    if let Err(error) = window.set_position(Position::Physical(PhysicalPosition::new(
-        toolbar_x_i32,
-        toolbar_y_i32,
-    ))) {
+    let toolbar_position =
+        toolbar_position_for_anchor(&position, toolbar_size, scale_factor, area, placement);
+    if let Err(error) = window.set_position(Position::Physical(toolbar_position)) {
         log::warn!("Failed to position toolbar window: {}", error);
     }
 
-    // 2. 设置置顶（仅在需要时）
-    if let Err(error) = window.set_always_on_top(true) {
-        log::warn!("Failed to set toolbar always-on-top: {}", error);
-    }
+    // 2. 按层级偏好设置置顶（前台应用全屏时可临时提升层级）
+    apply_toolbar_window_level(
+        &window,
+        effective_toolbar_window_level(window_level, fullscreen_boost),
+    );
 
     // 3. 发送文本事件并显示窗口
     // 优化：移除不必要的 50ms 延迟和先隐藏再显示的逻辑
     // 原因：位置更新是同步的，无需等待；先隐藏会导致闪烁
-    let text_payload = trimmed_text.to_string();
+    let text_payload = ToolbarTextSelectedPayload {
+        text: trimmed_text.to_string(),
+        content_type: classify_selection(trimmed_text),
+        default_toolbar_action: default_action,
+        truncated,
+        appear_cue: behavior.appear_cue,
+        theme,
+    };
     if let Err(error) = window.emit("toolbar-text-selected", text_payload) {
         log::warn!("Failed to emit toolbar text event: {}", error);
     }
@@ -514,7 +1505,45 @@ async fn show_toolbar_internal(
         }
     }
 
-    Ok(())
+    if let Some(auto_hide_ms) = behavior.auto_hide_ms {
+        schedule_toolbar_auto_hide(app.clone(), toolbar_manager, now, auto_hide_ms);
+    }
+
+    Ok(true)
+}
+
+/// 判断 `shown_at` 是否仍是最近一次展示（期间没有新的划词或隐藏）
+fn is_latest_show(toolbar_manager: &ToolbarManager, shown_at: Instant) -> bool {
+    toolbar_manager
+        .lock()
+        .map(|state| state.last_shown_at == Some(shown_at))
+        .unwrap_or(false)
+}
+
+/// 显示后无操作一段时间自动隐藏；期间重新展示或工具栏获得焦点时保留
+fn schedule_toolbar_auto_hide(
+    app: AppHandle,
+    toolbar_manager: ToolbarManager,
+    shown_at: Instant,
+    auto_hide_ms: u64,
+) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(auto_hide_ms)).await;
+        if !is_latest_show(&toolbar_manager, shown_at) {
+            return;
+        }
+        let focused = app
+            .get_webview_window("selection-toolbar")
+            .and_then(|window| window.is_focused().ok())
+            .unwrap_or(false);
+        if focused {
+            return;
+        }
+        log::debug!("Auto-hiding selection toolbar after {} ms", auto_hide_ms);
+        if let Err(error) = hide_toolbar_internal(&app, &toolbar_manager).await {
+            log::warn!("Failed to auto-hide selection toolbar: {}", error);
+        }
+    });
 }
 
 fn ensure_toolbar_window(app: &AppHandle) -> Result<WebviewWindow, String> {
@@ -540,7 +1569,12 @@ pub(crate) fn resolve_active_app_identifiers() -> Vec<String> {
         resolve_active_app_identifiers_windows()
     }
 
-    #[cfg(not(target_os = "windows"))]
+    #[cfg(target_os = "macos")]
+    {
+        resolve_active_app_identifiers_macos()
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
     {
         Vec::new()
     }
@@ -558,7 +1592,7 @@ fn resolve_active_app_identifiers_windows() -> Vec<String> {
         PROCESS_QUERY_LIMITED_INFORMATION,
     };
     use windows::Win32::UI::WindowsAndMessaging::{
-        GetForegroundWindow, GetWindowThreadProcessId, RealGetWindowClassW,
+        GetForegroundWindow, GetWindowTextW, GetWindowThreadProcessId, RealGetWindowClassW,
     };
 
     let mut identifiers = Vec::new();
@@ -581,6 +1615,20 @@ fn resolve_active_app_identifiers_windows() -> Vec<String> {
             }
         }
 
+        // 窗口标题变化频繁，仅作为额外的匹配候选
+        let mut title_buffer = [0u16; 512];
+        let title_len = GetWindowTextW(hwnd, &mut title_buffer);
+        if title_len > 0 {
+            let trimmed = (title_len as usize).min(title_buffer.len());
+            let title = OsString::from_wide(&title_buffer[..trimmed])
+                .to_string_lossy()
+                .trim()
+                .to_lowercase();
+            if !title.is_empty() {
+                identifiers.push(title);
+            }
+        }
+
         let mut pid: u32 = 0;
         GetWindowThreadProcessId(hwnd, Some(&mut pid));
         if pid != 0 {
@@ -618,6 +1666,51 @@ fn resolve_active_app_identifiers_windows() -> Vec<String> {
     identifiers
 }
 
+#[cfg(target_os = "macos")]
+fn resolve_active_app_identifiers_macos() -> Vec<String> {
+    use accessibility::{AXAttribute, AXUIElement};
+    use core_foundation::string::CFString;
+
+    fn element_attribute(element: &AXUIElement, name: &'static str) -> Option<AXUIElement> {
+        let attr = AXAttribute::new(&CFString::from_static_string(name));
+        element.attribute(&attr).ok()?.downcast::<AXUIElement>()
+    }
+
+    let mut identifiers = Vec::new();
+
+    let system = AXUIElement::system_wide();
+    let Some(application) = element_attribute(&system, "AXFocusedApplication") else {
+        return identifiers;
+    };
+
+    let title_attr = AXAttribute::new(&CFString::from_static_string("AXTitle"));
+    if let Some(name) = application
+        .attribute(&title_attr)
+        .ok()
+        .and_then(|value| value.downcast::<CFString>())
+    {
+        let normalized = name.to_string().trim().to_lowercase();
+        if !normalized.is_empty() {
+            identifiers.push(normalized);
+        }
+    }
+
+    // 窗口标题变化频繁，仅作为额外的匹配候选
+    if let Some(title) = element_attribute(&application, "AXFocusedWindow")
+        .and_then(|window| window.attribute(&title_attr).ok())
+        .and_then(|value| value.downcast::<CFString>())
+    {
+        let normalized = title.to_string().trim().to_lowercase();
+        if !normalized.is_empty() {
+            identifiers.push(normalized);
+        }
+    }
+
+    identifiers.sort();
+    identifiers.dedup();
+    identifiers
+}
+
 pub(crate) fn platform_cursor_position() -> Result<(f64, f64), String> {
     #[cfg(target_os = "windows")]
     {
@@ -0,0 +1,53 @@
+//! 后端偏好设置持久化模块
+//!
+//! 仅由 Rust 侧维护的偏好（托盘行为、窗口外观等）统一保存在 `config.json` 的
+//! `backend_settings` 键下，与前端维护的 `app_config` 互不覆盖。
+
+use serde::{de::DeserializeOwned, Serialize};
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+const STORE_FILE: &str = "config.json";
+const STORE_KEY_BACKEND_SETTINGS: &str = "backend_settings";
+
+/// 读取单项后端偏好，不存在或格式不符时返回 `None`
+pub(crate) fn load_setting<T: DeserializeOwned>(app: &AppHandle, key: &str) -> Option<T> {
+    let store = match app.store(STORE_FILE) {
+        Ok(store) => store,
+        Err(err) => {
+            log::warn!("Failed to open settings store: {}", err);
+            return None;
+        }
+    };
+
+    let value = store.get(STORE_KEY_BACKEND_SETTINGS)?.get(key)?.clone();
+    match serde_json::from_value(value) {
+        Ok(parsed) => Some(parsed),
+        Err(err) => {
+            log::warn!("Ignoring malformed backend setting '{}': {}", key, err);
+            None
+        }
+    }
+}
+
+/// 写入单项后端偏好并立即落盘
+pub(crate) fn save_setting<T: Serialize>(
+    app: &AppHandle,
+    key: &str,
+    value: &T,
+) -> Result<(), String> {
+    let store = app.store(STORE_FILE).map_err(|err| err.to_string())?;
+    let value = serde_json::to_value(value).map_err(|err| err.to_string())?;
+
+    let mut settings = store
+        .get(STORE_KEY_BACKEND_SETTINGS)
+        .filter(serde_json::Value::is_object)
+        .unwrap_or_else(|| serde_json::json!({}));
+    settings[key] = value;
+
+    store.set(STORE_KEY_BACKEND_SETTINGS, settings);
+    store.save().map_err(|err| {
+        log::error!("Failed to save backend settings: {}", err);
+        err.to_string()
+    })
+}
@@ -0,0 +1,293 @@
+//! 全局快捷键工具模块
+//!
+//! 提供快捷键字符串的解析与规范化，保证前端快捷键编辑器的校验规则
+//! 与后端注册快捷键时使用的解析逻辑完全一致；同时负责注册应用的全局快捷键并记录注册结果。
+
+use std::fmt::Display;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
+
+use crate::global_selection;
+use crate::selection_toolbar::ToolbarManager;
+use crate::window_control::{
+    resolve_active_panel_window, resolve_main_window, show_main_window,
+    toggle_main_window_visibility,
+};
+
+/// 主快捷键两次触发的最小间隔
+const MAIN_SHORTCUT_THROTTLE: Duration = Duration::from_millis(350);
+
+#[cfg(target_os = "macos")]
+const MAIN_SHORTCUT: &str = "Cmd+Shift+A";
+#[cfg(not(target_os = "macos"))]
+const MAIN_SHORTCUT: &str = "Ctrl+Shift+A";
+
+#[cfg(target_os = "macos")]
+const TRANSLATION_SHORTCUT: &str = "Cmd+Shift+T";
+#[cfg(not(target_os = "macos"))]
+const TRANSLATION_SHORTCUT: &str = "Ctrl+Shift+T";
+
+#[cfg(target_os = "macos")]
+const SELECTION_SHORTCUT: &str = "Cmd+Shift+S";
+#[cfg(not(target_os = "macos"))]
+const SELECTION_SHORTCUT: &str = "Ctrl+Shift+S";
+
+/// 快捷键解析结果
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub(crate) struct AcceleratorValidation {
+    pub valid: bool,
+    /// 规范化后的快捷键字符串（如 `shift+control+KeyA`），可用于比较是否相同
+    pub normalized: Option<String>,
+    pub error: Option<String>,
+}
+
+/// 解析快捷键字符串，与注册快捷键时使用相同的 `Shortcut` 解析逻辑
+fn inspect_accelerator(accelerator: &str) -> AcceleratorValidation {
+    match accelerator.trim().parse::<Shortcut>() {
+        Ok(shortcut) => AcceleratorValidation {
+            valid: true,
+            normalized: Some(shortcut.to_string()),
+            error: None,
+        },
+        Err(err) => AcceleratorValidation {
+            valid: false,
+            normalized: None,
+            error: Some(err.to_string()),
+        },
+    }
+}
+
+/// 校验快捷键字符串是否可被解析
+#[tauri::command]
+pub(crate) async fn parse_accelerator(
+    accelerator: String,
+) -> Result<AcceleratorValidation, String> {
+    Ok(inspect_accelerator(&accelerator))
+}
+
+/// 单个全局快捷键的注册结果
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub(crate) struct ShortcutRegistrationStatus {
+    pub action: String,
+    pub accelerator: String,
+    pub registered: bool,
+    /// 解析或注册失败的原因（例如快捷键已被其他应用占用）
+    pub error: Option<String>,
+}
+
+/// 记录每个动作最近一次的快捷键注册结果
+#[derive(Default)]
+pub(crate) struct ShortcutRegistry {
+    statuses: Mutex<Vec<ShortcutRegistrationStatus>>,
+    /// 主快捷键上次触发时间，重新注册后继续沿用
+    last_main_trigger: Arc<Mutex<Option<Instant>>>,
+}
+
+impl ShortcutRegistry {
+    /// 记录注册结果，同一动作的旧记录会被替换
+    pub(crate) fn record<E: Display>(
+        &self,
+        action: &str,
+        accelerator: &str,
+        result: Result<(), E>,
+    ) {
+        let status = ShortcutRegistrationStatus {
+            action: action.to_string(),
+            accelerator: accelerator.to_string(),
+            registered: result.is_ok(),
+            error: result.err().map(|err| err.to_string()),
+        };
+        match &status.error {
+            Some(error) => log::warn!(
+                "Failed to register {} shortcut {}: {}",
+                action,
+                accelerator,
+                error
+            ),
+            None => log::info!("Registered {} shortcut: {}", action, accelerator),
+        }
+
+        let mut statuses = match self.statuses.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        match statuses.iter_mut().find(|entry| entry.action == action) {
+            Some(entry) => *entry = status,
+            None => statuses.push(status),
+        }
+    }
+
+    fn snapshot(&self) -> Vec<ShortcutRegistrationStatus> {
+        match self.statuses.lock() {
+            Ok(guard) => guard.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+}
+
+/// 注销并重新注册全部全局快捷键，结果写入 [`ShortcutRegistry`]
+///
+/// 启动时调用一次；快捷键被其他应用占用时可在冲突解除后再次调用重试
+pub(crate) fn register_global_shortcuts(app: &AppHandle) {
+    let Some(registry) = app.try_state::<ShortcutRegistry>() else {
+        log::warn!("Shortcut registry state missing, skipping shortcut registration");
+        return;
+    };
+    let global_shortcut = app.global_shortcut();
+
+    for accelerator in [MAIN_SHORTCUT, TRANSLATION_SHORTCUT, SELECTION_SHORTCUT] {
+        if let Ok(shortcut) = accelerator.parse::<Shortcut>() {
+            if global_shortcut.is_registered(shortcut) {
+                if let Err(err) = global_shortcut.unregister(shortcut) {
+                    log::warn!("Failed to unregister shortcut {}: {}", accelerator, err);
+                }
+            }
+        }
+    }
+
+    // 注册主快捷键
+    match MAIN_SHORTCUT.parse::<Shortcut>() {
+        Ok(shortcut) => {
+            log::info!("Registering main shortcut: {}", shortcut);
+            let throttle = registry.last_main_trigger.clone();
+            let handle = app.clone();
+            let result = global_shortcut.on_shortcut(shortcut, move |_app, _event, _shortcut| {
+                // 锁中毒时仍然恢复内部状态，避免因一次 panic 永久禁用快捷键
+                let mut last = match throttle.lock() {
+                    Ok(guard) => guard,
+                    Err(poisoned) => {
+                        log::warn!("Shortcut throttle mutex poisoned, recovering inner state");
+                        poisoned.into_inner()
+                    }
+                };
+                let now = Instant::now();
+                if let Some(previous) = *last {
+                    let elapsed = now.duration_since(previous);
+                    if elapsed < MAIN_SHORTCUT_THROTTLE {
+                        log::debug!(
+                            "Shortcut trigger throttled: {}ms < {}ms",
+                            elapsed.as_millis(),
+                            MAIN_SHORTCUT_THROTTLE.as_millis()
+                        );
+                        return;
+                    }
+                }
+
+                *last = Some(now);
+                log::debug!("Main shortcut triggered");
+
+                let app_handle = handle.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Some(window) = resolve_active_panel_window(&app_handle) {
+                        let _ = toggle_main_window_visibility(&window).await;
+                    }
+                });
+            });
+            registry.record("main", MAIN_SHORTCUT, result);
+        }
+        Err(err) => registry.record("main", MAIN_SHORTCUT, Err(err)),
+    }
+
+    // 注册翻译快捷键
+    match TRANSLATION_SHORTCUT.parse::<Shortcut>() {
+        Ok(shortcut) => {
+            log::info!("Registering translation shortcut: {}", shortcut);
+            let handle = app.clone();
+            let result = global_shortcut.on_shortcut(shortcut, move |_app, _event, _shortcut| {
+                log::debug!("Translation shortcut triggered");
+
+                let app_handle = handle.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Some(window) = resolve_main_window(&app_handle) {
+                        if show_main_window(&window).await.is_ok() {
+                            let _ = window.emit("translation-hotkey-triggered", ());
+                        }
+                    }
+                });
+            });
+            registry.record("translation", TRANSLATION_SHORTCUT, result);
+        }
+        Err(err) => registry.record("translation", TRANSLATION_SHORTCUT, Err(err)),
+    }
+
+    // 注册划词工具栏快捷键
+    match SELECTION_SHORTCUT.parse::<Shortcut>() {
+        Ok(shortcut) => {
+            log::info!("Registering selection toolbar shortcut: {}", shortcut);
+            let handle = app.clone();
+            let result = global_shortcut.on_shortcut(shortcut, move |_app, _event, _shortcut| {
+                log::debug!("Selection toolbar shortcut triggered");
+
+                let app_handle = handle.clone();
+                if let Some(toolbar_state) = app_handle.try_state::<ToolbarManager>() {
+                    let toolbar_manager = toolbar_state.inner().clone();
+                    global_selection::trigger_toolbar_from_hotkey(app_handle, toolbar_manager);
+                } else {
+                    log::warn!("Selection toolbar shortcut triggered but manager state missing");
+                }
+            });
+            registry.record("selection-toolbar", SELECTION_SHORTCUT, result);
+        }
+        Err(err) => registry.record("selection-toolbar", SELECTION_SHORTCUT, Err(err)),
+    }
+}
+
+/// 重新注册全部全局快捷键（例如占用快捷键的应用已退出），返回最新的注册结果
+#[tauri::command]
+pub(crate) async fn retry_shortcut_registration(
+    app: AppHandle,
+    registry: State<'_, ShortcutRegistry>,
+) -> Result<Vec<ShortcutRegistrationStatus>, String> {
+    log::info!("Retrying global shortcut registration");
+    register_global_shortcuts(&app);
+    Ok(registry.snapshot())
+}
+
+/// 获取各全局快捷键是否注册成功，用于提示被其他应用占用等静默失败
+#[tauri::command]
+pub(crate) async fn get_shortcut_registration_status(
+    registry: State<'_, ShortcutRegistry>,
+) -> Result<Vec<ShortcutRegistrationStatus>, String> {
+    Ok(registry.snapshot())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_case_insensitive_accelerators() {
+        let lower = inspect_accelerator("ctrl+shift+a");
+        let upper = inspect_accelerator("Ctrl+Shift+A");
+        assert!(lower.valid);
+        assert_eq!(lower.normalized, upper.normalized);
+        assert!(lower.error.is_none());
+    }
+
+    #[test]
+    fn registry_replaces_status_for_same_action() {
+        let registry = ShortcutRegistry::default();
+        registry.record("main", "Ctrl+Shift+A", Err("already registered"));
+        registry.record("translation", "Ctrl+Shift+T", Ok::<(), String>(()));
+        registry.record("main", "Ctrl+Shift+A", Ok::<(), String>(()));
+
+        let statuses = registry.snapshot();
+        assert_eq!(statuses.len(), 2);
+        assert_eq!(statuses[0].action, "main");
+        assert!(statuses[0].registered);
+        assert!(statuses[0].error.is_none());
+        assert!(statuses[1].registered);
+    }
+
+    #[test]
+    fn reports_error_for_invalid_accelerator() {
+        let result = inspect_accelerator("Ctrl+NotAKey");
+        assert!(!result.valid);
+        assert!(result.normalized.is_none());
+        assert!(result.error.is_some());
+    }
+}
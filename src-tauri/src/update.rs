@@ -7,32 +7,78 @@
 //! - 通过缓存结构避免重复解析同一版本的 Release 元数据
 
 use std::{
-    collections::HashMap,
+    collections::{hash_map::RandomState, HashMap},
     fs,
+    hash::{BuildHasher, Hasher},
     path::{Path, PathBuf},
-    sync::{Arc, Mutex, OnceLock},
-    time::{Duration, SystemTime, UNIX_EPOCH},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use anyhow::{anyhow, Context};
 use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
 use semver::Version;
-use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Emitter, Manager};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_store::StoreExt;
 use time::format_description::well_known::Rfc3339;
 use tokio::{fs as async_fs, io::AsyncWriteExt};
 
-use crate::proxy::{build_client_with_proxy, ProxyTestConfig};
+use crate::app_paths;
+use crate::i18n::Message;
+use crate::proxy::{
+    active_proxy, build_client_with_proxy, normalize_user_agent, validate_proxy_config,
+    ProxyTestConfig, DOWNLOAD_MAX_REDIRECTS,
+};
+use crate::settings;
+use crate::utils::{digest_file_hex, lock_or_recover, write_clipboard_text, HashAlgorithm};
 
 const GITHUB_RELEASES_API: &str = "https://api.github.com/repos/200hub/ai-ask/releases";
 const STORE_FILE: &str = "config.json";
 const STORE_KEY_CONFIG: &str = "app_config";
+/// 记录 `config.json` 结构版本的顶层键
+const STORE_KEY_SCHEMA_VERSION: &str = "config_schema_version";
+/// 当前配置结构版本，新增迁移规则时递增
+///
+/// 版本 1 曾把前端使用的驼峰键改写为下划线键，版本 2 将其恢复。
+const CURRENT_CONFIG_SCHEMA_VERSION: u64 = 2;
 const PENDING_UPDATE_FILE: &str = "pending-update.json";
+/// 更新镜像列表的后端设置键（由 `set_update_mirrors` 写入，优先于 `app_config` 中的配置）
+const SETTING_UPDATE_MIRRORS: &str = "updateMirrors";
+/// 最多保存的镜像数量
+const MAX_UPDATE_MIRRORS: usize = 10;
+/// 检查最新版本时拉取的 Release 数量
+const LATEST_RELEASES_PAGE_SIZE: u32 = 5;
+/// 汇总变更日志时拉取的 Release 数量
+const CHANGELOG_RELEASES_PAGE_SIZE: u32 = 30;
+/// 默认周期检查间隔（小时）
+const DEFAULT_CHECK_INTERVAL_HOURS: u64 = 24;
+/// 周期检查间隔上限（小时），超出的配置按一年处理，避免换算成 Duration 或叠加抖动时溢出
+const MAX_CHECK_INTERVAL_HOURS: u64 = 24 * 365;
+/// 周期检查随机抖动占间隔的比例上限（1/10），避免所有客户端同时请求 GitHub
+const CHECK_JITTER_DIVISOR: u32 = 10;
+/// 更新服务器连通性预检的单个来源超时时间
+const REACHABILITY_TIMEOUT: Duration = Duration::from_secs(5);
+/// 下载测速默认读取的字节数（2MB）
+const DEFAULT_THROUGHPUT_TEST_BYTES: u64 = 2 * 1024 * 1024;
+/// 下载测速允许读取的最大字节数（50MB）
+const MAX_THROUGHPUT_TEST_BYTES: u64 = 50 * 1024 * 1024;
+/// 下载测速的整体时限，超时后按已读取的数据计算速率
+const THROUGHPUT_TEST_TIMEOUT: Duration = Duration::from_secs(60);
 
 /// 更新事件：检测到新版本可用（会推送给前端显示更新 Banner）
 pub const EVENT_UPDATE_AVAILABLE: &str = "update:available";
 /// 更新事件：更新安装包下载完成（用于提示用户安装或下次启动时自动安装）
 pub const EVENT_UPDATE_DOWNLOADED: &str = "update:downloaded";
+/// 更新事件：启动检查被用户取消
+pub const EVENT_UPDATE_CHECK_CANCELLED: &str = "update:check-cancelled";
+/// 更新事件：下载进度采样（固定间隔推送，供前端绘制速度曲线）
+pub const EVENT_UPDATE_DOWNLOAD_SAMPLE: &str = "update:download-sample";
+/// 下载进度采样间隔
+const DOWNLOAD_SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
 
 /// 下载任务状态
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
@@ -102,6 +148,69 @@ pub struct CheckUpdateResponse {
     pub assets: Vec<ReleaseAsset>,
 }
 
+/// `test_download_throughput` 命令返回的测速结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThroughputResult {
+    pub bytes_downloaded: u64,
+    pub elapsed_ms: u64,
+    pub megabytes_per_second: f64,
+    /// 是否因达到时限提前结束
+    pub timed_out: bool,
+}
+
+/// `check_update_server_reachable` 命令返回的连通性预检结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateServerReachability {
+    pub reachable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    /// 响应成功的镜像地址；使用 GitHub 官方接口时为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mirror_used: Option<String>,
+}
+
+/// `get_app_integrity` 命令返回的运行时版本与完整性信息
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppIntegrity {
+    pub name: String,
+    pub version: String,
+    pub target: String,
+    /// 当前可执行文件的 SHA-256（无法读取时为空），可与发布页公布的校验和人工比对
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub executable_sha256: Option<String>,
+}
+
+/// `reset_update_state` 命令返回的清理摘要
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResetUpdateSummary {
+    pub releases_cleared: usize,
+    pub downloads_cleared: usize,
+    /// 仍在下载而被保留的任务数（下载无法中途取消，完成后仍可安装）
+    pub downloads_kept_running: usize,
+    pub pending_install_cleared: bool,
+    pub cache_files_removed: usize,
+    pub cache_bytes_freed: u64,
+}
+
+/// 更新缓存目录中的单个安装包
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedUpdateFile {
+    /// 从文件名解析出的版本号（文件名格式为 `{version}-{asset}`）
+    pub version: Option<String>,
+    pub asset_name: String,
+    pub path: String,
+    pub size: u64,
+    /// 文件修改时间（RFC 3339）
+    pub downloaded_at: Option<String>,
+    /// 是否仍在下载中（下载中的文件不可删除）
+    pub in_use: bool,
+}
+
 /// 触发 `update:available` 事件时携带的负载结构
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -126,6 +235,16 @@ struct UpdateDownloadedPayload {
     file_path: Option<String>,
 }
 
+/// 触发 `update:download-sample` 事件时携带的负载结构
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DownloadSamplePayload {
+    task_id: String,
+    /// 采样时刻（Unix 毫秒时间戳）
+    timestamp_ms: u64,
+    bytes_downloaded: u64,
+}
+
 /// 内部缓存的单个资源数据，用于避免重复解析 GitHub API 响应
 #[derive(Debug, Clone)]
 struct CachedAsset {
@@ -160,6 +279,11 @@ struct UpdateState {
 
 struct UpdateManager {
     state: Mutex<UpdateState>,
+    /// 应用退出时置位，通知后台周期检查任务退出
+    shutdown_requested: AtomicBool,
+    shutdown_notify: tokio::sync::Notify,
+    /// 正在进行的启动检查任务，用于 `cancel_update_check` 中止
+    startup_check: Mutex<Option<tokio::task::AbortHandle>>,
 }
 
 impl UpdateManager {
@@ -167,62 +291,212 @@ impl UpdateManager {
         static INSTANCE: OnceLock<UpdateManager> = OnceLock::new();
         INSTANCE.get_or_init(|| UpdateManager {
             state: Mutex::new(UpdateState::default()),
+            shutdown_requested: AtomicBool::new(false),
+            shutdown_notify: tokio::sync::Notify::new(),
+            startup_check: Mutex::new(None),
         })
     }
 
+    fn set_startup_check(&self, handle: Option<tokio::task::AbortHandle>) {
+        *lock_or_recover(&self.startup_check, "startup update check") = handle;
+    }
+
+    /// 中止仍在运行的启动检查，返回是否确实中止了任务
+    fn abort_startup_check(&self) -> bool {
+        let handle = lock_or_recover(&self.startup_check, "startup update check").take();
+        match handle {
+            Some(handle) if !handle.is_finished() => {
+                handle.abort();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn request_shutdown(&self) {
+        self.shutdown_requested.store(true, Ordering::SeqCst);
+        self.shutdown_notify.notify_waiters();
+    }
+
+    fn is_shutdown_requested(&self) -> bool {
+        self.shutdown_requested.load(Ordering::SeqCst)
+    }
+
+    /// 等待指定时长；若期间收到退出通知则提前返回 `false`
+    async fn sleep_unless_shutdown(&self, duration: Duration) -> bool {
+        let notified = self.shutdown_notify.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+        if self.is_shutdown_requested() {
+            return false;
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(duration) => !self.is_shutdown_requested(),
+            _ = notified => false,
+        }
+    }
+
     fn store_release(&self, release: CachedRelease) {
-        let mut state = self
-            .state
-            .lock()
-            .expect("update manager mutex poisoned during store_release");
+        let mut state = lock_or_recover(&self.state, "update manager state");
         state.releases.insert(release.version.clone(), release);
     }
 
     fn get_release(&self, version: &str) -> Option<CachedRelease> {
-        let state = self
-            .state
-            .lock()
-            .expect("update manager mutex poisoned during get_release");
+        let state = lock_or_recover(&self.state, "update manager state");
         state.releases.get(version).cloned()
     }
 
     fn store_download(&self, task_id: String, task: Arc<Mutex<DownloadTaskInternal>>) {
-        let mut state = self
-            .state
-            .lock()
-            .expect("update manager mutex poisoned during store_download");
+        let mut state = lock_or_recover(&self.state, "update manager state");
         state.downloads.insert(task_id, task);
     }
 
     fn get_download(&self, task_id: &str) -> Option<Arc<Mutex<DownloadTaskInternal>>> {
-        let state = self
-            .state
-            .lock()
-            .expect("update manager mutex poisoned during get_download");
+        let state = lock_or_recover(&self.state, "update manager state");
         state.downloads.get(task_id).cloned()
     }
+
+    /// 仍在下载中的安装包路径
+    fn active_download_paths(&self) -> Vec<PathBuf> {
+        let state = lock_or_recover(&self.state, "update manager state");
+        Self::running_download_paths(&state)
+    }
+
+    fn running_download_paths(state: &UpdateState) -> Vec<PathBuf> {
+        state
+            .downloads
+            .values()
+            .filter_map(|task| {
+                let task = lock_or_recover(task, "download task");
+                Self::is_running(&task)
+                    .then(|| task.download_path.clone())
+                    .flatten()
+            })
+            .collect()
+    }
+
+    fn is_running(task: &DownloadTaskInternal) -> bool {
+        task.task.status == DownloadStatus::Running
+    }
+
+    /// 清空缓存的 Release 与已结束的下载任务，仍在下载的任务保留，以便继续汇报进度并完成安装
+    ///
+    /// 返回 `(release 数, 清除的下载任务数, 保留的下载任务数, 仍在下载的文件路径)`
+    fn clear(&self) -> (usize, usize, usize, Vec<PathBuf>) {
+        let mut state = lock_or_recover(&self.state, "update manager state");
+        let active_paths = Self::running_download_paths(&state);
+        let releases = state.releases.len();
+        let downloads = state.downloads.len();
+        state.releases.clear();
+        state
+            .downloads
+            .retain(|_, task| Self::is_running(&lock_or_recover(task, "download task")));
+        let kept = state.downloads.len();
+        (releases, downloads - kept, kept, active_paths)
+    }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 struct UpdateConfig {
     auto_update_enabled: bool,
     proxy: Option<ProxyTestConfig>,
+    /// 启动时是否检查更新
+    check_on_startup: bool,
+    /// 是否暂停所有自动检查（手动检查不受影响）
+    checks_paused: bool,
+    /// 周期检查间隔；`None` 表示关闭周期检查
+    check_interval: Option<Duration>,
+    /// 自定义 User-Agent（已校验）；`None` 表示使用默认值
+    user_agent: Option<String>,
+    /// Releases API 镜像地址（已校验），按顺序优先于 GitHub 官方接口尝试
+    mirrors: Vec<String>,
+}
+
+impl UpdateConfig {
+    /// 更新与代理请求统一使用的 User-Agent
+    fn user_agent(&self, app: &AppHandle) -> String {
+        self.user_agent
+            .clone()
+            .unwrap_or_else(|| default_user_agent(app))
+    }
+
+    /// 依次尝试的 Releases API 地址：配置的镜像在前，GitHub 官方接口兜底
+    fn release_sources(&self) -> Vec<&str> {
+        let mut sources: Vec<&str> = self.mirrors.iter().map(String::as_str).collect();
+        if !sources.contains(&GITHUB_RELEASES_API) {
+            sources.push(GITHUB_RELEASES_API);
+        }
+        sources
+    }
+}
+
+impl Default for UpdateConfig {
+    fn default() -> Self {
+        Self {
+            auto_update_enabled: false,
+            proxy: None,
+            check_on_startup: true,
+            checks_paused: false,
+            check_interval: Some(Duration::from_secs(DEFAULT_CHECK_INTERVAL_HOURS * 3600)),
+            user_agent: None,
+            mirrors: Vec::new(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
 struct StoredProxyConfig {
-    #[serde(rename = "type")]
+    #[serde(rename = "type", alias = "proxyType")]
     proxy_type: Option<String>,
     host: Option<String>,
     port: Option<String>,
+    #[serde(default)]
+    bypass: Option<String>,
 }
 
+/// 前端 `app_config` 中与更新相关的字段（键名与前端一致使用驼峰，兼容旧版下划线写法）
 #[derive(Debug, Clone, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
 struct StoredConfig {
-    #[serde(default)]
+    #[serde(default, alias = "auto_update_enabled")]
     auto_update_enabled: bool,
     #[serde(default)]
     proxy: Option<StoredProxyConfig>,
+    #[serde(default, alias = "check_on_startup")]
+    check_on_startup: Option<bool>,
+    #[serde(default, alias = "update_checks_paused")]
+    update_checks_paused: bool,
+    /// 周期检查间隔（小时），0 表示关闭
+    #[serde(default, alias = "update_check_interval_hours")]
+    update_check_interval_hours: Option<u64>,
+    /// 自定义 User-Agent（企业镜像或隐私需求），为空时使用默认值
+    #[serde(default, alias = "user_agent")]
+    user_agent: Option<String>,
+    /// Releases API 镜像地址列表（如自建的 GitHub Releases API 反向代理）
+    #[serde(default, alias = "update_mirrors")]
+    update_mirrors: Vec<String>,
+}
+
+impl StoredConfig {
+    /// 转换为代理配置，未指定类型时视为系统代理
+    fn proxy_config(&self) -> Option<ProxyTestConfig> {
+        self.proxy.as_ref().map(|proxy| ProxyTestConfig {
+            proxy_type: proxy.proxy_type.clone().unwrap_or_else(|| "system".into()),
+            host: proxy.host.clone(),
+            port: proxy.port.clone(),
+            bypass: proxy.bypass.clone(),
+            user_agent: None,
+        })
+    }
+
+    /// 规范化后的自定义 User-Agent，未配置或为空时为 `Ok(None)`
+    fn normalized_user_agent(&self) -> Result<Option<String>, String> {
+        self.user_agent
+            .as_deref()
+            .filter(|value| !value.trim().is_empty())
+            .map(normalize_user_agent)
+            .transpose()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -234,20 +508,47 @@ struct PendingInstall {
     scheduled_at: String,
 }
 
-/// Initialize update system: apply pending updates and trigger startup check.
+/// Initialize update system: apply pending updates, trigger startup check
+/// and spawn the periodic background check.
 pub fn init(app: AppHandle) {
     log::info!("update manager init");
+    if let Err(err) = migrate_config(&app) {
+        log::warn!("config migration failed: {}", err);
+    }
     tauri::async_runtime::spawn(async move {
         if let Err(err) = apply_pending_update(&app).await {
             log::warn!("apply pending update failed: {}", err);
         }
 
-        if let Err(err) = perform_startup_check(&app).await {
-            log::warn!("startup update check failed: {}", err);
+        match load_config(&app) {
+            Ok(config) if config.checks_paused || !config.check_on_startup => {
+                log::info!("Startup update check disabled by settings");
+            }
+            _ => {
+                let check_app = app.clone();
+                let check = tauri::async_runtime::spawn(async move {
+                    if let Err(err) = perform_startup_check(&check_app).await {
+                        log::warn!("startup update check failed: {}", err);
+                    }
+                });
+                let manager = UpdateManager::global();
+                manager.set_startup_check(Some(check.inner().abort_handle()));
+                if check.await.is_err() {
+                    log::info!("Startup update check cancelled");
+                }
+                manager.set_startup_check(None);
+            }
         }
+
+        run_periodic_checks(&app).await;
     });
 }
 
+/// 通知后台周期检查任务退出（应用退出时调用）
+pub fn shutdown() {
+    UpdateManager::global().request_shutdown();
+}
+
 /// Check whether an update exists
 #[tauri::command]
 pub async fn check_update(app: AppHandle) -> Result<CheckUpdateResponse, String> {
@@ -282,6 +583,282 @@ pub async fn check_update(app: AppHandle) -> Result<CheckUpdateResponse, String>
     }
 }
 
+/// Abort the startup update check if it is still running
+#[tauri::command]
+pub async fn cancel_update_check(app: AppHandle) -> Result<bool, String> {
+    if !UpdateManager::global().abort_startup_check() {
+        return Ok(false);
+    }
+
+    log::info!("Cancelling startup update check");
+    if let Err(err) = app.emit(EVENT_UPDATE_CHECK_CANCELLED, ()) {
+        log::error!("Failed to emit update:check-cancelled event: {}", err);
+    }
+    Ok(true)
+}
+
+/// Copy the cached release notes of a version to the clipboard as plain text
+#[tauri::command]
+pub async fn copy_release_notes(version: String) -> Result<(), String> {
+    let version = version.trim().trim_start_matches('v');
+    let release = UpdateManager::global()
+        .get_release(version)
+        .ok_or_else(|| format!("Release {} is not cached", version))?;
+    let notes = release
+        .release_notes
+        .as_deref()
+        .map(markdown_to_plain_text)
+        .unwrap_or_default();
+
+    write_clipboard_text(&notes)?;
+    log::info!("Copied release notes for {} to clipboard", version);
+    Ok(())
+}
+
+/// Aggregate release notes of all releases newer than the given version
+#[tauri::command]
+pub async fn get_changelog_since(
+    app: AppHandle,
+    current_version: String,
+) -> Result<String, String> {
+    let current = parse_version(current_version.trim())
+        .ok_or_else(|| format!("Invalid version: {}", current_version))?;
+    let config = load_config(&app)?;
+    let releases: Vec<GithubRelease> =
+        fetch_github_releases(&app, &config, CHANGELOG_RELEASES_PAGE_SIZE)
+            .await
+            .map_err(|err| err.to_string())?;
+
+    Ok(aggregate_changelog(&current, releases))
+}
+
+/// Measure effective download throughput through the configured proxy without saving data
+#[tauri::command]
+pub async fn test_download_throughput(
+    app: AppHandle,
+    url: String,
+    max_bytes: Option<u64>,
+) -> Result<ThroughputResult, String> {
+    let parsed = reqwest::Url::parse(url.trim()).map_err(|err| format!("Invalid URL: {}", err))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(Message::UnsupportedUrlScheme(parsed.scheme()).to_string());
+    }
+    let max_bytes = max_bytes
+        .unwrap_or(DEFAULT_THROUGHPUT_TEST_BYTES)
+        .clamp(1, MAX_THROUGHPUT_TEST_BYTES);
+
+    let config = load_config(&app)?;
+    let client = build_http_client(&app, &config).map_err(|err| err.to_string())?;
+
+    let started = Instant::now();
+    let mut response = client
+        .get(parsed)
+        .send()
+        .await
+        .map_err(|err| format!("Failed to send download request: {}", err))?;
+    if !response.status().is_success() {
+        return Err(Message::DownloadFailedStatus(response.status().as_u16()).to_string());
+    }
+
+    let mut downloaded = 0u64;
+    let mut timed_out = false;
+    while downloaded < max_bytes {
+        let remaining = THROUGHPUT_TEST_TIMEOUT.saturating_sub(started.elapsed());
+        match tokio::time::timeout(remaining, response.chunk()).await {
+            Ok(Ok(Some(chunk))) => downloaded += chunk.len() as u64,
+            Ok(Ok(None)) => break,
+            Ok(Err(err)) => return Err(format!("Failed to read download data: {}", err)),
+            Err(_) => {
+                timed_out = true;
+                break;
+            }
+        }
+    }
+
+    let elapsed = started.elapsed();
+    let result = ThroughputResult {
+        bytes_downloaded: downloaded,
+        elapsed_ms: elapsed.as_millis() as u64,
+        megabytes_per_second: megabytes_per_second(downloaded, elapsed),
+        timed_out,
+    };
+    log::info!(
+        "download throughput test: bytes={} elapsed_ms={} speed={:.2}MB/s timed_out={}",
+        result.bytes_downloaded,
+        result.elapsed_ms,
+        result.megabytes_per_second,
+        result.timed_out
+    );
+    Ok(result)
+}
+
+/// Report the running app version, build target and executable hash for support diagnostics
+#[tauri::command]
+pub async fn get_app_integrity(
+    app: AppHandle,
+    include_hash: Option<bool>,
+) -> Result<AppIntegrity, String> {
+    let package_info = app.package_info();
+    let target = tauri::utils::platform::target_triple().unwrap_or_else(|err| {
+        log::warn!("Failed to resolve target triple: {}", err);
+        format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS)
+    });
+
+    let executable_sha256 = if include_hash.unwrap_or(true) {
+        tauri::async_runtime::spawn_blocking(hash_current_executable)
+            .await
+            .map_err(|err| err.to_string())?
+            .map_err(|err| log::warn!("Failed to hash current executable: {}", err))
+            .ok()
+    } else {
+        None
+    };
+    Ok(AppIntegrity {
+        name: package_info.name.clone(),
+        version: package_info.version.to_string(),
+        target,
+        executable_sha256,
+    })
+}
+
+/// Preflight whether any release source is reachable through the configured proxy
+#[tauri::command]
+pub async fn check_update_server_reachable(
+    app: AppHandle,
+) -> Result<UpdateServerReachability, String> {
+    let config = load_config(&app)?;
+    let client = build_http_client(&app, &config).map_err(|err| err.to_string())?;
+
+    for source in config.release_sources() {
+        let started = Instant::now();
+        let result = client
+            .get(source)
+            .query(&[("per_page", "1")])
+            .header(USER_AGENT, config.user_agent(&app))
+            .timeout(REACHABILITY_TIMEOUT)
+            .send()
+            .await;
+
+        match result {
+            Ok(response) if response.status().is_success() => {
+                let latency_ms = started.elapsed().as_millis() as u64;
+                log::info!(
+                    "Update server reachable: source={} latency_ms={}",
+                    source,
+                    latency_ms
+                );
+                return Ok(UpdateServerReachability {
+                    reachable: true,
+                    latency_ms: Some(latency_ms),
+                    mirror_used: (source != GITHUB_RELEASES_API).then(|| source.to_string()),
+                });
+            }
+            Ok(response) => log::warn!(
+                "Update server {} responded with status {}",
+                source,
+                response.status()
+            ),
+            Err(err) => log::warn!("Update server {} unreachable: {}", source, err),
+        }
+    }
+
+    Ok(UpdateServerReachability {
+        reachable: false,
+        latency_ms: None,
+        mirror_used: None,
+    })
+}
+
+/// Configure release API mirrors tried in order before the official GitHub API
+#[tauri::command]
+pub async fn set_update_mirrors(app: AppHandle, urls: Vec<String>) -> Result<Vec<String>, String> {
+    let mirrors = normalize_mirrors(&urls)?;
+    settings::save_setting(&app, SETTING_UPDATE_MIRRORS, &mirrors)?;
+    log::info!("Update mirrors set: {:?}", mirrors);
+    Ok(mirrors)
+}
+
+/// Environment variable that enables update debugging commands in release builds
+const UPDATE_DEBUG_ENV: &str = "AI_ASK_UPDATE_DEBUG";
+
+fn update_debug_enabled() -> bool {
+    cfg!(debug_assertions) || std::env::var_os(UPDATE_DEBUG_ENV).is_some_and(|value| value != "0")
+}
+
+/// Raw latest release data for bug reports about asset classification
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawLatestRelease {
+    /// Unparsed release object as returned by the releases API
+    release: serde_json::Value,
+    asset_names: Vec<String>,
+}
+
+/// Pick the highest non-draft release by tag version from raw API objects
+fn select_latest_raw_release(releases: Vec<serde_json::Value>) -> Option<serde_json::Value> {
+    releases
+        .into_iter()
+        .filter(|release| {
+            !release
+                .get("draft")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false)
+        })
+        .filter_map(|release| {
+            let version = release
+                .get("tag_name")
+                .and_then(|v| v.as_str())
+                .and_then(parse_version)?;
+            Some((version, release))
+        })
+        .max_by(|a, b| a.0.cmp(&b.0))
+        .map(|(_, release)| release)
+}
+
+/// Return the unparsed JSON of the latest release, fetched through the configured proxy and mirrors
+///
+/// Only available in debug builds or when `AI_ASK_UPDATE_DEBUG` is set.
+#[tauri::command]
+pub async fn get_raw_latest_release(app: AppHandle) -> Result<RawLatestRelease, String> {
+    if !update_debug_enabled() {
+        return Err(format!(
+            "Raw release data is only available in debug builds or with {} set",
+            UPDATE_DEBUG_ENV
+        ));
+    }
+
+    let config = load_config(&app)?;
+    let releases: Vec<serde_json::Value> =
+        fetch_github_releases(&app, &config, LATEST_RELEASES_PAGE_SIZE)
+            .await
+            .map_err(|err| err.to_string())?;
+    let release = select_latest_raw_release(releases)
+        .ok_or_else(|| Message::NoPublishedRelease.to_string())?;
+
+    let asset_names = release
+        .get("assets")
+        .and_then(|v| v.as_array())
+        .map(|assets| {
+            assets
+                .iter()
+                .filter_map(|asset| asset.get("name").and_then(|v| v.as_str()))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(RawLatestRelease {
+        release,
+        asset_names,
+    })
+}
+
+/// Get the configured release API mirrors
+#[tauri::command]
+pub async fn get_update_mirrors(app: AppHandle) -> Result<Vec<String>, String> {
+    Ok(load_config(&app)?.mirrors)
+}
+
 /// Download selected asset
 #[tauri::command]
 pub async fn download_update(
@@ -300,7 +877,7 @@ pub async fn download_update(
         None => fetch_latest_release(&app, &config)
             .await
             .map_err(|err| err.to_string())?
-            .ok_or_else(|| "Target release not found".to_string())?,
+            .ok_or_else(|| Message::TargetReleaseNotFound.to_string())?,
     };
 
     let asset = release
@@ -308,17 +885,13 @@ pub async fn download_update(
         .iter()
         .find(|asset| asset.meta.id == asset_id || asset.id.to_string() == asset_id)
         .cloned()
-        .ok_or_else(|| "Target release asset not found".to_string())?;
+        .ok_or_else(|| Message::TargetAssetNotFound.to_string())?;
 
     let handle = start_download(&app, &release, &asset, &config)
         .await
         .map_err(|err| err.to_string())?;
 
-    let task = handle
-        .lock()
-        .map_err(|_| "Download task state unavailable".to_string())?
-        .task
-        .clone();
+    let task = lock_or_recover(&handle, "download task").task.clone();
 
     Ok(task)
 }
@@ -329,11 +902,9 @@ pub async fn get_download_status(_app: AppHandle, task_id: String) -> Result<Dow
     let manager = UpdateManager::global();
     let task = manager
         .get_download(&task_id)
-        .ok_or_else(|| "Download task does not exist".to_string())?;
+        .ok_or_else(|| Message::DownloadTaskNotFound.to_string())?;
 
-    let state = task
-        .lock()
-        .map_err(|_| "Download task state unavailable".to_string())?;
+    let state = lock_or_recover(&task, "download task");
     Ok(state.task.clone())
 }
 
@@ -343,7 +914,7 @@ pub async fn schedule_install(app: AppHandle, task_id: String) -> Result<(), Str
     let manager = UpdateManager::global();
     let download = manager
         .get_download(&task_id)
-        .ok_or_else(|| "Download task does not exist".to_string())?;
+        .ok_or_else(|| Message::DownloadTaskNotFound.to_string())?;
 
     let (installer_path, version, _) = extract_installation_info(&download)?;
 
@@ -364,13 +935,114 @@ pub async fn schedule_install(app: AppHandle, task_id: String) -> Result<(), Str
     Ok(())
 }
 
-/// Install the downloaded update immediately by launching the installer and exiting the app.
+/// Reveal the downloaded installer in the system file manager
 #[tauri::command]
-pub async fn install_update_now(app: AppHandle, task_id: String) -> Result<(), String> {
+pub async fn reveal_downloaded_installer(task_id: String) -> Result<(), String> {
+    let download = UpdateManager::global()
+        .get_download(&task_id)
+        .ok_or_else(|| Message::DownloadTaskNotFound.to_string())?;
+
+    let (installer_path, _, _) = extract_installation_info(&download)?;
+    if !installer_path.is_file() {
+        return Err(format!(
+            "Installer file missing: {}",
+            installer_path.display()
+        ));
+    }
+
+    tauri_plugin_opener::reveal_item_in_dir(&installer_path).map_err(|err| {
+        log::error!(
+            "Failed to reveal installer {}: {}",
+            installer_path.display(),
+            err
+        );
+        err.to_string()
+    })?;
+    log::info!("Revealed installer: {}", installer_path.display());
+    Ok(())
+}
+
+/// Clear cached releases, finished download tasks, the pending install and optionally the updates cache
+///
+/// Downloads still in progress cannot be cancelled, so their tasks and files are kept.
+#[tauri::command]
+pub async fn reset_update_state(
+    app: AppHandle,
+    purge_cache: Option<bool>,
+) -> Result<ResetUpdateSummary, String> {
+    let (releases_cleared, downloads_cleared, downloads_kept_running, active_paths) =
+        UpdateManager::global().clear();
+
+    let pending_install_cleared = load_pending_install(&app).ok().flatten().is_some();
+    clear_pending_install(&app)?;
+
+    let (cache_files_removed, cache_bytes_freed) = if purge_cache.unwrap_or(false) {
+        purge_updates_dir(&updates_dir(&app)?, &active_paths)
+    } else {
+        (0, 0)
+    };
+
+    let summary = ResetUpdateSummary {
+        releases_cleared,
+        downloads_cleared,
+        downloads_kept_running,
+        pending_install_cleared,
+        cache_files_removed,
+        cache_bytes_freed,
+    };
+    log::info!("Update state reset: {:?}", summary);
+    Ok(summary)
+}
+
+/// List installers in the updates cache directory
+#[tauri::command]
+pub async fn list_cached_updates(app: AppHandle) -> Result<Vec<CachedUpdateFile>, String> {
+    let dir = updates_dir(&app)?;
+    let active_paths = UpdateManager::global().active_download_paths();
+    Ok(list_cached_update_files(&dir, &active_paths))
+}
+
+/// Delete a single installer from the updates cache directory
+#[tauri::command]
+pub async fn delete_cached_update(app: AppHandle, path: String) -> Result<(), String> {
+    let dir = updates_dir(&app)?;
+    let file = resolve_cached_update_path(&dir, Path::new(&path))?;
+
+    let in_use = UpdateManager::global()
+        .active_download_paths()
+        .iter()
+        .any(|active| active.canonicalize().ok().as_ref() == Some(&file));
+    if in_use {
+        return Err(Message::InstallerStillDownloading.to_string());
+    }
+
+    fs::remove_file(&file)
+        .map_err(|err| format!("Failed to delete {}: {}", file.display(), err))?;
+    log::info!("Deleted cached update: {}", file.display());
+    Ok(())
+}
+
+/// Compute the hex digest (`sha256` or `sha512`) of an installer in the updates cache
+#[tauri::command]
+pub async fn hash_cached_update(
+    app: AppHandle,
+    path: String,
+    algo: String,
+) -> Result<String, String> {
+    let algorithm: HashAlgorithm = algo.parse()?;
+    let file = resolve_cached_update_path(&updates_dir(&app)?, Path::new(&path))?;
+
+    tauri::async_runtime::spawn_blocking(move || digest_file_hex(&file, algorithm))
+        .await
+        .map_err(|err| err.to_string())?
+}
+
+/// Look up the installer of a completed download task and ensure it still exists on disk.
+fn completed_installer(task_id: &str) -> Result<(PathBuf, String, String), String> {
     let manager = UpdateManager::global();
     let download = manager
-        .get_download(&task_id)
-        .ok_or_else(|| "Download task does not exist".to_string())?;
+        .get_download(task_id)
+        .ok_or_else(|| Message::DownloadTaskNotFound.to_string())?;
 
     let (installer_path, release_version, asset_name) = extract_installation_info(&download)?;
 
@@ -381,27 +1053,65 @@ pub async fn install_update_now(app: AppHandle, task_id: String) -> Result<(), S
         ));
     }
 
-    log::info!(
-        "Launching installer immediately: version={} task_id={} asset={} path={}",
-        release_version,
-        task_id,
-        asset_name,
-        installer_path.display()
-    );
+    Ok((installer_path, release_version, asset_name))
+}
 
-    let launch_path = installer_path.clone();
+/// Run the platform-specific installer launch on a blocking thread.
+async fn spawn_installer(installer_path: PathBuf) -> Result<(), String> {
     let log_path = installer_path.clone();
-    tauri::async_runtime::spawn_blocking(move || launch_installer(&launch_path))
+    tauri::async_runtime::spawn_blocking(move || launch_installer(&installer_path))
         .await
         .map_err(|err| err.to_string())?
         .map_err(|err| {
             log::error!(
-                "Failed to launch installer immediately: path={} error={}",
+                "Failed to launch installer: path={} error={}",
                 log_path.display(),
                 err
             );
             err
-        })?;
+        })
+}
+
+/// Launch the installer of a completed download without exiting the app.
+///
+/// Returns the spawn error when the launch command fails. Only available in debug builds or
+/// when `AI_ASK_UPDATE_DEBUG` is set, so the platform-specific invocation can be verified
+/// without tearing down the app.
+#[tauri::command]
+pub async fn test_launch_installer(task_id: String) -> Result<(), String> {
+    if !update_debug_enabled() {
+        return Err(format!(
+            "Installer launch testing is only available in debug builds or with {} set",
+            UPDATE_DEBUG_ENV
+        ));
+    }
+
+    let (installer_path, release_version, asset_name) = completed_installer(&task_id)?;
+    log::info!(
+        "Test-launching installer: version={} task_id={} asset={} path={}",
+        release_version,
+        task_id,
+        asset_name,
+        installer_path.display()
+    );
+
+    spawn_installer(installer_path).await
+}
+
+/// Install the downloaded update immediately by launching the installer and exiting the app.
+#[tauri::command]
+pub async fn install_update_now(app: AppHandle, task_id: String) -> Result<(), String> {
+    let (installer_path, release_version, asset_name) = completed_installer(&task_id)?;
+
+    log::info!(
+        "Launching installer immediately: version={} task_id={} asset={} path={}",
+        release_version,
+        task_id,
+        asset_name,
+        installer_path.display()
+    );
+
+    spawn_installer(installer_path).await?;
 
     if let Err(err) = clear_pending_install(&app) {
         log::warn!(
@@ -423,18 +1133,16 @@ pub async fn install_update_now(app: AppHandle, task_id: String) -> Result<(), S
 fn extract_installation_info(
     download: &Arc<Mutex<DownloadTaskInternal>>,
 ) -> Result<(PathBuf, String, String), String> {
-    let state = download
-        .lock()
-        .map_err(|_| "Download task state unavailable".to_string())?;
+    let state = lock_or_recover(download, "download task");
 
     if state.task.status != DownloadStatus::Completed {
-        return Err("Download not completed".into());
+        return Err(Message::DownloadNotCompleted.to_string());
     }
 
     let path = state
         .download_path
         .clone()
-        .ok_or_else(|| "Downloaded installer path missing".to_string())?;
+        .ok_or_else(|| Message::InstallerPathMissing.to_string())?;
 
     let release_version = state.release_version.clone();
     let asset_name = state.task.target_asset.name.clone();
@@ -535,6 +1243,72 @@ async fn perform_startup_check(app: &AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+/// 周期性后台检查：每轮重新读取配置，间隔叠加随机抖动，收到退出通知后结束
+async fn run_periodic_checks(app: &AppHandle) {
+    let manager = UpdateManager::global();
+    loop {
+        let config = load_config(app).unwrap_or_default();
+        let Some(interval) = config.check_interval else {
+            log::info!("Periodic update check disabled by settings");
+            return;
+        };
+
+        let delay = interval.saturating_add(check_jitter(interval, random_seed()));
+        log::debug!("Next periodic update check in {}s", delay.as_secs());
+        if !manager.sleep_unless_shutdown(delay).await {
+            log::info!("Periodic update check stopped");
+            return;
+        }
+
+        // 睡眠期间配置可能已变化，按最新配置决定是否执行
+        let config = load_config(app).unwrap_or_default();
+        if config.checks_paused {
+            log::debug!("Periodic update check skipped: checks paused");
+            continue;
+        }
+        if config.check_interval.is_none() {
+            continue;
+        }
+
+        log::info!("Running periodic update check");
+        if let Err(err) = perform_startup_check(app).await {
+            log::warn!("periodic update check failed: {}", err);
+        }
+    }
+}
+
+/// 根据种子计算抖动时长，范围为 `[0, interval / CHECK_JITTER_DIVISOR)`
+fn check_jitter(interval: Duration, seed: u64) -> Duration {
+    let max_ms = (interval / CHECK_JITTER_DIVISOR).as_millis() as u64;
+    if max_ms == 0 {
+        return Duration::ZERO;
+    }
+    Duration::from_millis(seed % max_ms)
+}
+
+/// 将配置的检查间隔（小时）换算为 Duration：0 表示关闭，未配置时使用默认值，超出上限时按上限处理
+fn check_interval_from_hours(hours: Option<u64>) -> Option<Duration> {
+    match hours {
+        Some(0) => None,
+        Some(hours) => Some(Duration::from_secs(
+            hours.min(MAX_CHECK_INTERVAL_HOURS) * 3600,
+        )),
+        None => Some(Duration::from_secs(DEFAULT_CHECK_INTERVAL_HOURS * 3600)),
+    }
+}
+
+/// 使用标准库随机化哈希种子生成随机数，无需额外依赖
+fn random_seed() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default(),
+    );
+    hasher.finish()
+}
+
 /// Execute download
 async fn start_download(
     app: &AppHandle,
@@ -610,7 +1384,7 @@ async fn start_download(
     });
 
     task.download_path = Some(file_path.clone());
-    let mut guard = shared.lock().expect("download mutex poisoned after spawn");
+    let mut guard = lock_or_recover(&shared, "download task");
     guard.download_path = Some(file_path);
     Ok(Arc::clone(&shared))
 }
@@ -624,7 +1398,7 @@ async fn perform_download(
 ) -> Result<(), anyhow::Error> {
     let client = build_http_client(&app, config)?;
     let mut headers = HeaderMap::new();
-    headers.insert(USER_AGENT, HeaderValue::from_str(&build_user_agent(&app))?);
+    headers.insert(USER_AGENT, HeaderValue::from_str(&config.user_agent(&app))?);
 
     let request = client.get(&asset.meta.download_url).headers(headers);
 
@@ -632,12 +1406,18 @@ async fn perform_download(
     let mut response = match response_result {
         Ok(resp) => resp,
         Err(err) => {
-            let error_msg = format!("Failed to send download request: {}", err);
+            let error_msg = describe_download_error(&asset.meta.download_url, &err);
             update_task_status(&shared, DownloadStatus::Failed, Some(error_msg.clone()));
             return Err(anyhow!(error_msg));
         }
     };
 
+    log::info!(
+        "download resolved: task_host={} final_host={}",
+        url_host(&asset.meta.download_url).unwrap_or_default(),
+        response.url().host_str().unwrap_or_default()
+    );
+
     if !response.status().is_success() {
         update_task_status(
             &shared,
@@ -649,9 +1429,7 @@ async fn perform_download(
 
     let total = response.content_length();
     {
-        let mut guard = shared
-            .lock()
-            .map_err(|_| anyhow!("Download task state unavailable"))?;
+        let mut guard = lock_or_recover(&shared, "download task");
         guard.task.bytes_total = total;
         guard.task.bytes_downloaded = Some(0);
     }
@@ -666,46 +1444,45 @@ async fn perform_download(
         .await
         .with_context(|| format!("Failed to create update file: {}", file_path.display()))?;
 
+    // 采样任务按固定间隔推送已下载字节数（下载停滞时同样推送），函数返回时自动中止
+    let _sampler = DownloadSampler::spawn(app.clone(), Arc::clone(&shared));
+
     let mut downloaded = 0u64;
-    while let Some(chunk) = response
-        .chunk()
-        .await
-        .context("Failed to read download data")?
-    {
+    while let Some(chunk) = match response.chunk().await {
+        Ok(chunk) => chunk,
+        Err(err) => {
+            let error_msg = describe_download_error(&asset.meta.download_url, &err);
+            update_task_status(&shared, DownloadStatus::Failed, Some(error_msg.clone()));
+            return Err(anyhow!(error_msg));
+        }
+    } {
         file.write_all(&chunk)
             .await
             .with_context(|| format!("Failed to write update file: {}", file_path.display()))?;
         downloaded += chunk.len() as u64;
 
-        let mut guard = shared
-            .lock()
-            .map_err(|_| anyhow!("Download task state unavailable"))?;
+        let mut guard = lock_or_recover(&shared, "download task");
         guard.task.bytes_downloaded = Some(downloaded);
     }
 
     file.flush().await.ok();
 
     {
-        let mut guard = shared
-            .lock()
-            .map_err(|_| anyhow!("Download task state unavailable"))?;
+        let mut guard = lock_or_recover(&shared, "download task");
         guard.task.status = DownloadStatus::Completed;
         guard.task.completed_at = Some(now_iso());
         guard.download_path = Some(file_path.to_path_buf());
         guard.task.bytes_downloaded = Some(downloaded);
     }
+    emit_download_sample(&app, &shared);
 
     let payload = UpdateDownloadedPayload {
         version: {
-            let guard = shared
-                .lock()
-                .map_err(|_| anyhow!("Download task state unavailable"))?;
+            let guard = lock_or_recover(&shared, "download task");
             guard.release_version.clone()
         },
         task_id: {
-            let guard = shared
-                .lock()
-                .map_err(|_| anyhow!("Download task state unavailable"))?;
+            let guard = lock_or_recover(&shared, "download task");
             guard.task.id.clone()
         },
         file_path: Some(file_path.to_string_lossy().to_string()),
@@ -726,36 +1503,100 @@ async fn perform_download(
     Ok(())
 }
 
+/// 推送一次下载进度采样
+fn emit_download_sample(app: &AppHandle, shared: &Arc<Mutex<DownloadTaskInternal>>) {
+    let payload = {
+        let guard = lock_or_recover(shared, "download task");
+        DownloadSamplePayload {
+            task_id: guard.task.id.clone(),
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or_default(),
+            bytes_downloaded: guard.task.bytes_downloaded.unwrap_or(0),
+        }
+    };
+    if let Err(err) = app.emit(EVENT_UPDATE_DOWNLOAD_SAMPLE, &payload) {
+        log::debug!("Failed to emit update:download-sample event: {}", err);
+    }
+}
+
+/// 下载进度采样任务，离开作用域时中止
+struct DownloadSampler(tokio::task::AbortHandle);
+
+impl DownloadSampler {
+    fn spawn(app: AppHandle, shared: Arc<Mutex<DownloadTaskInternal>>) -> Self {
+        let handle = tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(DOWNLOAD_SAMPLE_INTERVAL);
+            loop {
+                interval.tick().await;
+                emit_download_sample(&app, &shared);
+            }
+        });
+        Self(handle.inner().abort_handle())
+    }
+}
+
+impl Drop for DownloadSampler {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
 fn update_task_status(
     task: &Arc<Mutex<DownloadTaskInternal>>,
     status: DownloadStatus,
     error: Option<String>,
 ) {
-    if let Ok(mut guard) = task.lock() {
-        guard.task.status = status.clone();
-        guard.task.error = error.clone();
-        if status == DownloadStatus::Failed {
-            guard.task.completed_at = Some(now_iso());
-        }
+    let mut guard = lock_or_recover(task, "download task");
+    guard.task.status = status.clone();
+    guard.task.error = error.clone();
+    if status == DownloadStatus::Failed {
+        guard.task.completed_at = Some(now_iso());
     }
 }
 
-async fn fetch_latest_release(
+/// 按顺序从镜像与 GitHub 官方接口获取 Releases，第一个成功的来源即返回
+async fn fetch_github_releases<T: DeserializeOwned>(
     app: &AppHandle,
     config: &UpdateConfig,
-) -> Result<Option<CachedRelease>, anyhow::Error> {
+    per_page: u32,
+) -> Result<Vec<T>, anyhow::Error> {
     let client = build_http_client(app, config)?;
     let mut headers = HeaderMap::new();
-    headers.insert(USER_AGENT, HeaderValue::from_str(&build_user_agent(app))?);
+    headers.insert(USER_AGENT, HeaderValue::from_str(&config.user_agent(app))?);
     headers.insert(
         reqwest::header::ACCEPT,
         HeaderValue::from_static("application/vnd.github+json"),
     );
 
+    let mut last_error = None;
+    for source in config.release_sources() {
+        match fetch_releases_from(&client, &headers, source, per_page).await {
+            Ok(releases) => {
+                log::info!("Releases fetched from {}", source);
+                return Ok(releases);
+            }
+            Err(err) => {
+                log::warn!("Failed to fetch releases from {}: {:#}", source, err);
+                last_error = Some(err);
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| anyhow!("No release source configured")))
+}
+
+async fn fetch_releases_from<T: DeserializeOwned>(
+    client: &reqwest::Client,
+    headers: &HeaderMap,
+    source: &str,
+    per_page: u32,
+) -> Result<Vec<T>, anyhow::Error> {
     let request = client
-        .get(GITHUB_RELEASES_API)
-        .query(&[("per_page", "5")])
-        .headers(headers);
+        .get(source)
+        .query(&[("per_page", per_page.to_string())])
+        .headers(headers.clone());
 
     let response = request
         .send()
@@ -769,10 +1610,18 @@ async fn fetch_latest_release(
         ));
     }
 
-    let releases: Vec<GithubRelease> = response
+    response
         .json()
         .await
-        .context("failed to parse GitHub Releases response")?;
+        .context("failed to parse GitHub Releases response")
+}
+
+async fn fetch_latest_release(
+    app: &AppHandle,
+    config: &UpdateConfig,
+) -> Result<Option<CachedRelease>, anyhow::Error> {
+    let releases: Vec<GithubRelease> =
+        fetch_github_releases(app, config, LATEST_RELEASES_PAGE_SIZE).await?;
 
     let current_version = current_version(app)?;
     log::info!("update check start: current_version={}", current_version);
@@ -848,6 +1697,31 @@ async fn fetch_latest_release(
     Ok(None)
 }
 
+/// 按版本降序拼接比当前版本新的 Release Notes（遵循稳定版/预发布通道过滤）
+fn aggregate_changelog(current_version: &Version, releases: Vec<GithubRelease>) -> String {
+    let mut newer: Vec<(Version, GithubRelease)> = releases
+        .into_iter()
+        .filter(|release| !release.draft)
+        .filter_map(|release| parse_version(&release.tag_name).map(|v| (v, release)))
+        .filter(|(version, release)| !should_skip_release(current_version, version, release))
+        .collect();
+    newer.sort_by(|a, b| b.0.cmp(&a.0));
+
+    newer
+        .into_iter()
+        .map(|(version, release)| {
+            let notes = release
+                .body
+                .as_deref()
+                .map(str::trim)
+                .filter(|notes| !notes.is_empty())
+                .unwrap_or("No release notes.");
+            format!("## v{}\n\n{}", version, notes)
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
 fn should_skip_release(
     current_version: &Version,
     release_version: &Version,
@@ -949,42 +1823,313 @@ fn build_cached_release(
     })
 }
 
+/// 解析 `config.json` 文本，不是合法 JSON 时返回错误
+fn parse_config_file(data: &str) -> Result<serde_json::Value, String> {
+    serde_json::from_str(data).map_err(|err| err.to_string())
+}
+
+/// 读取 `app_config`：缺失时为默认值，无法反序列化时为 `None`（`load_config` 回退到默认值）
+fn stored_config_from(value: &serde_json::Value) -> Option<StoredConfig> {
+    match value.get(STORE_KEY_CONFIG) {
+        None => Some(StoredConfig::default()),
+        Some(section) => serde_json::from_value(section.clone()).ok(),
+    }
+}
+
 fn load_config(app: &AppHandle) -> Result<UpdateConfig, String> {
-    let resolver = app.path();
-    let config_path = resolver
-        .app_data_dir()
-        .map_err(|err| err.to_string())?
-        .join(STORE_FILE);
+    let config_path = app_paths::data_dir(app)?.join(STORE_FILE);
 
     let Ok(data) = fs::read_to_string(&config_path) else {
         return Ok(UpdateConfig::default());
     };
 
-    let value: serde_json::Value = serde_json::from_str(&data).map_err(|err| err.to_string())?;
-    let stored: StoredConfig = value
+    let value = parse_config_file(&data)?;
+    let stored = stored_config_from(&value).unwrap_or_default();
+
+    let user_agent = stored.normalized_user_agent().unwrap_or_else(|err| {
+        log::warn!("Ignoring configured User-Agent: {}", err);
+        None
+    });
+
+    // `set_active_proxy` 同步的是 `app_config.proxy`，仅在尚未同步时直接读取配置
+    let proxy = active_proxy(app).or_else(|| stored.proxy_config());
+
+    let check_interval = check_interval_from_hours(stored.update_check_interval_hours);
+
+    Ok(UpdateConfig {
+        auto_update_enabled: stored.auto_update_enabled,
+        proxy,
+        check_on_startup: stored.check_on_startup.unwrap_or(true),
+        checks_paused: stored.update_checks_paused,
+        check_interval,
+        user_agent,
+        mirrors: configured_mirrors(app, &stored.update_mirrors),
+    })
+}
+
+/// 读取当前生效的全局代理配置（供子 WebView 继承）
+pub(crate) fn configured_proxy(app: &AppHandle) -> Option<ProxyTestConfig> {
+    load_config(app).ok()?.proxy
+}
+
+/// 读取镜像列表：优先使用 `set_update_mirrors` 保存的后端设置，其次为 `app_config` 中的配置
+fn configured_mirrors(app: &AppHandle, stored: &[String]) -> Vec<String> {
+    let raw = settings::load_setting::<Vec<String>>(app, SETTING_UPDATE_MIRRORS)
+        .unwrap_or_else(|| stored.to_vec());
+    normalize_mirrors(&raw).unwrap_or_else(|err| {
+        log::warn!("Ignoring invalid update mirrors: {}", err);
+        Vec::new()
+    })
+}
+
+/// 校验并规范化镜像地址：仅允许 http/https，去除首尾空白与末尾斜杠，忽略空项与重复项
+fn normalize_mirrors(urls: &[String]) -> Result<Vec<String>, String> {
+    let mut mirrors: Vec<String> = Vec::new();
+    for url in urls
+        .iter()
+        .map(|url| url.trim())
+        .filter(|url| !url.is_empty())
+    {
+        let parsed = reqwest::Url::parse(url)
+            .map_err(|err| format!("Invalid mirror URL {}: {}", url, err))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(format!("Unsupported mirror URL scheme: {}", url));
+        }
+        let normalized = url.trim_end_matches('/').to_string();
+        if !mirrors.contains(&normalized) {
+            mirrors.push(normalized);
+        }
+    }
+    if mirrors.len() > MAX_UPDATE_MIRRORS {
+        return Err(format!(
+            "At most {} mirrors are supported",
+            MAX_UPDATE_MIRRORS
+        ));
+    }
+    Ok(mirrors)
+}
+
+/// 版本 1 迁移写入的下划线键到前端驼峰键的映射（同时存在时以前端的驼峰键为准）
+const SNAKE_CASE_CONFIG_KEYS: [(&str, &str); 6] = [
+    ("auto_update_enabled", "autoUpdateEnabled"),
+    ("check_on_startup", "checkOnStartup"),
+    ("update_checks_paused", "updateChecksPaused"),
+    ("update_check_interval_hours", "updateCheckIntervalHours"),
+    ("user_agent", "userAgent"),
+    ("update_mirrors", "updateMirrors"),
+];
+
+/// 恢复被版本 1 迁移改写的 `app_config` 键，返回变更描述（无变更时为空，可重复执行）
+///
+/// `app_config` 归前端所有，这里只把下划线键还原为前端读取的驼峰键，不改动其他字段。
+fn migrate_config_section(section: &mut serde_json::Map<String, serde_json::Value>) -> Vec<String> {
+    let mut changes = Vec::new();
+
+    for (snake, camel) in SNAKE_CASE_CONFIG_KEYS {
+        let Some(value) = section.remove(snake) else {
+            continue;
+        };
+        if section.contains_key(camel) {
+            changes.push(format!("dropped {snake} (superseded by {camel})"));
+        } else {
+            section.insert(camel.to_string(), value);
+            changes.push(format!("restored {snake} -> {camel}"));
+        }
+    }
+
+    changes
+}
+
+/// 启动时将 `config.json` 迁移到当前结构并记录结构版本
+fn migrate_config(app: &AppHandle) -> Result<(), String> {
+    let store = app.store(STORE_FILE).map_err(|err| err.to_string())?;
+    let version = store
+        .get(STORE_KEY_SCHEMA_VERSION)
+        .and_then(|value| value.as_u64())
+        .unwrap_or(0);
+    if version >= CURRENT_CONFIG_SCHEMA_VERSION {
+        return Ok(());
+    }
+
+    if let Some(mut section) = store
         .get(STORE_KEY_CONFIG)
-        .cloned()
-        .and_then(|v| serde_json::from_value(v).ok())
-        .unwrap_or_default();
+        .and_then(|value| value.as_object().cloned())
+    {
+        let changes = migrate_config_section(&mut section);
+        if !changes.is_empty() {
+            log::info!("Migrated config.json: {}", changes.join(", "));
+            store.set(STORE_KEY_CONFIG, serde_json::Value::Object(section));
+        }
+    }
+
+    store.set(STORE_KEY_SCHEMA_VERSION, CURRENT_CONFIG_SCHEMA_VERSION);
+    store.save().map_err(|err| err.to_string())?;
+    log::info!(
+        "config.json schema version: {} -> {}",
+        version,
+        CURRENT_CONFIG_SCHEMA_VERSION
+    );
+    Ok(())
+}
+
+/// 配置项校验状态
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigFieldStatus {
+    Parsed,
+    Missing,
+    Invalid,
+}
+
+/// 单个配置项的校验结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigFieldReport {
+    key: String,
+    status: ConfigFieldStatus,
+    /// 无效时附带原始值，便于定位问题
+    #[serde(skip_serializing_if = "Option::is_none")]
+    raw_value: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// `config.json` 校验报告
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigValidationReport {
+    config_path: String,
+    file_exists: bool,
+    /// 文件不是合法 JSON 时的解析错误，此时 `load_config` 直接报错
+    parse_error: Option<String>,
+    /// `app_config` 整体无法反序列化时，`load_config` 会整体回退到默认值
+    falls_back_to_defaults: bool,
+    fields: Vec<ConfigFieldReport>,
+}
+
+/// 取得 serde 派生的结构体可接受的键名（已应用 `rename_all`，含别名），避免另行维护一份字段列表
+fn struct_field_names<T: DeserializeOwned>() -> &'static [&'static str] {
+    struct FieldNames(&'static [&'static str]);
+
+    impl<'de> serde::Deserializer<'de> for &mut FieldNames {
+        type Error = serde::de::value::Error;
+
+        fn deserialize_any<V: serde::de::Visitor<'de>>(
+            self,
+            _visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            Err(serde::de::Error::custom(
+                "only struct field names are collected",
+            ))
+        }
+
+        fn deserialize_struct<V: serde::de::Visitor<'de>>(
+            self,
+            _name: &'static str,
+            fields: &'static [&'static str],
+            _visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            self.0 = fields;
+            Err(serde::de::Error::custom(
+                "only struct field names are collected",
+            ))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes
+            byte_buf option unit unit_struct newtype_struct seq tuple tuple_struct map enum
+            identifier ignored_any
+        }
+    }
+
+    let mut names = FieldNames(&[]);
+    let _ = T::deserialize(&mut names);
+    names.0
+}
+
+/// 校验单个字段：与 `load_config` 一样反序列化为 `StoredConfig`，再做相同的取值校验
+fn check_config_field(key: &str, value: &serde_json::Value) -> Result<(), String> {
+    let stored: StoredConfig =
+        serde_json::from_value(serde_json::json!({ key: value })).map_err(|err| err.to_string())?;
+    if let Some(proxy) = stored.proxy_config() {
+        validate_proxy_config(&proxy)?;
+    }
+    stored.normalized_user_agent()?;
+    normalize_mirrors(&stored.update_mirrors)?;
+    Ok(())
+}
+
+/// 按 `StoredConfig` 的字段逐项校验 `app_config`，旧版下划线键归入对应的驼峰字段
+fn validate_stored_config(section: Option<&serde_json::Value>) -> Vec<ConfigFieldReport> {
+    struct_field_names::<StoredConfig>()
+        .iter()
+        .filter(|name| {
+            !SNAKE_CASE_CONFIG_KEYS
+                .iter()
+                .any(|(snake, _)| snake == *name)
+        })
+        .map(|key| {
+            let legacy_key = SNAKE_CASE_CONFIG_KEYS
+                .iter()
+                .find(|(_, camel)| camel == key)
+                .map(|(snake, _)| *snake);
+            let found = std::iter::once(*key).chain(legacy_key).find_map(|name| {
+                section
+                    .and_then(|section| section.get(name))
+                    .map(|v| (name, v))
+            });
+            let (status, raw_value, error) = match found {
+                None => (ConfigFieldStatus::Missing, None, None),
+                Some((name, value)) => match check_config_field(name, value) {
+                    Ok(()) => (ConfigFieldStatus::Parsed, None, None),
+                    Err(err) => (ConfigFieldStatus::Invalid, Some(value.clone()), Some(err)),
+                },
+            };
+            ConfigFieldReport {
+                key: key.to_string(),
+                status,
+                raw_value,
+                error,
+            }
+        })
+        .collect()
+}
 
-    let proxy = stored.proxy.map(|proxy| {
-        let proxy_type = proxy.proxy_type.unwrap_or_else(|| "system".into());
-        ProxyTestConfig {
-            proxy_type,
-            host: proxy.host,
-            port: proxy.port,
-        }
-    });
+/// 校验 `config.json` 中更新相关配置，报告已解析、缺失与无效的字段
+///
+/// `load_config` 遇到解析错误会静默回退到默认值，此命令用于排查“配置不生效”的问题
+#[tauri::command]
+pub async fn validate_config(app: AppHandle) -> Result<ConfigValidationReport, String> {
+    let config_path = app_paths::data_dir(&app)?.join(STORE_FILE);
+    let mut report = ConfigValidationReport {
+        config_path: config_path.to_string_lossy().into_owned(),
+        file_exists: config_path.exists(),
+        parse_error: None,
+        falls_back_to_defaults: false,
+        fields: Vec::new(),
+    };
 
-    Ok(UpdateConfig {
-        auto_update_enabled: stored.auto_update_enabled,
-        proxy,
-    })
+    let value = match fs::read_to_string(&config_path) {
+        Ok(data) => match parse_config_file(&data) {
+            Ok(value) => Some(value),
+            Err(err) => {
+                report.parse_error = Some(err);
+                None
+            }
+        },
+        Err(_) => None,
+    };
+
+    if let Some(value) = &value {
+        report.falls_back_to_defaults = stored_config_from(value).is_none();
+    }
+    let section = value.as_ref().and_then(|value| value.get(STORE_KEY_CONFIG));
+    report.fields = validate_stored_config(section);
+    Ok(report)
 }
 
 fn store_pending_install(app: &AppHandle, pending: &PendingInstall) -> Result<(), String> {
-    let resolver = app.path();
-    let dir = resolver.app_data_dir().map_err(|err| err.to_string())?;
+    let dir = app_paths::data_dir(app)?;
     fs::create_dir_all(&dir).map_err(|err| err.to_string())?;
 
     let path = dir.join(PENDING_UPDATE_FILE);
@@ -993,11 +2138,7 @@ fn store_pending_install(app: &AppHandle, pending: &PendingInstall) -> Result<()
 }
 
 fn load_pending_install(app: &AppHandle) -> Result<Option<PendingInstall>, String> {
-    let resolver = app.path();
-    let path = resolver
-        .app_data_dir()
-        .map_err(|err| err.to_string())?
-        .join(PENDING_UPDATE_FILE);
+    let path = app_paths::data_dir(app)?.join(PENDING_UPDATE_FILE);
 
     if !path.exists() {
         return Ok(None);
@@ -1009,11 +2150,7 @@ fn load_pending_install(app: &AppHandle) -> Result<Option<PendingInstall>, Strin
 }
 
 fn clear_pending_install(app: &AppHandle) -> Result<(), String> {
-    let resolver = app.path();
-    let path = resolver
-        .app_data_dir()
-        .map_err(|err| err.to_string())?
-        .join(PENDING_UPDATE_FILE);
+    let path = app_paths::data_dir(app)?.join(PENDING_UPDATE_FILE);
     if path.exists() {
         fs::remove_file(path).map_err(|err| err.to_string())?;
     }
@@ -1028,12 +2165,18 @@ fn build_http_client(
     // connect_timeout: 连接超时 30 秒
     // timeout: 整体请求超时（包括下载），设置为 30 分钟以支持大文件
     // 使用 native-tls 后端，兼容性更好（rustls 在某些代理环境下有问题）
+    // 显式限定重定向次数，与代理客户端保持一致
     let mut builder = reqwest::Client::builder()
         .connect_timeout(Duration::from_secs(30))
-        .timeout(Duration::from_secs(30 * 60)); // 30 minutes for large file downloads
+        .timeout(Duration::from_secs(30 * 60)) // 30 minutes for large file downloads
+        .redirect(reqwest::redirect::Policy::limited(DOWNLOAD_MAX_REDIRECTS));
 
     if let Some(proxy) = &config.proxy {
-        builder = match build_client_with_proxy(proxy) {
+        let mut proxy = proxy.clone();
+        proxy
+            .user_agent
+            .get_or_insert_with(|| config.user_agent(app));
+        builder = match build_client_with_proxy(&proxy) {
             Ok(client) => return Ok(client),
             Err(err) => {
                 log::warn!(
@@ -1043,17 +2186,74 @@ fn build_http_client(
                 reqwest::Client::builder()
                     .connect_timeout(Duration::from_secs(30))
                     .timeout(Duration::from_secs(30 * 60))
+                    .redirect(reqwest::redirect::Policy::limited(DOWNLOAD_MAX_REDIRECTS))
             }
         };
     }
 
     builder
-        .user_agent(build_user_agent(app))
+        .user_agent(config.user_agent(app))
         .build()
         .map_err(|err| err.into())
 }
 
-fn build_user_agent(app: &AppHandle) -> String {
+/// 计算当前可执行文件的 SHA-256
+fn hash_current_executable() -> Result<String, String> {
+    let path = std::env::current_exe().map_err(|err| err.to_string())?;
+    digest_file_hex(&path, HashAlgorithm::Sha256)
+}
+
+/// 提取 URL 的主机名
+fn url_host(url: &str) -> Option<String> {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+}
+
+/// 若请求最终落到与原始地址不同的主机（重定向），返回该主机名
+fn redirected_host(original_url: &str, final_url: Option<&reqwest::Url>) -> Option<String> {
+    let final_host = final_url?.host_str()?;
+    match url_host(original_url) {
+        Some(original_host) if original_host.eq_ignore_ascii_case(final_host) => None,
+        _ => Some(final_host.to_string()),
+    }
+}
+
+/// 将下载错误转换为便于诊断的描述，区分重定向次数过多与重定向目标不可达
+fn describe_download_error(original_url: &str, err: &reqwest::Error) -> String {
+    if err.is_redirect() {
+        return format!(
+            "Download failed: too many redirects (limit {}): {}",
+            DOWNLOAD_MAX_REDIRECTS, err
+        );
+    }
+    if let Some(host) = redirected_host(original_url, err.url()) {
+        if err.is_connect() || err.is_timeout() || err.is_body() || err.is_request() {
+            log::warn!("download redirect target unreachable: host={}", host);
+            return format!(
+                "Download redirected to {} which could not be reached, check that the proxy allows this host: {}",
+                host, err
+            );
+        }
+    }
+    format!("Failed to send download request: {}", err)
+}
+
+/// 根据字节数与耗时计算 MB/s（1MB = 1024 * 1024 字节）
+fn megabytes_per_second(bytes: u64, elapsed: Duration) -> f64 {
+    let seconds = elapsed.as_secs_f64();
+    if seconds <= 0.0 {
+        return 0.0;
+    }
+    bytes as f64 / (1024.0 * 1024.0) / seconds
+}
+
+/// 读取配置得到当前生效的 User-Agent（供代理测试等模块复用）
+pub(crate) fn configured_user_agent(app: &AppHandle) -> String {
+    load_config(app).unwrap_or_default().user_agent(app)
+}
+
+fn default_user_agent(app: &AppHandle) -> String {
     format!(
         "{}/{} (+https://github.com/200hub/ai-ask)",
         app.package_info().name,
@@ -1066,6 +2266,87 @@ fn current_version(app: &AppHandle) -> Result<Version, anyhow::Error> {
     Version::parse(&version).map_err(|err| err.into())
 }
 
+/// 将 Release Notes 中常见的 Markdown 语法转换为纯文本
+///
+/// 只处理标题、引用、代码块围栏、分隔线、强调、行内代码、链接与图片，足以用于复制分享。
+fn markdown_to_plain_text(markdown: &str) -> String {
+    let mut lines = Vec::new();
+    for raw_line in markdown.lines() {
+        let line = raw_line.trim_end();
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            continue;
+        }
+        let is_rule = trimmed.len() >= 3
+            && ['-', '*', '_']
+                .iter()
+                .any(|marker| trimmed.chars().all(|c| c == *marker || c == ' '));
+        if is_rule {
+            continue;
+        }
+
+        let indent = &line[..line.len() - trimmed.len()];
+        let hashes = trimmed.len() - trimmed.trim_start_matches('#').len();
+        let mut content = if (1..=6).contains(&hashes) && trimmed[hashes..].starts_with(' ') {
+            trimmed[hashes..].trim_start()
+        } else {
+            trimmed
+        };
+        while let Some(rest) = content.strip_prefix('>') {
+            content = rest.trim_start();
+        }
+
+        lines.push(format!("{}{}", indent, strip_inline_markdown(content)));
+    }
+
+    lines.join("\n").trim().to_string()
+}
+
+/// 去除行内 Markdown：`![alt](url)` → `alt`，`[text](url)` → `text (url)`，并移除强调与代码标记
+fn strip_inline_markdown(line: &str) -> String {
+    let mut output = String::with_capacity(line.len());
+    let mut rest = line;
+
+    while !rest.is_empty() {
+        let is_image = rest.starts_with("![");
+        if is_image || rest.starts_with('[') {
+            let label_start = if is_image { 2 } else { 1 };
+            let label_end = rest
+                .find(']')
+                .filter(|end| rest[end + 1..].starts_with('('));
+            if let Some(label_end) = label_end {
+                if let Some(url_len) = rest[label_end + 2..].find(')') {
+                    let label = &rest[label_start..label_end];
+                    let url = &rest[label_end + 2..label_end + 2 + url_len];
+                    output.push_str(&strip_inline_markdown(label));
+                    if !is_image && !url.is_empty() && url != label {
+                        output.push_str(&format!(" ({})", url));
+                    }
+                    rest = &rest[label_end + 3 + url_len..];
+                    continue;
+                }
+            }
+        }
+
+        if let Some(stripped) = ["**", "__", "~~"]
+            .iter()
+            .find_map(|marker| rest.strip_prefix(marker))
+        {
+            rest = stripped;
+            continue;
+        }
+
+        let mut chars = rest.chars();
+        let ch = chars.next().unwrap_or_default();
+        if ch != '`' {
+            output.push(ch);
+        }
+        rest = chars.as_str();
+    }
+
+    output
+}
+
 fn parse_version(tag: &str) -> Option<Version> {
     let trimmed = tag.trim_start_matches('v');
     Version::parse(trimmed).ok()
@@ -1134,6 +2415,35 @@ fn select_asset_for_current_platform(assets: &[CachedAsset]) -> Option<CachedAss
         _ => None,
     };
 
+    // On Linux, only the package format the running copy was installed from is acceptable;
+    // installing e.g. a .deb over an AppImage would leave two diverging copies behind
+    #[cfg(target_os = "linux")]
+    if let Some(format) = linux_package_format() {
+        let asset = assets.iter().find(|asset| {
+            asset.meta.platform == current_platform
+                && (asset.meta.arch.is_none()
+                    || current_arch.is_none()
+                    || asset.meta.arch.as_deref() == current_arch)
+                && format.matches_asset(&asset.meta.name)
+        });
+        match asset {
+            Some(asset) => log::info!(
+                "select update asset={} (platform match, arch match, {:?} format) for platform={} arch={:?}",
+                asset.meta.name,
+                format,
+                current_platform,
+                current_arch
+            ),
+            None => log::warn!(
+                "no {:?} asset matched for platform={} arch={:?}; not falling back to another package format",
+                format,
+                current_platform,
+                current_arch
+            ),
+        }
+        return asset.cloned();
+    }
+
     // Prefer platform + architecture match
     if let Some(asset) = assets.iter().find(|asset| {
         asset.meta.platform == current_platform
@@ -1173,16 +2483,189 @@ fn select_asset_for_current_platform(assets: &[CachedAsset]) -> Option<CachedAss
     None
 }
 
+/// How the running Linux build was installed
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinuxPackageFormat {
+    AppImage,
+    Deb,
+    Rpm,
+    Pacman,
+}
+
+#[cfg(target_os = "linux")]
+impl LinuxPackageFormat {
+    /// Whether a release asset name is a package of this format
+    fn matches_asset(self, name: &str) -> bool {
+        let lower = name.to_ascii_lowercase();
+        match self {
+            Self::AppImage => lower.ends_with(".appimage"),
+            Self::Deb => lower.ends_with(".deb"),
+            Self::Rpm => lower.ends_with(".rpm"),
+            Self::Pacman => lower.ends_with(".pkg.tar.zst") || lower.ends_with(".pkg.tar.xz"),
+        }
+    }
+}
+
+/// Detected package format of the running build, cached for the process lifetime
+#[cfg(target_os = "linux")]
+fn linux_package_format() -> Option<LinuxPackageFormat> {
+    static FORMAT: std::sync::OnceLock<Option<LinuxPackageFormat>> = std::sync::OnceLock::new();
+    *FORMAT.get_or_init(detect_linux_package_format)
+}
+
+/// Detect the package format from the AppImage runtime environment or the package database owning the binary
+#[cfg(target_os = "linux")]
+fn detect_linux_package_format() -> Option<LinuxPackageFormat> {
+    // The AppImage runtime exports the image path to the launched binary
+    if std::env::var_os("APPIMAGE").is_some() {
+        return Some(LinuxPackageFormat::AppImage);
+    }
+
+    let exe = std::env::current_exe().ok()?;
+    let owners = [
+        ("dpkg", "-S", LinuxPackageFormat::Deb),
+        ("rpm", "-qf", LinuxPackageFormat::Rpm),
+        ("pacman", "-Qo", LinuxPackageFormat::Pacman),
+    ];
+    for (tool, flag, format) in owners {
+        if find_in_path(tool).is_none() {
+            continue;
+        }
+        let owned = std::process::Command::new(tool)
+            .arg(flag)
+            .arg(&exe)
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false);
+        if owned {
+            log::info!("Detected Linux package format {:?} via {}", format, tool);
+            return Some(format);
+        }
+    }
+
+    log::info!(
+        "Could not detect Linux package format for {}",
+        exe.display()
+    );
+    None
+}
+
+/// Report how the running Linux build was installed (`None` when unknown or not on Linux)
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub async fn get_linux_package_format() -> Result<Option<LinuxPackageFormat>, String> {
+    tauri::async_runtime::spawn_blocking(linux_package_format)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+/// Report how the running Linux build was installed (always `None` on other platforms)
+#[cfg(not(target_os = "linux"))]
+#[tauri::command]
+pub async fn get_linux_package_format() -> Result<Option<String>, String> {
+    Ok(None)
+}
+
+fn updates_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app_paths::cache_dir(app)?.join("updates"))
+}
+
 fn ensure_updates_dir(app: &AppHandle) -> Result<PathBuf, anyhow::Error> {
-    let dir = app
-        .path()
-        .app_cache_dir()
-        .map_err(|err| anyhow!(err.to_string()))?
-        .join("updates");
+    let dir = updates_dir(app).map_err(|err| anyhow!(err))?;
     fs::create_dir_all(&dir).map_err(|err| anyhow!(err.to_string()))?;
     Ok(dir)
 }
 
+/// 删除更新缓存目录中的安装包文件（跳过仍在下载的文件），返回 `(删除数量, 释放字节数)`
+fn purge_updates_dir(dir: &Path, skip: &[PathBuf]) -> (usize, u64) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return (0, 0);
+    };
+
+    let mut removed = 0;
+    let mut freed = 0;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() || skip.contains(&path) {
+            continue;
+        }
+        let size = entry.metadata().map(|meta| meta.len()).unwrap_or_default();
+        match fs::remove_file(&path) {
+            Ok(()) => {
+                removed += 1;
+                freed += size;
+            }
+            Err(err) => log::warn!("Failed to remove {}: {}", path.display(), err),
+        }
+    }
+    (removed, freed)
+}
+
+/// 列出更新缓存目录中的安装包文件，按修改时间从新到旧排列
+fn list_cached_update_files(dir: &Path, active_paths: &[PathBuf]) -> Vec<CachedUpdateFile> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<(Option<std::time::SystemTime>, CachedUpdateFile)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let metadata = entry.metadata().ok().filter(|meta| meta.is_file())?;
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let (version, asset_name) = parse_cached_update_name(&file_name);
+            let modified = metadata.modified().ok();
+            let downloaded_at =
+                modified.and_then(|time| time::OffsetDateTime::from(time).format(&Rfc3339).ok());
+            Some((
+                modified,
+                CachedUpdateFile {
+                    version,
+                    asset_name,
+                    path: path.to_string_lossy().to_string(),
+                    size: metadata.len(),
+                    downloaded_at,
+                    in_use: active_paths.contains(&path),
+                },
+            ))
+        })
+        .collect();
+
+    files.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    files.into_iter().map(|(_, file)| file).collect()
+}
+
+/// 解析缓存文件名 `{version}-{asset}`；资源名经 `sanitize_filename` 处理后不含 `-`，因此按最后一个 `-` 拆分
+fn parse_cached_update_name(file_name: &str) -> (Option<String>, String) {
+    match file_name.rsplit_once('-') {
+        Some((version, asset)) if parse_version(version).is_some() && !asset.is_empty() => {
+            (Some(version.to_string()), asset.to_string())
+        }
+        _ => (None, file_name.to_string()),
+    }
+}
+
+/// 校验路径指向更新缓存目录中的文件，返回规范化后的路径
+fn resolve_cached_update_path(dir: &Path, path: &Path) -> Result<PathBuf, String> {
+    let dir = dir
+        .canonicalize()
+        .map_err(|err| format!("Updates cache is unavailable: {}", err))?;
+    let file = path
+        .canonicalize()
+        .map_err(|err| format!("Cached update not found: {}", err))?;
+    if file.parent() != Some(dir.as_path()) || !file.is_file() {
+        return Err(format!(
+            "{} is not a file in the updates cache",
+            path.display()
+        ));
+    }
+    Ok(file)
+}
+
 fn sanitize_filename(name: &str) -> String {
     name.chars()
         .map(|c| {
@@ -1233,20 +2716,121 @@ fn launch_installer(path: &Path) -> Result<(), String> {
 
     #[cfg(target_os = "linux")]
     {
-        std::process::Command::new("chmod")
-            .args(["+x", &path.to_string_lossy()])
-            .spawn()
-            .map_err(|err| err.to_string())
-            .ok();
-
-        std::process::Command::new(path)
-            .spawn()
-            .map_err(|err| err.to_string())?;
+        match linux_install_command(path, |program| find_in_path(program).is_some())? {
+            Some(command) => {
+                log::info!(
+                    "Launching Linux installer: {} {}",
+                    command.program,
+                    command.args.join(" ")
+                );
+                std::process::Command::new(&command.program)
+                    .args(&command.args)
+                    .spawn()
+                    .map_err(|err| format!("Failed to run {}: {}", command.program, err))?;
+            }
+            None => {
+                std::process::Command::new("chmod")
+                    .args(["+x", &path.to_string_lossy()])
+                    .spawn()
+                    .map_err(|err| err.to_string())
+                    .ok();
+
+                std::process::Command::new(path)
+                    .spawn()
+                    .map_err(|err| err.to_string())?;
+            }
+        }
     }
 
     Ok(())
 }
 
+/// Package-manager invocation used to install a Linux package
+#[cfg(any(target_os = "linux", test))]
+#[derive(Debug, PartialEq, Eq)]
+struct LinuxInstallCommand {
+    program: String,
+    args: Vec<String>,
+}
+
+/// Decide how to install a Linux package based on its extension.
+///
+/// Returns `Ok(None)` for AppImage and other self-contained executables, which are run directly.
+/// `.deb` prefers the gdebi GUI and falls back to apt/dpkg via pkexec; `.rpm` uses
+/// dnf/zypper/rpm via pkexec; `.pkg.tar.zst` uses pacman via pkexec.
+#[cfg(any(target_os = "linux", test))]
+fn linux_install_command(
+    path: &Path,
+    is_available: impl Fn(&str) -> bool,
+) -> Result<Option<LinuxInstallCommand>, String> {
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let file = path.to_string_lossy().to_string();
+
+    let elevated = |tool: &str, args: &[&str]| -> Option<LinuxInstallCommand> {
+        if !is_available("pkexec") || !is_available(tool) {
+            return None;
+        }
+        let mut full_args = vec![tool.to_string()];
+        full_args.extend(args.iter().map(|arg| arg.to_string()));
+        full_args.push(file.clone());
+        Some(LinuxInstallCommand {
+            program: "pkexec".to_string(),
+            args: full_args,
+        })
+    };
+
+    let command = if name.ends_with(".deb") {
+        if is_available("gdebi-gtk") {
+            Some(LinuxInstallCommand {
+                program: "gdebi-gtk".to_string(),
+                args: vec![file.clone()],
+            })
+        } else {
+            elevated("apt-get", &["install", "-y"]).or_else(|| elevated("dpkg", &["-i"]))
+        }
+    } else if name.ends_with(".rpm") {
+        elevated("dnf", &["install", "-y"])
+            .or_else(|| elevated("zypper", &["--non-interactive", "install"]))
+            .or_else(|| elevated("rpm", &["-U"]))
+    } else if name.ends_with(".pkg.tar.zst") || name.ends_with(".pkg.tar.xz") {
+        elevated("pacman", &["-U", "--noconfirm"])
+    } else if name.ends_with(".tar.gz") || name.ends_with(".tar.xz") {
+        return Err(format!(
+            "{} is an archive and must be extracted manually",
+            path.display()
+        ));
+    } else {
+        return Ok(None);
+    };
+
+    command.map(Some).ok_or_else(|| {
+        format!(
+            "No suitable package installer found for {}; install it with your package manager",
+            path.display()
+        )
+    })
+}
+
+/// Locate an executable in `PATH`
+#[cfg(target_os = "linux")]
+fn find_in_path(program: &str) -> Option<PathBuf> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let paths = std::env::var_os("PATH")?;
+    std::env::split_paths(&paths)
+        .map(|dir| dir.join(program))
+        .find(|candidate| {
+            candidate
+                .metadata()
+                .map(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+                .unwrap_or(false)
+        })
+}
+
 #[derive(Debug, Deserialize, Clone)]
 struct GithubRelease {
     tag_name: String,
@@ -1284,6 +2868,96 @@ mod tests {
         }
     }
 
+    #[test]
+    fn select_latest_raw_release_skips_drafts_and_invalid_tags() {
+        let releases = vec![
+            serde_json::json!({ "tag_name": "v1.2.0", "draft": false }),
+            serde_json::json!({ "tag_name": "v2.0.0", "draft": true }),
+            serde_json::json!({ "tag_name": "nightly", "draft": false }),
+            serde_json::json!({ "tag_name": "v1.10.0", "draft": false }),
+        ];
+        let latest = select_latest_raw_release(releases).unwrap();
+        assert_eq!(latest["tag_name"], "v1.10.0");
+        assert!(select_latest_raw_release(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn linux_install_command_picks_package_tool() {
+        let only = |tools: &'static [&'static str]| move |tool: &str| tools.contains(&tool);
+
+        let deb = Path::new("/tmp/ai-ask_amd64.deb");
+        let gui = linux_install_command(deb, only(&["gdebi-gtk", "pkexec", "apt-get"]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(gui.program, "gdebi-gtk");
+        let apt = linux_install_command(deb, only(&["pkexec", "apt-get", "dpkg"]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(apt.program, "pkexec");
+        assert_eq!(
+            apt.args,
+            ["apt-get", "install", "-y", "/tmp/ai-ask_amd64.deb"]
+        );
+        assert!(linux_install_command(deb, only(&["apt-get"])).is_err());
+
+        let rpm = Path::new("/tmp/ai-ask.x86_64.rpm");
+        let zypper = linux_install_command(rpm, only(&["pkexec", "zypper", "rpm"]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(zypper.args[0], "zypper");
+
+        let appimage = Path::new("/tmp/ai-ask_amd64.AppImage");
+        assert_eq!(linux_install_command(appimage, only(&[])), Ok(None));
+        assert!(linux_install_command(Path::new("/tmp/ai-ask.tar.gz"), only(&[])).is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn linux_package_format_matches_asset_extension() {
+        assert!(LinuxPackageFormat::AppImage.matches_asset("AI.Ask_1.0.0_amd64.AppImage"));
+        assert!(!LinuxPackageFormat::AppImage.matches_asset("AI.Ask_1.0.0_amd64.deb"));
+        assert!(LinuxPackageFormat::Deb.matches_asset("AI.Ask_1.0.0_amd64.deb"));
+        assert!(LinuxPackageFormat::Rpm.matches_asset("AI.Ask-1.0.0-1.x86_64.rpm"));
+        assert!(LinuxPackageFormat::Pacman.matches_asset("ai-ask-1.0.0-1-x86_64.pkg.tar.zst"));
+    }
+
+    #[test]
+    fn parses_cached_update_file_names() {
+        assert_eq!(
+            parse_cached_update_name("0.0.1-alpha.2-AI.Ask_0.0.1_alpha.2_x64_en_US.msi"),
+            (
+                Some("0.0.1-alpha.2".to_string()),
+                "AI.Ask_0.0.1_alpha.2_x64_en_US.msi".to_string()
+            )
+        );
+        assert_eq!(
+            parse_cached_update_name("stray.bin"),
+            (None, "stray.bin".to_string())
+        );
+    }
+
+    #[test]
+    fn cached_update_paths_must_be_inside_updates_dir() {
+        let root = tempfile::tempdir().unwrap();
+        let updates = root.path().join("updates");
+        fs::create_dir_all(&updates).unwrap();
+        let cached = updates.join("1.0.0-setup.exe");
+        fs::write(&cached, b"installer").unwrap();
+        let outside = root.path().join("secret.txt");
+        fs::write(&outside, b"secret").unwrap();
+
+        assert!(resolve_cached_update_path(&updates, &cached).is_ok());
+        assert!(resolve_cached_update_path(&updates, &outside).is_err());
+        assert!(resolve_cached_update_path(&updates, &updates.join("../secret.txt")).is_err());
+        assert!(resolve_cached_update_path(&updates, &updates).is_err());
+
+        let listed = list_cached_update_files(&updates, std::slice::from_ref(&cached));
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].version.as_deref(), Some("1.0.0"));
+        assert_eq!(listed[0].size, 9);
+        assert!(listed[0].in_use);
+    }
+
     #[test]
     fn classify_asset_matches_windows_arm() {
         let result = classify_asset("AIAsk-setup-win-arm64.exe");
@@ -1481,4 +3155,255 @@ mod tests {
 
         assert!(!should_skip_release(&current, &target, &release));
     }
+
+    #[test]
+    fn check_jitter_stays_within_tenth_of_interval() {
+        let interval = Duration::from_secs(24 * 3600);
+        for seed in [0, 1, 42, u64::MAX / 3, u64::MAX] {
+            assert!(check_jitter(interval, seed) < interval / CHECK_JITTER_DIVISOR);
+        }
+    }
+
+    #[test]
+    fn check_interval_from_hours_clamps_oversized_values() {
+        assert_eq!(check_interval_from_hours(Some(0)), None);
+        assert_eq!(
+            check_interval_from_hours(None),
+            Some(Duration::from_secs(DEFAULT_CHECK_INTERVAL_HOURS * 3600))
+        );
+        assert_eq!(
+            check_interval_from_hours(Some(6)),
+            Some(Duration::from_secs(6 * 3600))
+        );
+        let max = Duration::from_secs(MAX_CHECK_INTERVAL_HOURS * 3600);
+        assert_eq!(check_interval_from_hours(Some(u64::MAX)), Some(max));
+        let jittered = max.saturating_add(check_jitter(max, u64::MAX));
+        assert!(jittered >= max);
+    }
+
+    #[test]
+    fn check_jitter_is_zero_for_tiny_interval() {
+        assert_eq!(
+            check_jitter(Duration::from_millis(5), 12345),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn markdown_to_plain_text_strips_common_syntax() {
+        let markdown = "## What's Changed\n\n- **Fix** crash in `proxy` module\n- See [PR #12](https://example.com/pr/12)\n\n---\n\n```\ncode\n```\n> ![logo](logo.png) Thanks";
+        assert_eq!(
+            markdown_to_plain_text(markdown),
+            "What's Changed\n\n- Fix crash in proxy module\n- See PR #12 (https://example.com/pr/12)\n\n\ncode\nlogo Thanks"
+        );
+    }
+
+    #[test]
+    fn markdown_to_plain_text_keeps_plain_brackets_and_hashes() {
+        assert_eq!(
+            markdown_to_plain_text("#12 fixed [x] item"),
+            "#12 fixed [x] item"
+        );
+    }
+
+    #[test]
+    fn aggregate_changelog_lists_newer_releases_descending() {
+        let mut releases = vec![
+            mock_release("v0.0.1-beta.7", true),
+            mock_release("v0.0.2", false),
+            mock_release("v0.0.3", false),
+            mock_release("v0.0.1", false),
+        ];
+        releases[1].body = Some("Second".into());
+        releases[2].body = Some("Third".into());
+
+        let current = parse_version("0.0.1").unwrap();
+        assert_eq!(
+            aggregate_changelog(&current, releases),
+            "## v0.0.3\n\nThird\n\n## v0.0.2\n\nSecond"
+        );
+    }
+
+    #[test]
+    fn computes_megabytes_per_second() {
+        assert_eq!(
+            megabytes_per_second(2 * 1024 * 1024, Duration::from_secs(2)),
+            1.0
+        );
+        assert_eq!(megabytes_per_second(1024, Duration::ZERO), 0.0);
+    }
+
+    #[test]
+    fn detects_redirected_download_host() {
+        let original = "https://github.com/200hub/ai-ask/releases/download/v1.0.0/app.dmg";
+        let cdn = reqwest::Url::parse("https://objects.githubusercontent.com/asset").unwrap();
+        let same = reqwest::Url::parse("https://GitHub.com/other").unwrap();
+
+        assert_eq!(
+            redirected_host(original, Some(&cdn)),
+            Some("objects.githubusercontent.com".to_string())
+        );
+        assert_eq!(redirected_host(original, Some(&same)), None);
+        assert_eq!(redirected_host(original, None), None);
+    }
+
+    #[test]
+    fn purge_updates_dir_skips_active_downloads() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let finished = dir.path().join("finished.dmg");
+        let active = dir.path().join("active.dmg");
+        fs::write(&finished, b"1234").unwrap();
+        fs::write(&active, b"12").unwrap();
+
+        let (removed, freed) = purge_updates_dir(dir.path(), std::slice::from_ref(&active));
+        assert_eq!((removed, freed), (1, 4));
+        assert!(!finished.exists());
+        assert!(active.exists());
+
+        assert_eq!(purge_updates_dir(&dir.path().join("missing"), &[]), (0, 0));
+    }
+
+    #[test]
+    fn migrate_config_section_restores_camel_case_keys_idempotently() {
+        let mut section = serde_json::json!({
+            "auto_update_enabled": true,
+            "user_agent": "Migrated/1.0",
+            "userAgent": "Frontend/2.0",
+            "update_mirrors": ["https://mirror.example.com"],
+            "proxy": { "type": "custom", "host": "127.0.0.1" },
+            "theme": "dark",
+        })
+        .as_object()
+        .cloned()
+        .unwrap();
+
+        let changes = migrate_config_section(&mut section);
+        assert_eq!(changes.len(), 3);
+        assert_eq!(section["autoUpdateEnabled"], serde_json::json!(true));
+        assert_eq!(section["userAgent"], serde_json::json!("Frontend/2.0"));
+        assert_eq!(
+            section["updateMirrors"],
+            serde_json::json!(["https://mirror.example.com"])
+        );
+        assert_eq!(section["proxy"]["type"], serde_json::json!("custom"));
+        assert_eq!(section["theme"], serde_json::json!("dark"));
+        assert!(!section.contains_key("user_agent"));
+
+        assert!(migrate_config_section(&mut section).is_empty());
+    }
+
+    #[test]
+    fn stored_config_reads_frontend_and_legacy_keys() {
+        let frontend: StoredConfig = serde_json::from_value(serde_json::json!({
+            "autoUpdateEnabled": true,
+            "updateCheckIntervalHours": 6,
+            "proxy": { "type": "custom", "host": "127.0.0.1", "port": "7890" },
+        }))
+        .unwrap();
+        assert!(frontend.auto_update_enabled);
+        assert_eq!(frontend.update_check_interval_hours, Some(6));
+
+        let legacy: StoredConfig = serde_json::from_value(serde_json::json!({
+            "auto_update_enabled": true,
+            "user_agent": "Legacy/1.0",
+            "proxy": { "proxyType": "system" },
+        }))
+        .unwrap();
+        assert!(legacy.auto_update_enabled);
+        assert_eq!(legacy.user_agent.as_deref(), Some("Legacy/1.0"));
+        assert_eq!(
+            legacy.proxy.and_then(|proxy| proxy.proxy_type).as_deref(),
+            Some("system")
+        );
+    }
+
+    #[test]
+    fn struct_field_names_lists_stored_config_keys_and_aliases() {
+        let names = struct_field_names::<StoredConfig>();
+        assert_eq!(names.len(), 13);
+        assert!(names.contains(&"proxy"));
+        for (snake, camel) in SNAKE_CASE_CONFIG_KEYS {
+            assert!(names.contains(&snake) && names.contains(&camel));
+        }
+    }
+
+    #[test]
+    fn check_config_field_validates_proxy_like_load_config() {
+        let proxy = |value: serde_json::Value| check_config_field("proxy", &value);
+        assert!(proxy(serde_json::json!({ "type": "none" })).is_ok());
+        assert!(proxy(serde_json::json!({ "type": "pac" }))
+            .is_err_and(|err| err.contains("Unsupported proxy type")));
+        assert!(proxy(serde_json::json!({ "type": "custom", "host": "127.0.0.1" })).is_err());
+    }
+
+    #[test]
+    fn stored_config_from_distinguishes_missing_and_malformed_sections() {
+        assert!(stored_config_from(&serde_json::json!({})).is_some());
+        assert!(stored_config_from(
+            &serde_json::json!({ STORE_KEY_CONFIG: { "autoUpdateEnabled": "yes" } })
+        )
+        .is_none());
+        assert!(parse_config_file("{not json").is_err());
+    }
+
+    #[test]
+    fn validate_stored_config_reports_each_field() {
+        let section = serde_json::json!({
+            "auto_update_enabled": "yes",
+            "proxy": { "type": "custom", "host": "127.0.0.1", "port": "7890" },
+            "updateMirrors": ["ftp://mirror.example.com"],
+        });
+        let reports = validate_stored_config(Some(&section));
+        let status = |key: &str| {
+            reports
+                .iter()
+                .find(|report| report.key == key)
+                .map(|report| report.status)
+        };
+
+        assert_eq!(
+            status("autoUpdateEnabled"),
+            Some(ConfigFieldStatus::Invalid)
+        );
+        assert_eq!(status("proxy"), Some(ConfigFieldStatus::Parsed));
+        assert_eq!(status("updateMirrors"), Some(ConfigFieldStatus::Invalid));
+        assert_eq!(status("checkOnStartup"), Some(ConfigFieldStatus::Missing));
+        assert_eq!(reports.len(), 7);
+
+        let invalid = reports
+            .iter()
+            .find(|report| report.key == "autoUpdateEnabled")
+            .unwrap();
+        assert_eq!(invalid.raw_value, Some(serde_json::json!("yes")));
+    }
+
+    #[test]
+    fn normalize_mirrors_trims_and_deduplicates() {
+        let mirrors = normalize_mirrors(&[
+            " https://mirror.example.com/releases/ ".into(),
+            "".into(),
+            "https://mirror.example.com/releases".into(),
+        ])
+        .expect("valid mirrors");
+        assert_eq!(mirrors, vec!["https://mirror.example.com/releases"]);
+
+        assert!(normalize_mirrors(&["ftp://mirror.example.com".into()]).is_err());
+        assert!(normalize_mirrors(&["not a url".into()]).is_err());
+    }
+
+    #[test]
+    fn release_sources_fall_back_to_github() {
+        let config = UpdateConfig {
+            mirrors: vec!["https://mirror.example.com/releases".into()],
+            ..UpdateConfig::default()
+        };
+        assert_eq!(
+            config.release_sources(),
+            vec!["https://mirror.example.com/releases", GITHUB_RELEASES_API]
+        );
+        assert_eq!(
+            UpdateConfig::default().release_sources(),
+            vec![GITHUB_RELEASES_API]
+        );
+    }
 }
@@ -1,9 +1,45 @@
 //! 通用工具函数模块
 //!
-//! 提供项目中常用的编码/解码、数据转换等工具函数
+//! 提供项目中常用的编码/解码、数据转换、哈希等工具函数
+
+use sha2::{Digest, Sha256, Sha512};
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+use std::sync::TryLockError;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+use std::sync::{Mutex, MutexGuard};
+
+/// Base64 输入长度上限的默认值（64 MiB，约合 48 MiB 解码数据）
+pub const DEFAULT_MAX_BASE64_INPUT_LEN: usize = 64 * 1024 * 1024;
+
+static MAX_BASE64_INPUT_LEN: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_BASE64_INPUT_LEN);
+
+/// 设置 Base64 解码允许的最大输入长度（字节）
+///
+/// 注入通道中的数据可能受网页控制，超长输入会在分配缓冲区之前被拒绝
+pub fn set_max_base64_input_len(max_len: usize) {
+    MAX_BASE64_INPUT_LEN.store(max_len, Ordering::Relaxed);
+}
+
+/// 获取当前 Base64 解码允许的最大输入长度（字节）
+pub fn max_base64_input_len() -> usize {
+    MAX_BASE64_INPUT_LEN.load(Ordering::Relaxed)
+}
+
+fn check_base64_input_len(len: usize, max_len: usize) -> Result<(), String> {
+    if len > max_len {
+        return Err(format!(
+            "base64 input too large: {} bytes (limit {} bytes)",
+            len, max_len
+        ));
+    }
+    Ok(())
+}
 
 /// Base64 解码器
 ///
+/// 输入长度超过 [`max_base64_input_len`] 时直接返回错误
+///
 /// 支持标准 Base64 字符集，使用查找表实现高效解码
 ///
 /// # Arguments
@@ -21,6 +57,12 @@
 /// assert_eq!(decoded, b"Hello World");
 /// ```
 pub fn decode_base64(input: &str) -> Result<Vec<u8>, String> {
+    decode_base64_with_limit(input, max_base64_input_len())
+}
+
+fn decode_base64_with_limit(input: &str, max_len: usize) -> Result<Vec<u8>, String> {
+    check_base64_input_len(input.len(), max_len)?;
+
     const CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
 
     // Build decode lookup table
@@ -100,6 +142,13 @@ pub fn decode_base64(input: &str) -> Result<Vec<u8>, String> {
 /// assert_eq!(decoded, b"Hello World");
 /// ```
 pub fn decode_base64url(base64url: &str) -> Result<Vec<u8>, String> {
+    decode_base64url_with_limit(base64url, max_base64_input_len())
+}
+
+fn decode_base64url_with_limit(base64url: &str, max_len: usize) -> Result<Vec<u8>, String> {
+    // Reject before the replace below allocates a copy
+    check_base64_input_len(base64url.len(), max_len)?;
+
     // Convert base64url to standard base64
     let mut base64 = base64url.replace('-', "+").replace('_', "/");
 
@@ -112,7 +161,7 @@ pub fn decode_base64url(base64url: &str) -> Result<Vec<u8>, String> {
         base64.push('=');
     }
 
-    decode_base64(&base64)
+    decode_base64_with_limit(&base64, max_len.saturating_add(padding_len))
 }
 
 /// Base64url 字符串解码为 JSON
@@ -142,6 +191,136 @@ pub fn decode_base64url_to_json(base64url: &str) -> Result<serde_json::Value, St
     serde_json::from_str(&json_str).map_err(|e| format!("JSON parse failed: {}", e))
 }
 
+// ===== 哈希工具 =====
+
+/// 计算字节数据的 SHA-256 摘要（小写十六进制）
+///
+/// 安装包校验与前端内容去重共用此实现
+///
+/// # Examples
+/// ```
+/// use ai_ask_lib::sha256_digest_hex;
+///
+/// assert_eq!(
+///     sha256_digest_hex(b"abc"),
+///     "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+/// );
+/// ```
+pub fn sha256_digest_hex(data: &[u8]) -> String {
+    to_hex(&Sha256::digest(data))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(output, "{byte:02x}");
+    }
+    output
+}
+
+/// 文件摘要算法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HashAlgorithm {
+    Sha256,
+    Sha512,
+}
+
+impl std::str::FromStr for HashAlgorithm {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().replace('-', "").as_str() {
+            "sha256" => Ok(Self::Sha256),
+            "sha512" => Ok(Self::Sha512),
+            other => Err(format!("unsupported hash algorithm: {other}")),
+        }
+    }
+}
+
+/// 以流式读取的方式计算任意数据源的摘要（小写十六进制），不会把整个文件读入内存
+pub(crate) fn digest_reader_hex<R: std::io::Read>(
+    mut reader: R,
+    algorithm: HashAlgorithm,
+) -> std::io::Result<String> {
+    fn stream<D: Digest, R: std::io::Read>(reader: &mut R) -> std::io::Result<String> {
+        let mut hasher = D::new();
+        let mut buffer = vec![0u8; 64 * 1024];
+        loop {
+            let read = reader.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+        }
+        Ok(to_hex(&hasher.finalize()))
+    }
+
+    match algorithm {
+        HashAlgorithm::Sha256 => stream::<Sha256, R>(&mut reader),
+        HashAlgorithm::Sha512 => stream::<Sha512, R>(&mut reader),
+    }
+}
+
+/// 流式计算文件摘要（小写十六进制）
+pub(crate) fn digest_file_hex(
+    path: &std::path::Path,
+    algorithm: HashAlgorithm,
+) -> Result<String, String> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    digest_reader_hex(std::io::BufReader::new(file), algorithm)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+/// 计算 Base64 输入数据的 SHA-256 摘要（小写十六进制）
+#[tauri::command]
+pub(crate) async fn sha256_hex(data_base64: String) -> Result<String, String> {
+    let data = decode_base64(data_base64.trim())?;
+    Ok(sha256_digest_hex(&data))
+}
+
+/// 将纯文本写入系统剪贴板（仅桌面平台）
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+pub(crate) fn write_clipboard_text(text: &str) -> Result<(), String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text.to_owned()))
+        .map_err(|e| format!("Failed to write clipboard: {}", e))
+}
+
+// ===== 锁工具 =====
+
+/// 获取互斥锁；若锁已中毒（持锁线程曾 panic），记录警告后继续使用内部数据并清除中毒标记，
+/// 避免一次 panic 使整个子系统永久失效，警告也只记录一次
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+pub(crate) fn lock_or_recover<'a, T>(mutex: &'a Mutex<T>, context: &str) -> MutexGuard<'a, T> {
+    mutex.lock().unwrap_or_else(|poisoned| {
+        log::warn!("Recovering from poisoned mutex: {}", context);
+        let guard = poisoned.into_inner();
+        mutex.clear_poison();
+        guard
+    })
+}
+
+/// `lock_or_recover` 的非阻塞版本：锁被占用时返回 `None`（划词监听的高频事件路径使用）
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+pub(crate) fn try_lock_or_recover<'a, T>(
+    mutex: &'a Mutex<T>,
+    context: &str,
+) -> Option<MutexGuard<'a, T>> {
+    match mutex.try_lock() {
+        Ok(guard) => Some(guard),
+        Err(TryLockError::Poisoned(poisoned)) => {
+            log::warn!("Recovering from poisoned mutex: {}", context);
+            let guard = poisoned.into_inner();
+            mutex.clear_poison();
+            Some(guard)
+        }
+        Err(TryLockError::WouldBlock) => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,6 +356,18 @@ mod tests {
         assert_eq!(result, serde_json::json!({"test": 123}));
     }
 
+    #[test]
+    fn test_decode_base64_rejects_oversized_input() {
+        let input = "A".repeat(17);
+        assert!(decode_base64_with_limit(&input[..16], 16).is_ok());
+        let err = decode_base64_with_limit(&input, 16).unwrap_err();
+        assert!(err.contains("base64 input too large"));
+        let err = decode_base64url_with_limit(&input, 16).unwrap_err();
+        assert!(err.contains("base64 input too large"));
+        // Padding added by the url variant does not count against the limit
+        assert!(decode_base64url_with_limit(&input[..15], 15).is_ok());
+    }
+
     #[test]
     fn test_decode_base64_invalid_char() {
         let input = "SGVsbG8g!!!";
@@ -184,4 +375,47 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("invalid base64 character"));
     }
+
+    #[test]
+    fn test_digest_reader_matches_known_vectors() {
+        let sha256 = digest_reader_hex(&b"abc"[..], HashAlgorithm::Sha256).unwrap();
+        assert_eq!(sha256, sha256_digest_hex(b"abc"));
+        let sha512 = digest_reader_hex(&b"abc"[..], "SHA-512".parse().unwrap()).unwrap();
+        assert!(sha512.starts_with("ddaf35a193617aba"));
+        assert_eq!(sha512.len(), 128);
+        assert!("md5".parse::<HashAlgorithm>().is_err());
+    }
+
+    #[test]
+    fn test_sha256_digest_hex_empty() {
+        assert_eq!(
+            sha256_digest_hex(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn test_sha256_digest_hex_of_decoded_base64() {
+        let data = decode_base64("SGVsbG8gV29ybGQ=").unwrap();
+        assert_eq!(
+            sha256_digest_hex(&data),
+            "a591a6d40bf420404a011733cfb7b190d62c65bf0bcda32b57b277d9ad9f146e"
+        );
+    }
+
+    #[test]
+    fn test_lock_or_recover_poisoned_mutex() {
+        let mutex = std::sync::Arc::new(Mutex::new(1));
+        let poisoner = std::sync::Arc::clone(&mutex);
+        let _ = std::thread::spawn(move || {
+            let _guard = poisoner.lock().unwrap();
+            panic!("poison the mutex");
+        })
+        .join();
+
+        assert!(mutex.is_poisoned());
+        *lock_or_recover(&mutex, "test") += 1;
+        assert!(!mutex.is_poisoned());
+        assert_eq!(*lock_or_recover(&mutex, "test"), 2);
+    }
 }
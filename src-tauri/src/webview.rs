@@ -20,19 +20,36 @@
 //! - 导航被取消（返回 false），不会真正跳转，避免页面中断
 //! - Rust 端解码确保前端逻辑简单，降低出错概率
 //! - 错误通过 /error 路径传递，统一错误处理
+//! - 聚合进行中到达的 begin 只拒绝新的一轮，通过 `child-webview:injection-rejected` 事件告知，
+//!   不影响进行中的注入结果
+//! - 后端探测脚本使用单独的 `/probe-result` 路径与 `child-webview:probe-result` 事件，
+//!   一次导航回传完整结果，不占用注入结果的聚合状态
 
 use std::collections::HashMap;
-use std::sync::Mutex;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use tauri::{
     webview::{NewWindowResponse, Webview, WebviewBuilder},
-    Emitter, LogicalPosition, LogicalSize, Manager, Position, Size, State, Url, WebviewUrl, Window,
+    AppHandle, Emitter, Listener, LogicalPosition, LogicalSize, Manager, Position, Size, State,
+    Url, WebviewUrl, Window,
 };
 use tauri_plugin_opener::open_url;
 
-use crate::proxy::{parse_external_url, parse_proxy_url, resolve_proxy_data_directory};
+use crate::process_usage::{
+    app_process_tree_usage, subtree_usage, total_usage, ProcessUsage,
+    PROCESS_TREE_INCLUDES_WEBVIEWS,
+};
+use crate::proxy::{
+    parse_external_url, parse_proxy_url, resolve_proxy_data_directory, webview_proxy_url,
+};
+use crate::update::configured_proxy;
 use crate::utils::decode_base64url_to_json;
+use crate::{app_paths, settings};
 
 /// 保存所有活跃子 WebView 实例
 ///
@@ -40,6 +57,8 @@ use crate::utils::decode_base64url_to_json;
 #[derive(Default)]
 pub(crate) struct ChildWebviewManager {
     webviews: Mutex<HashMap<String, ManagedWebview>>,
+    /// 统一缩放比例，新建的子 WebView 也会应用；`None` 表示保持引擎默认值
+    default_zoom: Mutex<Option<f64>>,
 }
 
 /// 单个子 WebView 的管理信息
@@ -49,10 +68,73 @@ pub(crate) struct ChildWebviewManager {
 struct ManagedWebview {
     webview: Webview,
     proxy_url: Option<String>,
+    /// 新窗口策略（与 on_new_window 回调共享，更新时无需重建）
+    new_window_policy: Arc<Mutex<NewWindowPolicy>>,
+    /// 已注入的样式 `(style_id, css)`，页面加载完成或重建后重新应用
+    injected_css: InjectedStyles,
+    /// 页面加载状态（由 on_page_load 回调更新）
+    load_state: Arc<Mutex<ChildWebviewLoadState>>,
+    /// 加载失败自动重试状态
+    load_retry: Arc<Mutex<LoadRetryState>>,
+    /// 最近一次的页面标题（由 on_document_title_changed 回调更新）
+    title: Arc<Mutex<Option<String>>>,
+    /// 附加请求头（创建时注入，变化时需要重建）
+    extra_headers: HashMap<String, String>,
+    /// Referrer 策略（创建时注入，变化时需要重建）
+    referrer_policy: Option<String>,
+    /// 是否为无痕会话（创建时决定，变化时需要重建）
+    incognito: bool,
+    /// 无痕会话的临时数据目录，关闭或重建时删除
+    incognito_data_dir: Option<PathBuf>,
+    /// 挂起前的真实地址；为 `Some` 时表示已挂起（页面已切换到 `about:blank`）
+    suspended_url: Option<Url>,
+    /// 与 `suspended_url.is_some()` 同步，供页面加载与标题回调忽略挂起用的 `about:blank`
+    suspended: Arc<AtomicBool>,
+    /// 最近一次隐藏的时间，用于空闲自动挂起；显示中为 `None`
+    hidden_since: Option<Instant>,
+    /// 最近一次 ensure 的请求参数，用于按新代理重建时沿用其余配置
+    last_payload: EnsureChildWebviewPayload,
+}
+
+/// 加载失败自动重试状态
+#[derive(Debug, Default)]
+struct LoadRetryState {
+    max_retries: u32,
+    backoff_ms: u64,
+    /// 已重试次数，加载成功后清零
+    attempts: u32,
+    /// 最近一次请求加载的地址（错误页地址不会覆盖）
+    target_url: Option<Url>,
+}
+
+/// 子 WebView 页面加载状态
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(tag = "state", rename_all = "camelCase")]
+pub(crate) enum ChildWebviewLoadState {
+    #[default]
+    Loading,
+    Ready,
+    Failed {
+        error: String,
+    },
+}
+
+type InjectedStyles = Arc<Mutex<Vec<(String, String)>>>;
+
+/// 子 WebView 中 target=_blank / window.open 的处理策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub(crate) enum NewWindowPolicy {
+    /// 使用系统默认浏览器打开（默认）
+    #[default]
+    OpenInBrowser,
+    /// 通知宿主创建新的子 WebView
+    OpenInNewChild,
+    /// 直接拒绝
+    Deny,
 }
 
 /// WebView 位置参数（逻辑坐标）
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub(crate) struct PositionPayload {
     #[serde(rename = "x")]
     x: f64,
@@ -61,7 +143,7 @@ pub(crate) struct PositionPayload {
 }
 
 /// WebView 尺寸参数（逻辑坐标）
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub(crate) struct SizePayload {
     #[serde(rename = "width")]
     width: f64,
@@ -70,7 +152,7 @@ pub(crate) struct SizePayload {
 }
 
 /// WebView 边界参数（位置 + 尺寸 + 缩放因子）
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub(crate) struct BoundsPayload {
     #[serde(rename = "positionLogical")]
     position_logical: PositionPayload,
@@ -80,15 +162,144 @@ pub(crate) struct BoundsPayload {
     _scale_factor: f64,
 }
 
+/// 反序列化可区分“字段缺省”（`None`）与“显式 null”（`Some(None)`）的可选字段
+fn deserialize_explicit_option<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// 解析子 WebView 实际使用的代理：显式覆盖优先，否则继承全局代理
+fn resolve_requested_proxy(
+    app: &AppHandle,
+    proxy_override: &Option<Option<String>>,
+) -> Option<String> {
+    match proxy_override {
+        Some(explicit) => explicit.clone(),
+        None => configured_proxy(app).and_then(|config| webview_proxy_url(&config)),
+    }
+}
+
 /// 创建或更新子 WebView 的请求参数
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct EnsureChildWebviewPayload {
     id: String,
     url: String,
     /// 边界参数（可选）- 如果不提供且 webview 已存在，则不更新位置和大小
     bounds: Option<BoundsPayload>,
-    proxy_url: Option<String>,
+    /// 代理覆盖：字段缺省时继承全局代理；显式传 `null` 表示该 WebView 直连（不使用代理）；
+    /// 传地址时使用指定代理
+    #[serde(default, deserialize_with = "deserialize_explicit_option")]
+    proxy_url: Option<Option<String>>,
+    /// 新窗口策略，缺省为 `OpenInBrowser`
+    #[serde(default)]
+    new_window_policy: NewWindowPolicy,
+    /// 加载失败时的最大自动重试次数
+    max_load_retries: Option<u32>,
+    /// 重试退避基准时长（毫秒），每次重试翻倍
+    load_retry_backoff_ms: Option<u64>,
+    /// 注入结果聚合的最大字节数（创建时生效），缺省为 `DEFAULT_MAX_INJECTION_RESULT_BYTES`
+    max_injection_result_bytes: Option<usize>,
+    /// 附加到页面同源 fetch/XHR 请求的 HTTP 头
+    ///
+    /// 由初始化脚本改写 `fetch`/`XMLHttpRequest` 实现，页面文档请求本身只有 Windows
+    /// （WebView2 资源请求拦截）会带上这些请求头；macOS 与 Linux 的首个文档请求及后续同源导航不会携带。
+    #[serde(default)]
+    extra_headers: HashMap<String, String>,
+    /// 页面 Referrer 策略（如 `no-referrer`、`origin`），创建时通过初始化脚本生效
+    referrer_policy: Option<String>,
+    /// 以无痕会话创建（不持久化历史与 Cookie，使用关闭即删除的临时数据目录）
+    #[serde(default)]
+    incognito: bool,
+}
+
+/// 注入结果分片的聚合状态
+#[derive(Debug, Default)]
+struct InjectionAggregate {
+    expected: usize,
+    received: usize,
+    data: String,
+    /// 本轮聚合已被中止（超出大小上限或分片数非法），后续分片与 end 信号直接忽略
+    aborted: bool,
+    /// 最近一次收到 begin/chunk 的时间，用于判断聚合是否仍在进行
+    last_activity: Option<Instant>,
+}
+
+impl InjectionAggregate {
+    fn aborted() -> Self {
+        Self {
+            aborted: true,
+            ..Self::default()
+        }
+    }
+
+    /// 已收到部分分片且未超时，说明另一轮聚合仍在进行
+    fn is_in_flight(&self, now: Instant) -> bool {
+        !self.aborted
+            && self.received > 0
+            && self.received < self.expected
+            && self
+                .last_activity
+                .is_some_and(|at| now.duration_since(at) < INJECTION_AGGREGATION_STALE_AFTER)
+    }
+
+    /// 开始新一轮聚合
+    ///
+    /// 进行中的聚合不会被打断；尚未收到分片或已超时的聚合视为合法重启。
+    /// 分片数为 0 或超过 `MAX_INJECTION_CHUNKS` 时拒绝，并忽略该轮后续分片。
+    fn begin(&mut self, expected: usize, now: Instant) -> Result<(), &'static str> {
+        if self.is_in_flight(now) {
+            return Err("aggregation_in_progress");
+        }
+        if expected == 0 || expected > MAX_INJECTION_CHUNKS {
+            *self = Self::aborted();
+            return Err("invalid_chunk_count");
+        }
+        *self = Self {
+            expected,
+            last_activity: Some(now),
+            ..Self::default()
+        };
+        Ok(())
+    }
+
+    /// 追加一个分片，返回是否被接受
+    ///
+    /// 分片声明的总数与 begin 不一致时忽略（多为被拒绝的 begin 所属的分片）；
+    /// 累计数据超过上限时丢弃已聚合内容并中止本轮聚合。
+    fn push_chunk(
+        &mut self,
+        chunk: &str,
+        total: Option<usize>,
+        max_bytes: usize,
+        now: Instant,
+    ) -> Result<bool, &'static str> {
+        if self.aborted || total.is_some_and(|total| total != self.expected) {
+            return Ok(false);
+        }
+        if self.data.len().saturating_add(chunk.len()) > max_bytes {
+            *self = Self::aborted();
+            return Err("payload_too_large");
+        }
+        self.data.push_str(chunk);
+        self.received = self.received.saturating_add(1);
+        self.last_activity = Some(now);
+        Ok(true)
+    }
+
+    /// 处理 end 信号：取出 `(expected, received, data)` 并复位；已中止的聚合返回 `None`
+    ///
+    /// end 声明的总数与当前聚合不一致时（属于被拒绝的 begin）返回 `None` 且不影响当前聚合。
+    fn finish(&mut self, total: Option<usize>) -> Option<(usize, usize, String)> {
+        if !self.aborted && self.expected > 0 && total.is_some_and(|total| total != self.expected) {
+            return None;
+        }
+        let finished = std::mem::take(self);
+        (!finished.aborted).then_some((finished.expected, finished.received, finished.data))
+    }
 }
 
 /// 更新子 WebView 边界的请求参数
@@ -104,8 +315,275 @@ pub(crate) struct ChildWebviewIdPayload {
     id: String,
 }
 
-/// 支持通过系统默认程序打开的新窗口 URL Scheme
+/// 注入结果回传事件
+const INJECTION_RESULT_EVENT: &str = "child-webview:injection-result";
+/// 聚合进行中时新的 begin 被拒绝的事件（与注入结果事件分开，避免误判进行中的注入失败）
+const INJECTION_REJECTED_EVENT: &str = "child-webview:injection-rejected";
+/// 注入结果聚合的默认大小上限（base64url 编码后的字节数）
+const DEFAULT_MAX_INJECTION_RESULT_BYTES: usize = 4 * 1024 * 1024;
+/// begin 信号允许声明的最大分片数
+const MAX_INJECTION_CHUNKS: usize = 100_000;
+/// 聚合超过该时长无新分片即视为已中断，允许新的 begin 重新开始
+const INJECTION_AGGREGATION_STALE_AFTER: Duration = Duration::from_secs(10);
+/// 新窗口策略为 `OpenInNewChild` 时通知宿主的事件
+const NEW_WINDOW_REQUESTED_EVENT: &str = "child-webview:new-window-requested";
+/// 页面加载完成通知宿主的事件
+const READY_EVENT: &str = "child-webview:ready";
+/// 页面标题变化通知宿主的事件
+const TITLE_CHANGED_EVENT: &str = "child-webview:title-changed";
+/// 自动重试耗尽后通知宿主的事件
+const LOAD_FAILED_EVENT: &str = "child-webview:load-failed";
+const DEFAULT_MAX_LOAD_RETRIES: u32 = 2;
+const DEFAULT_LOAD_RETRY_BACKOFF_MS: u64 = 1000;
+const MAX_LOAD_RETRY_DELAY: Duration = Duration::from_secs(30);
+/// 导航被阻止时通知宿主的事件
+const NAVIGATION_BLOCKED_EVENT: &str = "child-webview:navigation-blocked";
+/// 后端探测脚本等待结果的超时时间
+const PROBE_RESULT_TIMEOUT: Duration = Duration::from_secs(5);
+/// 后端探测结果回传事件（仅后端监听，与注入结果事件分开）
+const PROBE_RESULT_EVENT: &str = "child-webview:probe-result";
+/// 探测结果编码后的长度上限，超出时脚本改为回传错误
+const PROBE_RESULT_MAX_ENCODED_LEN: usize = 16 * 1024;
+/// 后端探测脚本请求编号
+static NEXT_PROBE_REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+/// 注入样式编号
+static NEXT_INJECTED_STYLE_ID: AtomicU64 = AtomicU64::new(1);
+
+/// 默认支持通过系统默认程序打开的新窗口 URL Scheme
 const SUPPORTED_EXTERNAL_URL_SCHEMES: [&str; 4] = ["http", "https", "mailto", "tel"];
+/// 出于安全考虑始终禁止交给系统打开的 Scheme
+const FORBIDDEN_EXTERNAL_URL_SCHEMES: [&str; 5] = ["javascript", "data", "file", "blob", "about"];
+const SETTING_EXTERNAL_URL_SCHEMES: &str = "externalUrlSchemes";
+const SETTING_CHILD_WEBVIEW_ZOOM: &str = "childWebviewZoom";
+const MIN_CHILD_WEBVIEW_ZOOM: f64 = 0.25;
+const MAX_CHILD_WEBVIEW_ZOOM: f64 = 5.0;
+
+/// 当前生效的外部 Scheme 集合（可在启动时或通过命令修改）
+fn external_url_schemes() -> &'static RwLock<Vec<String>> {
+    static SCHEMES: OnceLock<RwLock<Vec<String>>> = OnceLock::new();
+    SCHEMES.get_or_init(|| RwLock::new(default_external_url_schemes()))
+}
+
+fn default_external_url_schemes() -> Vec<String> {
+    SUPPORTED_EXTERNAL_URL_SCHEMES
+        .iter()
+        .map(|scheme| scheme.to_string())
+        .collect()
+}
+
+/// 规范化 Scheme 列表：去空白、转小写、去掉末尾冒号并去重；
+/// 非法或被禁止的项会被丢弃，结果为空时回退到默认集合
+fn normalize_external_url_schemes(schemes: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for scheme in schemes {
+        let scheme = scheme.trim().trim_end_matches(':').to_ascii_lowercase();
+        let valid = scheme
+            .chars()
+            .next()
+            .is_some_and(|ch| ch.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '+' | '-' | '.'));
+        if !valid || FORBIDDEN_EXTERNAL_URL_SCHEMES.contains(&scheme.as_str()) {
+            log::warn!("Ignoring invalid external URL scheme: {:?}", scheme);
+            continue;
+        }
+        if !normalized.contains(&scheme) {
+            normalized.push(scheme);
+        }
+    }
+
+    if normalized.is_empty() {
+        default_external_url_schemes()
+    } else {
+        normalized
+    }
+}
+
+fn replace_external_url_schemes(schemes: Vec<String>) {
+    match external_url_schemes().write() {
+        Ok(mut guard) => *guard = schemes,
+        Err(poisoned) => *poisoned.into_inner() = schemes,
+    }
+}
+
+/// 子 WebView 导航黑名单（精确主机名或 `*.domain` 通配）
+fn navigation_blocklist() -> &'static RwLock<Vec<String>> {
+    static BLOCKLIST: OnceLock<RwLock<Vec<String>>> = OnceLock::new();
+    BLOCKLIST.get_or_init(|| RwLock::new(Vec::new()))
+}
+
+/// 规范化黑名单规则：去除首尾空白与末尾的点并转小写，丢弃空规则，保持原有顺序去重
+fn normalize_navigation_blocklist(patterns: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for pattern in patterns {
+        let pattern = pattern.trim().trim_end_matches('.').to_ascii_lowercase();
+        if pattern.is_empty() || pattern == "*." {
+            continue;
+        }
+        if !normalized.contains(&pattern) {
+            normalized.push(pattern);
+        }
+    }
+    normalized
+}
+
+/// 判断主机名是否匹配黑名单规则
+///
+/// `*.example.com` 同时匹配 `example.com` 本身及其所有子域名。
+fn host_matches_pattern(host: &str, pattern: &str) -> bool {
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    match pattern.strip_prefix("*.") {
+        Some(domain) => host == domain || host.ends_with(&format!(".{domain}")),
+        None => host == pattern,
+    }
+}
+
+fn is_navigation_blocked(url: &Url) -> bool {
+    let Some(host) = url.host_str() else {
+        return false;
+    };
+    let blocklist = match navigation_blocklist().read() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    blocklist
+        .iter()
+        .any(|pattern| host_matches_pattern(host, pattern))
+}
+
+/// 无痕会话临时数据目录的父目录名
+const INCOGNITO_DATA_DIR_NAME: &str = "webview-incognito";
+/// 删除无痕数据目录的重试次数（WebView 进程退出前文件可能仍被占用）
+const INCOGNITO_CLEANUP_ATTEMPTS: u32 = 10;
+/// 删除无痕数据目录的重试间隔
+const INCOGNITO_CLEANUP_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+fn incognito_data_root(app: &AppHandle) -> Option<PathBuf> {
+    Some(app_paths::data_or_cache_dir(app)?.join(INCOGNITO_DATA_DIR_NAME))
+}
+
+/// 为无痕会话创建唯一的临时数据目录
+fn create_incognito_data_dir(app: &AppHandle, id: &str) -> Option<PathBuf> {
+    let sanitized: String = id
+        .chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '_' })
+        .collect();
+    let nonce = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis())
+        .unwrap_or_default();
+    let dir = incognito_data_root(app)?.join(format!("{sanitized}-{nonce}"));
+
+    match fs::create_dir_all(&dir) {
+        Ok(()) => Some(dir),
+        Err(err) => {
+            log::error!(
+                "Failed to create incognito data directory {:?}: {}",
+                dir,
+                err
+            );
+            None
+        }
+    }
+}
+
+/// 在后台删除无痕数据目录，WebView 进程仍占用文件时稍后重试
+fn remove_incognito_data_dir(dir: PathBuf) {
+    std::thread::spawn(move || {
+        for attempt in 1..=INCOGNITO_CLEANUP_ATTEMPTS {
+            match fs::remove_dir_all(&dir) {
+                Ok(()) => {
+                    log::debug!("Incognito data directory removed: {:?}", dir);
+                    return;
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => return,
+                Err(err) if attempt == INCOGNITO_CLEANUP_ATTEMPTS => {
+                    log::warn!(
+                        "Failed to remove incognito data directory {:?}: {}",
+                        dir,
+                        err
+                    );
+                }
+                Err(_) => std::thread::sleep(INCOGNITO_CLEANUP_RETRY_DELAY),
+            }
+        }
+    });
+}
+
+/// 启动时清理上次异常退出遗留的无痕数据目录
+///
+/// 同步记下启动时已存在的子目录后只删除这些目录，本次会话新建的无痕目录不受后台删除影响。
+pub(crate) fn cleanup_incognito_data_dirs(app: &AppHandle) {
+    let Some(root) = incognito_data_root(app) else {
+        return;
+    };
+    let Ok(entries) = fs::read_dir(&root) else {
+        return;
+    };
+    let stale: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    if !stale.is_empty() {
+        log::info!("Removing {} stale incognito data directories", stale.len());
+    }
+    for dir in stale {
+        remove_incognito_data_dir(dir);
+    }
+}
+
+/// 查询子 WebView 是否为无痕会话
+#[tauri::command]
+pub(crate) async fn get_child_webview_incognito(
+    state: State<'_, ChildWebviewManager>,
+    id: String,
+) -> Result<bool, String> {
+    let webviews = state
+        .webviews
+        .lock()
+        .map_err(|err| format!("failed to lock webview map: {err}"))?;
+    webviews
+        .get(&id)
+        .map(|entry| entry.incognito)
+        .ok_or_else(|| format!("child webview not found: {id}"))
+}
+
+/// 启动时加载已保存的外部 Scheme 配置
+pub(crate) fn init_external_url_schemes(app: &AppHandle) {
+    if let Some(schemes) = settings::load_setting::<Vec<String>>(app, SETTING_EXTERNAL_URL_SCHEMES)
+    {
+        let schemes = normalize_external_url_schemes(&schemes);
+        log::info!("Loaded external URL schemes: {:?}", schemes);
+        replace_external_url_schemes(schemes);
+    }
+}
+
+/// 启动时加载已保存的统一缩放比例
+pub(crate) fn init_child_webview_zoom(app: &AppHandle) {
+    let Some(factor) = settings::load_setting::<f64>(app, SETTING_CHILD_WEBVIEW_ZOOM) else {
+        return;
+    };
+    let Ok(factor) = clamp_zoom_factor(factor) else {
+        log::warn!("Ignoring invalid saved child webview zoom: {}", factor);
+        return;
+    };
+    if let Some(state) = app.try_state::<ChildWebviewManager>() {
+        log::info!("Loaded child webview zoom: {}", factor);
+        if let Ok(mut zoom) = state.default_zoom.lock() {
+            *zoom = Some(factor);
+        }
+    }
+}
+
+/// 将缩放比例限制在允许范围内，拒绝非有限值
+fn clamp_zoom_factor(factor: f64) -> Result<f64, String> {
+    if !factor.is_finite() {
+        return Err("Invalid zoom factor".to_string());
+    }
+    Ok(factor.clamp(MIN_CHILD_WEBVIEW_ZOOM, MAX_CHILD_WEBVIEW_ZOOM))
+}
 
 /// 部分站点（如千问）在内嵌 WebView 环境下会基于 UA 进行兼容性限制。
 /// 为其使用标准桌面浏览器 UA，可提高页面可访问性。
@@ -117,7 +595,14 @@ fn should_use_desktop_user_agent(webview_id: &str, url: &str) -> bool {
 }
 
 fn should_open_in_default_browser(url: &Url) -> bool {
-    SUPPORTED_EXTERNAL_URL_SCHEMES.contains(&url.scheme())
+    let scheme = url.scheme();
+    match external_url_schemes().read() {
+        Ok(schemes) => schemes.iter().any(|allowed| allowed == scheme),
+        Err(poisoned) => poisoned
+            .into_inner()
+            .iter()
+            .any(|allowed| allowed == scheme),
+    }
 }
 
 fn open_new_window_in_browser(webview_id: &str, url: &Url) {
@@ -159,6 +644,8 @@ fn logical_size(bounds: &BoundsPayload) -> LogicalSize<f64> {
 }
 
 /// 确保子 WebView 存在或在代理发生变化时重建
+///
+/// `extra_headers` 在 Windows 上同时附加到同源文档请求；macOS 与 Linux 只作用于页面发起的同源 fetch/XHR。
 #[tauri::command]
 pub(crate) async fn ensure_child_webview(
     window: Window,
@@ -184,26 +671,55 @@ pub(crate) async fn ensure_child_webview(
         .lock()
         .map_err(|err| format!("failed to lock webview map: {err}"))?;
 
-    let requested_proxy = payload.proxy_url.as_deref();
+    let resolved_proxy = resolve_requested_proxy(window.app_handle(), &payload.proxy_url);
+    let requested_proxy = resolved_proxy.as_deref();
     let should_recreate = webviews
         .get(&payload.id)
-        .map(|entry| entry.proxy_url.as_deref() != requested_proxy)
+        .map(|entry| {
+            entry.proxy_url.as_deref() != requested_proxy
+                || entry.extra_headers != payload.extra_headers
+                || entry.referrer_policy != payload.referrer_policy
+                || entry.incognito != payload.incognito
+        })
         .unwrap_or(false);
 
+    // 重建时沿用已注入的样式，新页面加载完成后重新应用
+    let mut carried_css = None;
     if should_recreate {
         log::info!(
-            "Proxy config changed, recreating child webview: {}",
+            "Proxy, header, referrer or session config changed, recreating child webview: {}",
             payload.id
         );
         if let Some(entry) = webviews.remove(&payload.id) {
+            carried_css = Some(entry.injected_css.clone());
             let _ = entry.webview.close();
+            if let Some(dir) = entry.incognito_data_dir {
+                remove_incognito_data_dir(dir);
+            }
         }
     }
 
-    if let Some(entry) = webviews.get(&payload.id) {
+    if let Some(entry) = webviews.get_mut(&payload.id) {
+        // 已挂起的 WebView 与显式恢复一样回到挂起前的地址并发出恢复事件，不再按请求地址导航
+        let resumed = entry.suspended_url.is_some();
+        if resumed {
+            log::info!("Resuming suspended child webview on ensure: {}", payload.id);
+            resume_managed_webview(&payload.id, entry)?;
+        }
+        entry.last_payload = payload.clone();
         let webview = &entry.webview;
 
-        if let Ok(current_url) = webview.url() {
+        if let Ok(mut policy) = entry.new_window_policy.lock() {
+            *policy = payload.new_window_policy;
+        }
+        if let Ok(mut retry) = entry.load_retry.lock() {
+            retry.max_retries = payload.max_load_retries.unwrap_or(DEFAULT_MAX_LOAD_RETRIES);
+            retry.backoff_ms = payload
+                .load_retry_backoff_ms
+                .unwrap_or(DEFAULT_LOAD_RETRY_BACKOFF_MS);
+        }
+
+        if let Some(current_url) = webview.url().ok().filter(|_| !resumed) {
             if current_url.as_str() != payload.url {
                 log::info!(
                     "Updating child webview URL: {} -> {}",
@@ -252,10 +768,43 @@ pub(crate) async fn ensure_child_webview(
             builder = builder.user_agent(CHILD_WEBVIEW_DESKTOP_USER_AGENT);
         }
 
-        if let Some(proxy_url) = requested_proxy {
-            builder = builder.proxy_url(parse_proxy_url(proxy_url)?);
-            if let Some(data_dir) = resolve_proxy_data_directory(&window, requested_proxy) {
-                builder = builder.data_directory(data_dir);
+        if let Some(script) = build_extra_headers_script(&payload.extra_headers) {
+            log::debug!(
+                "Applying extra headers to child webview {}: {:?}",
+                payload.id,
+                redact_headers_for_log(&payload.extra_headers)
+            );
+            builder = builder.initialization_script(script);
+        }
+
+        if let Some(policy) = payload.referrer_policy.as_deref() {
+            log::debug!(
+                "Applying referrer policy to child webview {}: {}",
+                payload.id,
+                policy
+            );
+            builder = builder.initialization_script(build_referrer_policy_script(policy)?);
+        }
+
+        let parsed_proxy_url = requested_proxy.map(parse_proxy_url).transpose()?;
+
+        // 无痕会话使用独立的临时数据目录，优先于代理数据目录
+        let incognito_data_dir = if payload.incognito {
+            builder = builder.incognito(true);
+            create_incognito_data_dir(window.app_handle(), &payload.id)
+        } else {
+            None
+        };
+        if let Some(dir) = &incognito_data_dir {
+            builder = builder.data_directory(dir.clone());
+        }
+
+        if let Some(proxy_url) = parsed_proxy_url {
+            builder = builder.proxy_url(proxy_url);
+            if incognito_data_dir.is_none() {
+                if let Some(data_dir) = resolve_proxy_data_directory(&window, requested_proxy) {
+                    builder = builder.data_directory(data_dir);
+                }
             }
         }
 
@@ -263,8 +812,10 @@ pub(crate) async fn ensure_child_webview(
         let main_window = window.clone();
         let app_handle = window.app_handle().clone();
         let webview_id_for_events = payload.id.clone();
-        use std::sync::{Arc, Mutex};
-        let agg_state = Arc::new(Mutex::new((0usize, 0usize, String::new()))); // (expected, received, data)
+        let agg_state = Arc::new(Mutex::new(InjectionAggregate::default()));
+        let max_injection_result_bytes = payload
+            .max_injection_result_bytes
+            .unwrap_or(DEFAULT_MAX_INJECTION_RESULT_BYTES);
 
         // Intercept navigation to http(s)://injection.localhost/* to shuttle injection results
         {
@@ -283,41 +834,117 @@ pub(crate) async fn ensure_child_webview(
                                 .find(|(k, _)| k == name)
                                 .map(|(_, v)| v.to_string())
                         };
-                        if path.starts_with("begin") {
+                        if path == "probe-result" {
+                            // 探测结果一次回传，不经过注入结果的聚合状态
+                            let data = get_param("d").unwrap_or_default();
+                            match decode_base64url_to_json(&data) {
+                                Ok(result) => {
+                                    if let Err(e) = app_handle_nav.emit(
+                                        PROBE_RESULT_EVENT,
+                                        serde_json::json!({
+                                            "id": webview_id_nav,
+                                            "result": result
+                                        }),
+                                    ) {
+                                        log::error!(
+                                            "[NAV-INTERCEPT] Failed to emit probe result: {}",
+                                            e
+                                        );
+                                    }
+                                }
+                                Err(e) => log::warn!("[NAV-INTERCEPT] Probe result decode failed: {}", e),
+                            }
+                        } else if path.starts_with("begin") {
                             if let Some(t_str) = get_param("t") {
                                 if let Ok(t) = t_str.parse::<usize>() {
                                     log::info!("[NAV-INTERCEPT] Begin: expecting {} chunks", t);
-                                    if let Ok(mut st) = agg_nav.lock() {
-                                        st.0 = t;
-                                        st.1 = 0;
-                                        st.2.clear();
+                                    let begun = match agg_nav.lock() {
+                                        Ok(mut st) => st.begin(t, Instant::now()),
+                                        Err(_) => Ok(()),
+                                    };
+                                    if let Err(error) = begun {
+                                        log::warn!("[NAV-INTERCEPT] Begin rejected: {}", error);
+                                        // 进行中的聚合被保留，只把拒绝告知新的一轮；
+                                        // 其他原因下该轮就是当前唯一的注入，按注入失败回传
+                                        let event = if error == "aggregation_in_progress" {
+                                            INJECTION_REJECTED_EVENT
+                                        } else {
+                                            INJECTION_RESULT_EVENT
+                                        };
+                                        if let Err(e) = app_handle_nav.emit(
+                                            event,
+                                            serde_json::json!({
+                                                "id": webview_id_nav,
+                                                "success": false,
+                                                "error": error,
+                                                "expected": t
+                                            }),
+                                        ) {
+                                            log::error!(
+                                                "[NAV-INTERCEPT] Failed to emit error event: {}",
+                                                e
+                                            );
+                                        }
                                     }
                                 }
                             }
                         } else if path.starts_with("chunk") {
                             let d = get_param("d").unwrap_or_default();
+                            let total = get_param("t").and_then(|t| t.parse::<usize>().ok());
                             if let Ok(mut st) = agg_nav.lock() {
-                                st.2.push_str(&d);
-                                st.1 = st.1.saturating_add(1);
-                                log::info!(
-                                    "[NAV-INTERCEPT] Chunk: received {}/{}, data_len={}",
-                                    st.1,
-                                    st.0,
-                                    st.2.len()
-                                );
+                                match st.push_chunk(
+                                    &d,
+                                    total,
+                                    max_injection_result_bytes,
+                                    Instant::now(),
+                                ) {
+                                    Ok(false) => log::debug!(
+                                        "[NAV-INTERCEPT] Chunk ignored (no matching aggregation)"
+                                    ),
+                                    Ok(true) => log::info!(
+                                        "[NAV-INTERCEPT] Chunk: received {}/{}, data_len={}",
+                                        st.received,
+                                        st.expected,
+                                        st.data.len()
+                                    ),
+                                    Err(error) => {
+                                        log::warn!(
+                                            "[NAV-INTERCEPT] Aggregated result exceeds {} bytes, aborting",
+                                            max_injection_result_bytes
+                                        );
+                                        if let Err(e) = app_handle_nav.emit(
+                                            INJECTION_RESULT_EVENT,
+                                            serde_json::json!({
+                                                "id": webview_id_nav,
+                                                "success": false,
+                                                "error": error
+                                            }),
+                                        ) {
+                                            log::error!(
+                                                "[NAV-INTERCEPT] Failed to emit error event: {}",
+                                                e
+                                            );
+                                        }
+                                    }
+                                }
                             }
                         } else if path.starts_with("end") {
                             // 若 mutex 中毒，仍尝试取出内部状态以避免后续永久阻塞
-                            let (expected, received, data) = match agg_nav.lock() {
-                                Ok(mut s) => (s.0, s.1, std::mem::take(&mut s.2)),
+                            let total = get_param("t").and_then(|t| t.parse::<usize>().ok());
+                            let finished = match agg_nav.lock() {
+                                Ok(mut s) => s.finish(total),
                                 Err(poisoned) => {
                                     log::warn!(
                                         "[NAV-INTERCEPT] agg_nav mutex poisoned, recovering inner state"
                                     );
-                                    let mut s = poisoned.into_inner();
-                                    (s.0, s.1, std::mem::take(&mut s.2))
+                                    poisoned.into_inner().finish(total)
                                 }
                             };
+                            // 已中止的聚合已发送过错误事件，不匹配的 end 不影响进行中的聚合
+                            let Some((expected, received, data)) = finished else {
+                                log::info!("[NAV-INTERCEPT] End ignored (aborted or unmatched aggregation)");
+                                return false;
+                            };
                             log::info!(
                                 "[NAV-INTERCEPT] End: expected={}, received={}, data_len={}",
                                 expected,
@@ -328,7 +955,7 @@ pub(crate) async fn ensure_child_webview(
                             if expected == 0 || received == 0 || received != expected {
                                 log::warn!("[NAV-INTERCEPT] Chunk mismatch");
                                 if let Err(e) = app_handle_nav.emit(
-                                    "child-webview:injection-result",
+                                    INJECTION_RESULT_EVENT,
                                     serde_json::json!({
                                         "id": webview_id_nav,
                                         "success": false,
@@ -351,7 +978,7 @@ pub(crate) async fn ensure_child_webview(
                                             "[NAV-INTERCEPT] Decode successful, emitting event"
                                         );
                                         if let Err(e) = app_handle_nav.emit(
-                                            "child-webview:injection-result",
+                                            INJECTION_RESULT_EVENT,
                                             serde_json::json!({
                                                 "id": webview_id_nav,
                                                 "success": true,
@@ -371,7 +998,7 @@ pub(crate) async fn ensure_child_webview(
                                     Err(e) => {
                                         log::error!("[NAV-INTERCEPT] Decode failed: {}", e);
                                         if let Err(emit_err) = app_handle_nav.emit(
-                                            "child-webview:injection-result",
+                                            INJECTION_RESULT_EVENT,
                                             serde_json::json!({
                                                 "id": webview_id_nav,
                                                 "success": false,
@@ -389,8 +1016,12 @@ pub(crate) async fn ensure_child_webview(
                         } else if path.starts_with("error") {
                             let m = get_param("m");
                             log::error!("[NAV-INTERCEPT] Error signal: {:?}", m);
+                            // 脚本出错时复位聚合状态，避免阻塞下一次 begin
+                            if let Ok(mut st) = agg_nav.lock() {
+                                *st = InjectionAggregate::default();
+                            }
                             if let Err(e) = app_handle_nav.emit(
-                                "child-webview:injection-result",
+                                INJECTION_RESULT_EVENT,
                                 serde_json::json!({
                                     "id": webview_id_nav,
                                     "success": false,
@@ -408,47 +1039,208 @@ pub(crate) async fn ensure_child_webview(
                         return false;
                     }
                 }
+
+                if is_navigation_blocked(url) {
+                    log::info!(
+                        "Blocked navigation in child webview {}: {}",
+                        webview_id_nav,
+                        url
+                    );
+                    let _ = app_handle_nav.emit(
+                        NAVIGATION_BLOCKED_EVENT,
+                        serde_json::json!({ "id": webview_id_nav, "url": url.as_str() }),
+                    );
+                    return false;
+                }
                 true
             });
         }
 
+        let new_window_policy = Arc::new(Mutex::new(payload.new_window_policy));
         {
             let webview_id_new_window = payload.id.clone();
+            let policy_new_window = new_window_policy.clone();
+            let app_handle_new_window = app_handle.clone();
             builder = builder.on_new_window(move |url, _features| {
-                open_new_window_in_browser(&webview_id_new_window, &url);
+                let policy = policy_new_window
+                    .lock()
+                    .map(|policy| *policy)
+                    .unwrap_or_default();
+                match policy {
+                    NewWindowPolicy::OpenInBrowser => {
+                        open_new_window_in_browser(&webview_id_new_window, &url);
+                    }
+                    NewWindowPolicy::OpenInNewChild => {
+                        log::info!(
+                            "New window requested from child webview {}: {}",
+                            webview_id_new_window,
+                            url
+                        );
+                        let _ = app_handle_new_window.emit(
+                            NEW_WINDOW_REQUESTED_EVENT,
+                            serde_json::json!({
+                                "id": webview_id_new_window,
+                                "url": url.as_str()
+                            }),
+                        );
+                    }
+                    NewWindowPolicy::Deny => {
+                        log::debug!(
+                            "Denied new window from child webview {}: {}",
+                            webview_id_new_window,
+                            url
+                        );
+                    }
+                }
                 NewWindowResponse::Deny
             });
         }
 
-        builder = builder.on_page_load(move |_wv, payload| {
+        let injected_css: InjectedStyles = carried_css.unwrap_or_default();
+        let injected_css_for_load = injected_css.clone();
+        let load_state = Arc::new(Mutex::new(ChildWebviewLoadState::Loading));
+        let load_state_for_load = load_state.clone();
+        let load_retry = Arc::new(Mutex::new(LoadRetryState {
+            max_retries: payload.max_load_retries.unwrap_or(DEFAULT_MAX_LOAD_RETRIES),
+            backoff_ms: payload
+                .load_retry_backoff_ms
+                .unwrap_or(DEFAULT_LOAD_RETRY_BACKOFF_MS),
+            attempts: 0,
+            target_url: parse_external_url(&payload.url).ok(),
+        }));
+        let load_retry_for_load = load_retry.clone();
+        let suspended = Arc::new(AtomicBool::new(false));
+        let suspended_for_title = suspended.clone();
+        let suspended_for_load = suspended.clone();
+        let title = Arc::new(Mutex::new(None));
+        let title_for_change = title.clone();
+        let main_window_for_title = main_window.clone();
+        let webview_id_for_title = webview_id_for_events.clone();
+        builder = builder.on_document_title_changed(move |_wv, new_title| {
+            if suspended_for_title.load(Ordering::SeqCst) {
+                return;
+            }
+            if let Ok(mut current) = title_for_change.lock() {
+                if current.as_deref() == Some(new_title.as_str()) {
+                    return;
+                }
+                *current = Some(new_title.clone());
+            }
+            let _ = main_window_for_title.emit(
+                TITLE_CHANGED_EVENT,
+                serde_json::json!({ "id": webview_id_for_title, "title": new_title }),
+            );
+        });
+        builder = builder.on_page_load(move |wv, payload| {
             use tauri::webview::PageLoadEvent;
+            // 挂起时导航到 about:blank 不属于真实页面加载，不更新状态、重试目标，也不发送事件
+            if suspended_for_load.load(Ordering::SeqCst) && payload.url().as_str() == "about:blank"
+            {
+                return;
+            }
             match payload.event() {
                 PageLoadEvent::Started => {
+                    set_load_state(&load_state_for_load, ChildWebviewLoadState::Loading);
+                    if detect_load_error(payload.url()).is_none() {
+                        if let Ok(mut retry) = load_retry_for_load.lock() {
+                            if retry.target_url.as_ref() != Some(payload.url()) {
+                                retry.target_url = Some(payload.url().clone());
+                                retry.attempts = 0;
+                            }
+                        }
+                    }
                     let _ = main_window.emit(
                         "child-webview:load-started",
                         serde_json::json!({ "id": webview_id_for_events }),
                     );
                 }
                 PageLoadEvent::Finished => {
-                    let _ = main_window.emit(
-                        "child-webview:ready",
-                        serde_json::json!({ "id": webview_id_for_events }),
-                    );
+                    let next_state = match detect_load_error(payload.url()) {
+                        Some(error) => {
+                            log::warn!(
+                                "Child webview {} failed to load: {}",
+                                webview_id_for_events,
+                                error
+                            );
+                            ChildWebviewLoadState::Failed { error }
+                        }
+                        None => ChildWebviewLoadState::Ready,
+                    };
+
+                    if let ChildWebviewLoadState::Failed { error } = &next_state {
+                        if schedule_load_retry(&wv, &webview_id_for_events, &load_retry_for_load) {
+                            return;
+                        }
+                        let _ = main_window.emit(
+                            LOAD_FAILED_EVENT,
+                            serde_json::json!({
+                                "id": webview_id_for_events,
+                                "url": payload.url().as_str(),
+                                "error": error
+                            }),
+                        );
+                    } else if let Ok(mut retry) = load_retry_for_load.lock() {
+                        retry.attempts = 0;
+                    }
+
+                    let failed = matches!(next_state, ChildWebviewLoadState::Failed { .. });
+                    set_load_state(&load_state_for_load, next_state);
+                    reapply_injected_css(&wv, &injected_css_for_load);
+                    // 失败时已发送 LOAD_FAILED_EVENT，不再发送就绪事件
+                    if !failed {
+                        let _ = main_window.emit(
+                            READY_EVENT,
+                            serde_json::json!({ "id": webview_id_for_events }),
+                        );
+                    }
                 }
             }
         });
 
-        let child = window
-            .add_child(builder, position, size)
-            .map_err(|err| err.to_string())?;
+        let child = match window.add_child(builder, position, size) {
+            Ok(child) => child,
+            Err(err) => {
+                if let Some(dir) = incognito_data_dir {
+                    remove_incognito_data_dir(dir);
+                }
+                return Err(err.to_string());
+            }
+        };
 
         let _ = child.hide();
 
+        #[cfg(target_os = "windows")]
+        install_document_headers_hook(&child, &payload.url, &payload.extra_headers);
+
+        let default_zoom = state.default_zoom.lock().ok().and_then(|zoom| *zoom);
+        if let Some(factor) = default_zoom {
+            if let Err(err) = child.set_zoom(factor) {
+                log::warn!(
+                    "Failed to apply zoom to child webview {}: {}",
+                    payload.id,
+                    err
+                );
+            }
+        }
+
         webviews.insert(
             payload.id.clone(),
             ManagedWebview {
                 webview: child,
-                proxy_url: payload.proxy_url.clone(),
+                proxy_url: resolved_proxy.clone(),
+                new_window_policy,
+                injected_css,
+                load_state,
+                load_retry,
+                title,
+                extra_headers: payload.extra_headers.clone(),
+                referrer_policy: payload.referrer_policy.clone(),
+                incognito: payload.incognito,
+                incognito_data_dir,
+                suspended_url: None,
+                suspended,
+                hidden_since: Some(Instant::now()),
+                last_payload: payload.clone(),
             },
         );
         log::info!("Child webview created successfully: {}", payload.id);
@@ -457,6 +1249,57 @@ pub(crate) async fn ensure_child_webview(
     Ok(())
 }
 
+/// 在后台预热子 WebView：创建并加载页面但保持隐藏，加载完成后发送 `child-webview:ready`
+///
+/// 与 `ensure_child_webview` 共用创建流程；已存在的 WebView 不改变可见性，
+/// 若页面已加载完成且地址未变则立即补发就绪事件
+#[tauri::command]
+pub(crate) async fn prewarm_child_webview(
+    window: Window,
+    state: State<'_, ChildWebviewManager>,
+    payload: EnsureChildWebviewPayload,
+) -> Result<(), String> {
+    let id = payload.id.clone();
+    let already_loaded = {
+        let webviews = state
+            .webviews
+            .lock()
+            .map_err(|err| format!("failed to lock webview map: {err}"))?;
+        webviews.get(&id).is_some_and(|entry| {
+            entry.suspended_url.is_none()
+                && entry
+                    .webview
+                    .url()
+                    .is_ok_and(|url| url.as_str() == payload.url)
+                && entry
+                    .load_state
+                    .lock()
+                    .is_ok_and(|load_state| *load_state == ChildWebviewLoadState::Ready)
+        })
+    };
+
+    log::info!("Prewarming child webview: {}", id);
+    ensure_child_webview(window.clone(), state.clone(), payload).await?;
+
+    // 若 ensure 因配置变化重建了 WebView，就绪事件由新页面的加载回调发送
+    let still_loaded = already_loaded
+        && state
+            .webviews
+            .lock()
+            .map_err(|err| format!("failed to lock webview map: {err}"))?
+            .get(&id)
+            .is_some_and(|entry| {
+                entry
+                    .load_state
+                    .lock()
+                    .is_ok_and(|load_state| *load_state == ChildWebviewLoadState::Ready)
+            });
+    if still_loaded {
+        let _ = window.emit(READY_EVENT, serde_json::json!({ "id": id }));
+    }
+    Ok(())
+}
+
 /// 更新子 WebView 边界
 #[tauri::command]
 pub(crate) async fn set_child_webview_bounds(
@@ -488,6 +1331,83 @@ pub(crate) async fn set_child_webview_bounds(
     Ok(())
 }
 
+/// 切换单个子 WebView 的代理（`None` 表示直连），在当前地址与边界上重建
+///
+/// 复用 `ensure_child_webview` 的重建流程，沿用最近一次创建时的其余配置，前端无需重新组装完整参数
+#[tauri::command]
+pub(crate) async fn set_child_webview_proxy(
+    window: Window,
+    state: State<'_, ChildWebviewManager>,
+    id: String,
+    proxy_url: Option<String>,
+) -> Result<(), String> {
+    if let Some(proxy) = proxy_url.as_deref() {
+        parse_proxy_url(proxy)?;
+    }
+
+    let (payload, visible) = {
+        let webviews = state
+            .webviews
+            .lock()
+            .map_err(|err| format!("failed to lock webview map: {err}"))?;
+        let entry = webviews
+            .get(&id)
+            .ok_or_else(|| format!("child webview not found: {id}"))?;
+        if entry.proxy_url == proxy_url {
+            return Ok(());
+        }
+
+        // 挂起中的 WebView 使用挂起前的真实地址
+        let current_url = match &entry.suspended_url {
+            Some(url) => url.clone(),
+            None => entry.webview.url().map_err(|err| err.to_string())?,
+        };
+        let scale_factor = window.scale_factor().map_err(|err| err.to_string())?;
+        let position = entry
+            .webview
+            .position()
+            .map_err(|err| err.to_string())?
+            .to_logical::<f64>(scale_factor);
+        let size = entry
+            .webview
+            .size()
+            .map_err(|err| err.to_string())?
+            .to_logical::<f64>(scale_factor);
+
+        let mut payload = entry.last_payload.clone();
+        payload.url = current_url.to_string();
+        payload.proxy_url = Some(proxy_url.clone());
+        payload.bounds = Some(BoundsPayload {
+            position_logical: PositionPayload {
+                x: position.x,
+                y: position.y,
+            },
+            size_logical: SizePayload {
+                width: size.width,
+                height: size.height,
+            },
+            _scale_factor: scale_factor,
+        });
+        (payload, entry.hidden_since.is_none())
+    };
+
+    log::info!("Switching proxy for child webview {}: {:?}", id, proxy_url);
+    ensure_child_webview(window, state.clone(), payload).await?;
+
+    // 重建后的 WebView 默认隐藏，原本可见时恢复显示
+    if visible {
+        let mut webviews = state
+            .webviews
+            .lock()
+            .map_err(|err| format!("failed to lock webview map: {err}"))?;
+        if let Some(entry) = webviews.get_mut(&id) {
+            entry.webview.show().map_err(|err| err.to_string())?;
+            entry.hidden_since = None;
+        }
+    }
+    Ok(())
+}
+
 /// 显示指定子 WebView
 #[tauri::command]
 pub(crate) async fn show_child_webview(
@@ -496,13 +1416,15 @@ pub(crate) async fn show_child_webview(
 ) -> Result<(), String> {
     log::debug!("Showing child webview: {}", payload.id);
 
-    let webviews = state
+    let mut webviews = state
         .webviews
         .lock()
         .map_err(|err| format!("failed to lock webview map: {err}"))?;
 
-    if let Some(entry) = webviews.get(&payload.id) {
+    if let Some(entry) = webviews.get_mut(&payload.id) {
+        resume_managed_webview(&payload.id, entry)?;
         entry.webview.show().map_err(|err| err.to_string())?;
+        entry.hidden_since = None;
         let _ = entry.webview.set_focus();
         log::debug!("Child webview shown: {}", payload.id);
     }
@@ -518,13 +1440,14 @@ pub(crate) async fn hide_child_webview(
 ) -> Result<(), String> {
     log::debug!("Hiding child webview: {}", payload.id);
 
-    let webviews = state
+    let mut webviews = state
         .webviews
         .lock()
         .map_err(|err| format!("failed to lock webview map: {err}"))?;
 
-    if let Some(entry) = webviews.get(&payload.id) {
+    if let Some(entry) = webviews.get_mut(&payload.id) {
         entry.webview.hide().map_err(|err| err.to_string())?;
+        entry.hidden_since.get_or_insert_with(Instant::now);
         log::debug!("Child webview hidden: {}", payload.id);
     }
 
@@ -546,6 +1469,9 @@ pub(crate) async fn close_child_webview(
 
     if let Some(entry) = webviews.remove(&payload.id) {
         entry.webview.close().map_err(|err| err.to_string())?;
+        if let Some(dir) = entry.incognito_data_dir {
+            remove_incognito_data_dir(dir);
+        }
         log::info!("Child webview closed: {}", payload.id);
     }
 
@@ -587,6 +1513,9 @@ pub(crate) async fn clear_child_webview_cache(
             .webview
             .close()
             .map_err(|err| format!("failed to close webview: {err}"))?;
+        if let Some(dir) = entry.incognito_data_dir {
+            remove_incognito_data_dir(dir);
+        }
 
         log::info!("Child webview cache cleared and closed: {}", payload.id);
     } else {
@@ -678,13 +1607,15 @@ pub(crate) async fn hide_all_child_webviews(
 ) -> Result<(), String> {
     log::debug!("Hiding all child webviews");
 
-    let webviews = state
+    let mut webviews = state
         .webviews
         .lock()
         .map_err(|err| format!("failed to lock webview map: {err}"))?;
 
-    for entry in webviews.values() {
+    let now = Instant::now();
+    for entry in webviews.values_mut() {
         let _ = entry.webview.hide();
+        entry.hidden_since.get_or_insert(now);
     }
 
     log::debug!("All child webviews hidden");
@@ -742,23 +1673,1774 @@ pub(crate) async fn evaluate_child_webview_script(
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::{should_open_in_default_browser, should_use_desktop_user_agent};
-    use tauri::Url;
+/// 将一段异步函数体包装为探测脚本：执行后附加请求编号，通过 `/probe-result` 导航一次性回传结果
+///
+/// 结果编码后超过 `PROBE_RESULT_MAX_ENCODED_LEN` 时改为回传错误，避免导航地址过长。
+fn build_probe_script(request_id: u64, body: &str) -> String {
+    format!(
+        r#"(async function() {{
+    const encode = (obj) => btoa(unescape(encodeURIComponent(JSON.stringify(obj))))
+        .replace(/\+/g, '-').replace(/\//g, '_').replace(/=/g, '');
+    let value = null;
+    let error = null;
+    try {{
+        value = await (async function() {{ {body} }})();
+    }} catch (e) {{
+        error = String((e && e.message) || e);
+    }}
+    let data = encode({{ __requestId: {request_id}, value, error }});
+    if (data.length > {PROBE_RESULT_MAX_ENCODED_LEN}) {{
+        data = encode({{ __requestId: {request_id}, value: null, error: 'probe_result_too_large' }});
+    }}
+    window.location.href = 'http://injection.localhost/probe-result?d=' + data;
+}})();"#
+    )
+}
 
-    #[test]
-    fn allows_well_known_schemes() {
-        let http = Url::parse("http://example.com").unwrap();
-        assert!(should_open_in_default_browser(&http));
+/// 在子 WebView 中执行探测脚本并等待其通过探测结果通道回传的结果
+async fn evaluate_child_webview_for_result(
+    app: &AppHandle,
+    state: &ChildWebviewManager,
+    id: &str,
+    body: &str,
+) -> Result<serde_json::Value, String> {
+    let request_id = NEXT_PROBE_REQUEST_ID.fetch_add(1, Ordering::Relaxed);
+    let (tx, rx) = tokio::sync::oneshot::channel::<Result<serde_json::Value, String>>();
+    let tx_shared = Arc::new(Mutex::new(Some(tx)));
+
+    let webview_id = id.to_string();
+    let tx_for_listener = tx_shared.clone();
+    let listener = app.listen(PROBE_RESULT_EVENT, move |event| {
+        let Ok(payload) = serde_json::from_str::<serde_json::Value>(event.payload()) else {
+            return;
+        };
+        if payload.get("id").and_then(|v| v.as_str()) != Some(webview_id.as_str()) {
+            return;
+        }
+        let Some(result) = payload.get("result") else {
+            return;
+        };
+        if result.get("__requestId").and_then(|v| v.as_u64()) != Some(request_id) {
+            return;
+        }
 
-        let https = Url::parse("https://example.com").unwrap();
-        assert!(should_open_in_default_browser(&https));
+        let outcome = match result.get("error").and_then(|v| v.as_str()) {
+            Some(error) => Err(error.to_string()),
+            None => Ok(result.get("value").cloned().unwrap_or_default()),
+        };
+        if let Ok(mut guard) = tx_for_listener.lock() {
+            if let Some(tx) = guard.take() {
+                let _ = tx.send(outcome);
+            }
+        }
+    });
+
+    let eval_result = {
+        let webviews = state
+            .webviews
+            .lock()
+            .map_err(|err| format!("failed to lock webview map: {err}"))?;
+        match webviews.get(id) {
+            Some(entry) => entry
+                .webview
+                .eval(build_probe_script(request_id, body))
+                .map_err(|err| format!("script evaluation failed: {err}")),
+            None => Err(format!("child webview not found: {id}")),
+        }
+    };
+
+    let result = match eval_result {
+        Ok(()) => match tokio::time::timeout(PROBE_RESULT_TIMEOUT, rx).await {
+            Ok(Ok(outcome)) => outcome,
+            Ok(Err(_)) => Err("probe result channel closed".to_string()),
+            Err(_) => Err(format!("timed out waiting for probe result: {id}")),
+        },
+        Err(err) => Err(err),
+    };
+
+    app.unlisten(listener);
+    result
+}
 
-        let mailto = Url::parse("mailto:user@example.com").unwrap();
-        assert!(should_open_in_default_browser(&mailto));
+/// 子 WebView 渲染引擎信息
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ChildWebviewInfo {
+    /// 渲染引擎名称（WebView2 / WKWebView / WebKitGTK）
+    engine: &'static str,
+    engine_version: Option<String>,
+    user_agent: Option<String>,
+    /// WebGL 渲染器描述（如 GPU 型号或 SwiftShader）
+    renderer: Option<String>,
+    /// 是否启用硬件加速；无法判断时为 `None`
+    hardware_acceleration: Option<bool>,
+}
 
-        let tel = Url::parse("tel:123456").unwrap();
+const WEBVIEW_ENGINE: &str = if cfg!(target_os = "windows") {
+    "WebView2"
+} else if cfg!(target_os = "macos") {
+    "WKWebView"
+} else {
+    "WebKitGTK"
+};
+
+/// 通过 WebGL 探测渲染器与硬件加速状态的脚本函数体
+const RENDERER_PROBE_BODY: &str = r#"
+    const info = { userAgent: navigator.userAgent, renderer: null, hardwareAcceleration: null };
+    try {
+        const canvas = document.createElement('canvas');
+        const gl = canvas.getContext('webgl') || canvas.getContext('experimental-webgl');
+        if (gl) {
+            const ext = gl.getExtension('WEBGL_debug_renderer_info');
+            info.renderer = String(gl.getParameter(ext ? ext.UNMASKED_RENDERER_WEBGL : gl.RENDERER));
+            info.hardwareAcceleration = !/swiftshader|llvmpipe|softpipe|software|basic render/i.test(info.renderer);
+        } else {
+            info.hardwareAcceleration = false;
+        }
+    } catch (e) {}
+    return info;
+"#;
+
+/// 获取子 WebView 的渲染引擎版本与硬件加速状态，用于排查页面空白/卡顿问题
+#[tauri::command]
+pub(crate) async fn get_child_webview_info(
+    app: AppHandle,
+    state: State<'_, ChildWebviewManager>,
+    id: String,
+) -> Result<ChildWebviewInfo, String> {
+    let engine_version = tauri::webview_version().ok();
+
+    let probe =
+        match evaluate_child_webview_for_result(&app, &state, &id, RENDERER_PROBE_BODY).await {
+            Ok(value) => value,
+            Err(err) if err.starts_with("child webview not found") => return Err(err),
+            Err(err) => {
+                log::warn!("Renderer probe failed for child webview {}: {}", id, err);
+                serde_json::Value::Null
+            }
+        };
+
+    Ok(ChildWebviewInfo {
+        engine: WEBVIEW_ENGINE,
+        engine_version,
+        user_agent: probe
+            .get("userAgent")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+        renderer: probe
+            .get("renderer")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+        hardware_acceleration: probe.get("hardwareAcceleration").and_then(|v| v.as_bool()),
+    })
+}
+
+/// 子 WebView 调试信息
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ChildWebviewDevtoolsInfo {
+    id: String,
+    /// 当前构建是否允许调试（仅 debug 构建）
+    enabled: bool,
+    /// 开发者工具是否已打开
+    opened: bool,
+    /// WebView2 的 CDP 调试地址（需通过 `WEBVIEW2_ADDITIONAL_BROWSER_ARGUMENTS`
+    /// 传入 `--remote-debugging-port`），其他平台为 `None`
+    cdp_endpoint: Option<String>,
+}
+
+/// 从 WebView2 附加启动参数中解析远程调试端口
+#[cfg(any(target_os = "windows", test))]
+fn remote_debugging_port(browser_args: &str) -> Option<u16> {
+    browser_args
+        .split_whitespace()
+        .find_map(|arg| arg.strip_prefix("--remote-debugging-port="))
+        .and_then(|port| port.trim_matches('"').parse().ok())
+        .filter(|port| *port != 0)
+}
+
+/// 获取子 WebView 的调试信息，debug 构建下同时打开其开发者工具
+///
+/// release 构建不暴露任何调试能力，仅返回 `enabled: false`
+#[tauri::command]
+pub(crate) async fn get_child_webview_devtools_info(
+    state: State<'_, ChildWebviewManager>,
+    id: String,
+) -> Result<ChildWebviewDevtoolsInfo, String> {
+    let webviews = state
+        .webviews
+        .lock()
+        .map_err(|err| format!("failed to lock webview map: {err}"))?;
+    let entry = webviews
+        .get(&id)
+        .ok_or_else(|| format!("child webview not found: {id}"))?;
+
+    #[cfg(debug_assertions)]
+    let (enabled, opened) = {
+        if !entry.webview.is_devtools_open() {
+            log::info!("Opening devtools for child webview {}", id);
+            entry.webview.open_devtools();
+        }
+        (true, entry.webview.is_devtools_open())
+    };
+    #[cfg(not(debug_assertions))]
+    let (enabled, opened) = {
+        let _ = entry;
+        (false, false)
+    };
+
+    #[cfg(target_os = "windows")]
+    let cdp_endpoint = enabled
+        .then(|| std::env::var("WEBVIEW2_ADDITIONAL_BROWSER_ARGUMENTS").ok())
+        .flatten()
+        .and_then(|args| remote_debugging_port(&args))
+        .map(|port| format!("http://127.0.0.1:{port}/json"));
+    #[cfg(not(target_os = "windows"))]
+    let cdp_endpoint = None;
+
+    Ok(ChildWebviewDevtoolsInfo {
+        id,
+        enabled,
+        opened,
+        cdp_endpoint,
+    })
+}
+
+/// 单个子 WebView 的进程资源占用
+///
+/// 仅 WebView2 能取得 WebView 对应的浏览器进程；其他平台各字段均为 `None`。
+/// 同一数据目录的 WebView 共享浏览器进程，此时多个 WebView 报告相同的数值。
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ChildWebviewResourceUsage {
+    id: String,
+    browser_process_id: Option<u32>,
+    /// 浏览器进程及其渲染等子进程的常驻内存合计
+    memory_bytes: Option<u64>,
+    /// 浏览器进程及其子进程累计的 CPU 时间
+    cpu_time_ms: Option<u64>,
+}
+
+/// 子 WebView 资源占用汇总
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ChildWebviewsResourceUsage {
+    webviews: Vec<ChildWebviewResourceUsage>,
+    /// 应用进程树（应用主进程在前）
+    processes: Vec<ProcessUsage>,
+    total_memory_bytes: u64,
+    total_cpu_time_ms: u64,
+    /// 进程树是否包含 WebView 渲染进程（macOS 上为 false）
+    includes_webview_processes: bool,
+}
+
+/// 读取 WebView2 浏览器进程号
+#[cfg(target_os = "windows")]
+async fn child_webview_browser_process_id(webview: &Webview) -> Option<u32> {
+    let (tx, rx) = tokio::sync::oneshot::channel::<Option<u32>>();
+    webview
+        .with_webview(move |platform| {
+            let mut pid = 0u32;
+            let result = unsafe {
+                platform
+                    .controller()
+                    .CoreWebView2()
+                    .and_then(|core| core.BrowserProcessId(&mut pid))
+            };
+            let _ = tx.send(result.ok().map(|()| pid).filter(|pid| *pid != 0));
+        })
+        .ok()?;
+    tokio::time::timeout(PROBE_RESULT_TIMEOUT, rx)
+        .await
+        .ok()?
+        .ok()?
+}
+
+/// 其他平台的 WebView 不暴露其进程号
+#[cfg(not(target_os = "windows"))]
+async fn child_webview_browser_process_id(_webview: &Webview) -> Option<u32> {
+    None
+}
+
+/// 获取子 WebView 的资源占用（尽力而为）
+///
+/// 按进程树给出应用整体的内存与 CPU 时间合计；WebView2 上另按每个 WebView 的浏览器进程
+/// 统计其进程子树，其他平台无法把进程归属到单个 WebView，对应字段为 `None`
+#[tauri::command]
+pub(crate) async fn get_child_webviews_resource_usage(
+    state: State<'_, ChildWebviewManager>,
+) -> Result<ChildWebviewsResourceUsage, String> {
+    let entries: Vec<(String, Webview)> = {
+        let webviews = state
+            .webviews
+            .lock()
+            .map_err(|err| format!("failed to lock webview map: {err}"))?;
+        webviews
+            .iter()
+            .map(|(id, entry)| (id.clone(), entry.webview.clone()))
+            .collect()
+    };
+
+    let mut browser_process_ids = Vec::with_capacity(entries.len());
+    for (id, webview) in entries {
+        browser_process_ids.push((id, child_webview_browser_process_id(&webview).await));
+    }
+
+    let processes = tauri::async_runtime::spawn_blocking(app_process_tree_usage)
+        .await
+        .map_err(|e| format!("process usage task failed: {}", e))??;
+    let (total_memory_bytes, total_cpu_time_ms) = total_usage(&processes);
+
+    let webviews = browser_process_ids
+        .into_iter()
+        .map(|(id, browser_process_id)| {
+            let usage = browser_process_id.and_then(|pid| subtree_usage(&processes, pid));
+            ChildWebviewResourceUsage {
+                id,
+                browser_process_id,
+                memory_bytes: usage.map(|(memory, _)| memory),
+                cpu_time_ms: usage.map(|(_, cpu)| cpu),
+            }
+        })
+        .collect();
+
+    Ok(ChildWebviewsResourceUsage {
+        webviews,
+        processes,
+        total_memory_bytes,
+        total_cpu_time_ms,
+        includes_webview_processes: PROCESS_TREE_INCLUDES_WEBVIEWS,
+    })
+}
+
+/// 设置允许通过系统默认程序打开的 URL Scheme 列表（空列表恢复默认）
+#[tauri::command]
+pub(crate) async fn set_external_url_schemes(
+    app: AppHandle,
+    schemes: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let normalized = normalize_external_url_schemes(&schemes);
+    settings::save_setting(&app, SETTING_EXTERNAL_URL_SCHEMES, &normalized)?;
+    log::info!("External URL schemes updated: {:?}", normalized);
+    replace_external_url_schemes(normalized.clone());
+    Ok(normalized)
+}
+
+/// 获取当前生效的外部 URL Scheme 列表
+#[tauri::command]
+pub(crate) async fn get_external_url_schemes() -> Result<Vec<String>, String> {
+    external_url_schemes()
+        .read()
+        .map(|schemes| schemes.clone())
+        .map_err(|err| err.to_string())
+}
+
+/// 使用系统默认程序打开外部链接
+///
+/// 与子 WebView 新窗口处理共用同一套 Scheme 白名单，不支持的 Scheme 直接返回错误。
+#[tauri::command]
+pub(crate) async fn open_external_url(url: String) -> Result<(), String> {
+    let parsed = Url::parse(url.trim()).map_err(|err| format!("Invalid URL: {}", err))?;
+    if !should_open_in_default_browser(&parsed) {
+        return Err(format!("Unsupported URL scheme: {}", parsed.scheme()));
+    }
+
+    open_url(parsed.as_str(), None::<&str>).map_err(|err| {
+        log::error!("Failed to open external URL {}: {}", parsed, err);
+        err.to_string()
+    })?;
+    log::info!("Opened external URL in system browser: {}", parsed);
+    Ok(())
+}
+
+fn set_load_state(state: &Mutex<ChildWebviewLoadState>, next: ChildWebviewLoadState) {
+    match state.lock() {
+        Ok(mut guard) => *guard = next,
+        Err(poisoned) => *poisoned.into_inner() = next,
+    }
+}
+
+/// 根据加载完成时的 URL 识别浏览器内置错误页
+///
+/// 页面加载回调不携带错误信息，各引擎加载失败时会跳转到内置错误页
+/// （如 `chrome-error://chromewebdata/`、`about:neterror`），据此判断失败。
+fn detect_load_error(url: &Url) -> Option<String> {
+    match url.scheme() {
+        "chrome-error" | "edge-error" => Some(format!("network error page: {url}")),
+        "about" if url.path().starts_with("neterror") || url.path().starts_with("certerror") => {
+            Some(format!("network error page: {url}"))
+        }
+        _ => None,
+    }
+}
+
+/// 日志中需要隐藏取值的请求头关键字
+const SENSITIVE_HEADER_KEYWORDS: [&str; 6] =
+    ["auth", "cookie", "token", "secret", "key", "session"];
+
+fn is_valid_header_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(ch))
+}
+
+/// 生成日志安全的请求头描述，敏感头的取值替换为 `***`
+fn redact_headers_for_log(headers: &HashMap<String, String>) -> Vec<String> {
+    let mut entries: Vec<String> = headers
+        .iter()
+        .map(|(name, value)| {
+            let lower = name.to_ascii_lowercase();
+            if SENSITIVE_HEADER_KEYWORDS
+                .iter()
+                .any(|keyword| lower.contains(keyword))
+            {
+                format!("{name}: ***")
+            } else {
+                format!("{name}: {value}")
+            }
+        })
+        .collect();
+    entries.sort();
+    entries
+}
+
+/// 合法的 Referrer-Policy 取值
+const REFERRER_POLICIES: &[&str] = &[
+    "no-referrer",
+    "no-referrer-when-downgrade",
+    "origin",
+    "origin-when-cross-origin",
+    "same-origin",
+    "strict-origin",
+    "strict-origin-when-cross-origin",
+    "unsafe-url",
+];
+
+/// 生成设置页面 Referrer 策略的初始化脚本
+///
+/// 通过 `<meta name="referrer">` 作用于页面内的资源与导航请求，并为未显式指定策略的
+/// `fetch` 调用补充 `referrerPolicy`。顶层文档自身的首个请求不受影响。
+fn build_referrer_policy_script(policy: &str) -> Result<String, String> {
+    let policy = policy.trim().to_ascii_lowercase();
+    if !REFERRER_POLICIES.contains(&policy.as_str()) {
+        return Err(format!("Unsupported referrer policy: {policy}"));
+    }
+
+    Ok(format!(
+        r#"(function() {{
+    const POLICY = "{policy}";
+    const apply = () => {{
+        const root = document.head || document.documentElement;
+        if (!root) return false;
+        let meta = document.querySelector('meta[name="referrer"][data-ai-ask]');
+        if (!meta) {{
+            meta = document.createElement('meta');
+            meta.name = 'referrer';
+            meta.setAttribute('data-ai-ask', '');
+            root.prepend(meta);
+        }}
+        meta.content = POLICY;
+        return true;
+    }};
+    if (!apply()) document.addEventListener('readystatechange', apply, {{ once: true }});
+    const originalFetch = window.fetch;
+    window.fetch = function(input, init) {{
+        if (input instanceof Request || (init && init.referrerPolicy)) {{
+            return originalFetch.call(this, input, init);
+        }}
+        return originalFetch.call(this, input, Object.assign({{ referrerPolicy: POLICY }}, init));
+    }};
+}})();"#
+    ))
+}
+
+/// 在 WebView2 上拦截与初始页面同源的文档请求并附加请求头
+///
+/// 初始化脚本只能覆盖页面发起的 fetch/XHR，文档导航需要通过 `WebResourceRequested` 事件改写。
+/// 过滤规则限定为初始页面的源，与脚本的同源限制保持一致。
+#[cfg(target_os = "windows")]
+fn install_document_headers_hook(webview: &Webview, url: &str, headers: &HashMap<String, String>) {
+    use webview2_com::Microsoft::Web::WebView2::Win32::COREWEBVIEW2_WEB_RESOURCE_CONTEXT_DOCUMENT;
+    use webview2_com::{CoTaskMemPWSTR, WebResourceRequestedEventHandler};
+
+    let headers: Vec<(String, String)> = headers
+        .iter()
+        .filter(|(name, _)| is_valid_header_name(name))
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect();
+    if headers.is_empty() {
+        return;
+    }
+    let Some(origin) = Url::parse(url)
+        .ok()
+        .map(|url| url.origin())
+        .filter(|origin| origin.is_tuple())
+        .map(|origin| origin.ascii_serialization())
+    else {
+        return;
+    };
+
+    let label = webview.label().to_string();
+    let result = webview.with_webview(move |platform| {
+        let filter = CoTaskMemPWSTR::from(format!("{origin}/*").as_str());
+        let handler = WebResourceRequestedEventHandler::create(Box::new(move |_, args| {
+            let Some(args) = args else {
+                return Ok(());
+            };
+            let request_headers = unsafe { args.Request()?.Headers()? };
+            for (name, value) in &headers {
+                let name = CoTaskMemPWSTR::from(name.as_str());
+                let value = CoTaskMemPWSTR::from(value.as_str());
+                unsafe {
+                    request_headers
+                        .SetHeader(*name.as_ref().as_pcwstr(), *value.as_ref().as_pcwstr())?;
+                }
+            }
+            Ok(())
+        }));
+        let mut token = 0i64;
+        let installed = unsafe {
+            platform.controller().CoreWebView2().and_then(|core| {
+                core.AddWebResourceRequestedFilter(
+                    *filter.as_ref().as_pcwstr(),
+                    COREWEBVIEW2_WEB_RESOURCE_CONTEXT_DOCUMENT,
+                )?;
+                core.add_WebResourceRequested(&handler, &mut token)
+            })
+        };
+        if let Err(err) = installed {
+            log::warn!(
+                "Failed to install document headers hook for {}: {}",
+                label,
+                err
+            );
+        }
+    });
+    if let Err(err) = result {
+        log::warn!(
+            "Failed to access webview for document headers hook: {}",
+            err
+        );
+    }
+}
+
+/// macOS 与 Linux 的 WebView 不支持为页面请求统一注入请求头，
+/// 因此通过初始化脚本改写 `fetch` 与 `XMLHttpRequest`，仅对同源请求附加请求头，
+/// 避免凭据泄露到第三方域名。
+fn build_extra_headers_script(headers: &HashMap<String, String>) -> Option<String> {
+    let valid: serde_json::Map<String, serde_json::Value> = headers
+        .iter()
+        .filter(|(name, _)| {
+            let valid = is_valid_header_name(name);
+            if !valid {
+                log::warn!("Ignoring invalid extra header name: {:?}", name);
+            }
+            valid
+        })
+        .map(|(name, value)| (name.clone(), serde_json::Value::from(value.as_str())))
+        .collect();
+    if valid.is_empty() {
+        return None;
+    }
+
+    let headers_json = serde_json::Value::Object(valid);
+    Some(format!(
+        r#"(function() {{
+    const EXTRA_HEADERS = {headers_json};
+    const isSameOrigin = (input) => {{
+        try {{ return new URL(input, window.location.href).origin === window.location.origin; }}
+        catch (_) {{ return false; }}
+    }};
+    const originalFetch = window.fetch;
+    window.fetch = function(input, init) {{
+        const url = (input && input.url) || String(input);
+        if (!isSameOrigin(url)) return originalFetch.call(this, input, init);
+        const options = Object.assign({{}}, init);
+        const headers = new Headers(options.headers || (input && input.headers) || undefined);
+        for (const [name, value] of Object.entries(EXTRA_HEADERS)) {{
+            if (!headers.has(name)) headers.set(name, value);
+        }}
+        options.headers = headers;
+        return originalFetch.call(this, input, options);
+    }};
+    const originalOpen = XMLHttpRequest.prototype.open;
+    const originalSend = XMLHttpRequest.prototype.send;
+    XMLHttpRequest.prototype.open = function(method, url) {{
+        this.__aiAskSameOrigin = isSameOrigin(url);
+        return originalOpen.apply(this, arguments);
+    }};
+    XMLHttpRequest.prototype.send = function() {{
+        if (this.__aiAskSameOrigin) {{
+            for (const [name, value] of Object.entries(EXTRA_HEADERS)) {{
+                try {{ this.setRequestHeader(name, value); }} catch (_) {{}}
+            }}
+        }}
+        return originalSend.apply(this, arguments);
+    }};
+}})();"#
+    ))
+}
+
+/// 第 `attempt` 次重试的等待时长：基准时长按次数翻倍，上限 30 秒
+fn load_retry_delay(backoff_ms: u64, attempt: u32) -> Duration {
+    let factor = 1u64 << attempt.saturating_sub(1).min(16);
+    Duration::from_millis(backoff_ms.saturating_mul(factor)).min(MAX_LOAD_RETRY_DELAY)
+}
+
+/// 加载失败时按退避策略重新加载目标地址；重试次数耗尽返回 `false`
+fn schedule_load_retry(webview: &Webview, webview_id: &str, retry: &Mutex<LoadRetryState>) -> bool {
+    let (attempt, delay, target) = {
+        let Ok(mut state) = retry.lock() else {
+            return false;
+        };
+        let Some(target) = state.target_url.clone() else {
+            return false;
+        };
+        if state.attempts >= state.max_retries {
+            log::warn!(
+                "Child webview {} load retries exhausted after {} attempts",
+                webview_id,
+                state.attempts
+            );
+            return false;
+        }
+        state.attempts += 1;
+        (
+            state.attempts,
+            load_retry_delay(state.backoff_ms, state.attempts),
+            target,
+        )
+    };
+
+    log::info!(
+        "Retrying child webview {} load in {}ms (attempt {}): {}",
+        webview_id,
+        delay.as_millis(),
+        attempt,
+        target
+    );
+    let webview = webview.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(delay).await;
+        if let Err(err) = webview.navigate(target) {
+            log::error!("Failed to retry child webview load: {}", err);
+        }
+    });
+    true
+}
+
+/// 查询子 WebView 当前的页面加载状态
+#[tauri::command]
+pub(crate) async fn get_child_webview_load_state(
+    state: State<'_, ChildWebviewManager>,
+    id: String,
+) -> Result<ChildWebviewLoadState, String> {
+    let webviews = state
+        .webviews
+        .lock()
+        .map_err(|err| format!("failed to lock webview map: {err}"))?;
+    let entry = webviews
+        .get(&id)
+        .ok_or_else(|| format!("child webview not found: {id}"))?;
+    let load_state = entry
+        .load_state
+        .lock()
+        .map(|guard| guard.clone())
+        .map_err(|err| format!("failed to lock load state: {err}"))?;
+    Ok(load_state)
+}
+
+/// 子 WebView 概要信息
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ChildWebviewSummary {
+    id: String,
+    /// 当前地址；挂起时为挂起前的真实地址
+    url: Option<String>,
+    /// 最近一次的页面标题，页面尚未设置标题时为 `None`
+    title: Option<String>,
+    suspended: bool,
+}
+
+/// 列出全部子 WebView 及其地址与页面标题，供宿主渲染标签页
+#[tauri::command]
+pub(crate) async fn list_child_webviews(
+    state: State<'_, ChildWebviewManager>,
+) -> Result<Vec<ChildWebviewSummary>, String> {
+    let webviews = state
+        .webviews
+        .lock()
+        .map_err(|err| format!("failed to lock webview map: {err}"))?;
+    let mut summaries: Vec<ChildWebviewSummary> = webviews
+        .iter()
+        .map(|(id, entry)| ChildWebviewSummary {
+            id: id.clone(),
+            url: entry
+                .suspended_url
+                .clone()
+                .or_else(|| entry.webview.url().ok())
+                .map(String::from),
+            title: entry.title.lock().ok().and_then(|title| title.clone()),
+            suspended: entry.suspended_url.is_some(),
+        })
+        .collect();
+    summaries.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(summaries)
+}
+
+/// 通过 DevTools 协议 `Page.reload` 忽略缓存刷新
+#[cfg(target_os = "windows")]
+fn reload_ignoring_cache(webview: &Webview) -> Result<(), String> {
+    webview
+        .with_webview(
+            |platform| match unsafe { platform.controller().CoreWebView2() } {
+                Ok(core) => call_devtools_method(
+                    &core,
+                    "Page.reload",
+                    r#"{"ignoreCache":true}"#,
+                    |result| {
+                        if let Err(err) = result {
+                            log::warn!("Hard reload failed: {}", err);
+                        }
+                    },
+                ),
+                Err(err) => log::warn!("Hard reload failed to access WebView2: {}", err),
+            },
+        )
+        .map_err(|err| format!("failed to access webview: {err}"))
+}
+
+/// 通过 `-[WKWebView reloadFromOrigin]` 忽略缓存刷新
+#[cfg(target_os = "macos")]
+fn reload_ignoring_cache(webview: &Webview) -> Result<(), String> {
+    webview
+        .with_webview(|platform| {
+            use objc2::msg_send;
+            use objc2::runtime::AnyObject;
+
+            let wk_webview = platform.inner() as *mut AnyObject;
+            let _: *mut AnyObject = unsafe { msg_send![wk_webview, reloadFromOrigin] };
+        })
+        .map_err(|err| format!("failed to access webview: {err}"))
+}
+
+/// 通过 WebKitGTK 的 `reload_bypass_cache` 忽略缓存刷新
+#[cfg(target_os = "linux")]
+fn reload_ignoring_cache(webview: &Webview) -> Result<(), String> {
+    webview
+        .with_webview(|platform| {
+            use webkit2gtk::WebViewExt;
+
+            platform.inner().reload_bypass_cache();
+        })
+        .map_err(|err| format!("failed to access webview: {err}"))
+}
+
+/// 其他平台没有忽略缓存的刷新接口
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+fn reload_ignoring_cache(_webview: &Webview) -> Result<(), String> {
+    Err(format!(
+        "hard reload is not supported on {WEBVIEW_ENGINE} yet"
+    ))
+}
+
+/// 忽略缓存强制刷新子 WebView
+///
+/// 使用各平台 WebView 自带的忽略缓存刷新接口重新请求当前页面，
+/// 用于 AI 站点发布异常版本后被 WebView 缓存的场景。
+#[tauri::command]
+pub(crate) async fn child_webview_hard_reload(
+    state: State<'_, ChildWebviewManager>,
+    id: String,
+) -> Result<(), String> {
+    let webviews = state
+        .webviews
+        .lock()
+        .map_err(|err| format!("failed to lock webview map: {err}"))?;
+    let entry = webviews
+        .get(&id)
+        .ok_or_else(|| format!("child webview not found: {id}"))?;
+    if entry.suspended_url.is_some() {
+        return Err(format!("child webview is suspended: {id}"));
+    }
+
+    log::info!(
+        "Hard reloading child webview {}: {}",
+        id,
+        entry.webview.url().map(String::from).unwrap_or_default()
+    );
+    reload_ignoring_cache(&entry.webview).map_err(|err| format!("hard reload failed: {err}"))
+}
+
+const SUSPENDED_EVENT: &str = "child-webview:suspended";
+const RESUMED_EVENT: &str = "child-webview:resumed";
+const SETTING_IDLE_SUSPEND_SECS: &str = "childWebviewIdleSuspendSecs";
+/// 空闲自动挂起的最短超时，避免频繁切换面板时反复重新加载页面
+const MIN_IDLE_SUSPEND_SECS: u64 = 60;
+const IDLE_SUSPEND_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 空闲自动挂起超时（秒），0 表示关闭
+static IDLE_SUSPEND_SECS: AtomicU64 = AtomicU64::new(0);
+
+/// 将子 WebView 导航到 `about:blank` 释放页面内存，并记住真实地址
+fn suspend_managed_webview(id: &str, entry: &mut ManagedWebview) -> Result<bool, String> {
+    if entry.suspended_url.is_some() {
+        return Ok(false);
+    }
+    let current_url = entry.webview.url().map_err(|err| err.to_string())?;
+    let blank = Url::parse("about:blank").map_err(|err| err.to_string())?;
+    // 先置位再导航，确保 about:blank 的加载回调能看到挂起状态
+    entry.suspended.store(true, Ordering::SeqCst);
+    if let Err(err) = entry.webview.navigate(blank) {
+        entry.suspended.store(false, Ordering::SeqCst);
+        return Err(format!("failed to suspend child webview: {err}"));
+    }
+
+    log::info!("Child webview suspended: {} ({})", id, current_url);
+    let _ = entry.webview.app_handle().emit(
+        SUSPENDED_EVENT,
+        serde_json::json!({ "id": id, "url": current_url.as_str() }),
+    );
+    entry.suspended_url = Some(current_url);
+    Ok(true)
+}
+
+/// 恢复已挂起的子 WebView（导航回挂起前的地址）
+fn resume_managed_webview(id: &str, entry: &mut ManagedWebview) -> Result<bool, String> {
+    let Some(url) = entry.suspended_url.take() else {
+        return Ok(false);
+    };
+    entry.suspended.store(false, Ordering::SeqCst);
+    if let Err(err) = entry.webview.navigate(url.clone()) {
+        entry.suspended_url = Some(url);
+        entry.suspended.store(true, Ordering::SeqCst);
+        return Err(format!("failed to resume child webview: {err}"));
+    }
+
+    log::info!("Child webview resumed: {} ({})", id, url);
+    let _ = entry.webview.app_handle().emit(
+        RESUMED_EVENT,
+        serde_json::json!({ "id": id, "url": url.as_str() }),
+    );
+    Ok(true)
+}
+
+/// 判断隐藏中的子 WebView 是否已空闲超过自动挂起超时
+fn idle_suspend_due(
+    hidden_since: Option<Instant>,
+    timeout: Option<Duration>,
+    now: Instant,
+) -> bool {
+    match (hidden_since, timeout) {
+        (Some(since), Some(timeout)) => now.saturating_duration_since(since) >= timeout,
+        _ => false,
+    }
+}
+
+fn idle_suspend_timeout() -> Option<Duration> {
+    match IDLE_SUSPEND_SECS.load(Ordering::Relaxed) {
+        0 => None,
+        secs => Some(Duration::from_secs(secs)),
+    }
+}
+
+/// 统一设置全部子 WebView 的缩放比例，并作为之后新建 WebView 的默认值持久化
+///
+/// 比例会被限制在 0.25 ~ 5.0 之间，返回实际生效的比例
+#[tauri::command]
+pub(crate) async fn set_all_child_webviews_zoom(
+    app: AppHandle,
+    state: State<'_, ChildWebviewManager>,
+    factor: f64,
+) -> Result<f64, String> {
+    let factor = clamp_zoom_factor(factor)?;
+    settings::save_setting(&app, SETTING_CHILD_WEBVIEW_ZOOM, &factor)?;
+    *state
+        .default_zoom
+        .lock()
+        .map_err(|err| format!("failed to lock zoom state: {err}"))? = Some(factor);
+
+    let webviews = state
+        .webviews
+        .lock()
+        .map_err(|err| format!("failed to lock webview map: {err}"))?;
+    for (id, entry) in webviews.iter() {
+        if let Err(err) = entry.webview.set_zoom(factor) {
+            log::warn!("Failed to set zoom for child webview {}: {}", id, err);
+        }
+    }
+    log::info!(
+        "Applied zoom {} to {} child webview(s)",
+        factor,
+        webviews.len()
+    );
+    Ok(factor)
+}
+
+/// 挂起子 WebView 以节省内存（返回是否发生了状态变化）
+#[tauri::command]
+pub(crate) async fn suspend_child_webview(
+    state: State<'_, ChildWebviewManager>,
+    id: String,
+) -> Result<bool, String> {
+    let mut webviews = state
+        .webviews
+        .lock()
+        .map_err(|err| format!("failed to lock webview map: {err}"))?;
+    let entry = webviews
+        .get_mut(&id)
+        .ok_or_else(|| format!("child webview not found: {id}"))?;
+    suspend_managed_webview(&id, entry)
+}
+
+/// 恢复已挂起的子 WebView（返回是否发生了状态变化）
+#[tauri::command]
+pub(crate) async fn resume_child_webview(
+    state: State<'_, ChildWebviewManager>,
+    id: String,
+) -> Result<bool, String> {
+    let mut webviews = state
+        .webviews
+        .lock()
+        .map_err(|err| format!("failed to lock webview map: {err}"))?;
+    let entry = webviews
+        .get_mut(&id)
+        .ok_or_else(|| format!("child webview not found: {id}"))?;
+    resume_managed_webview(&id, entry)
+}
+
+/// 查询子 WebView 是否已挂起
+#[tauri::command]
+pub(crate) async fn is_child_webview_suspended(
+    state: State<'_, ChildWebviewManager>,
+    id: String,
+) -> Result<bool, String> {
+    let webviews = state
+        .webviews
+        .lock()
+        .map_err(|err| format!("failed to lock webview map: {err}"))?;
+    webviews
+        .get(&id)
+        .map(|entry| entry.suspended_url.is_some())
+        .ok_or_else(|| format!("child webview not found: {id}"))
+}
+
+/// 设置隐藏子 WebView 的空闲自动挂起超时（`None` 或 0 表示关闭）
+#[tauri::command]
+pub(crate) async fn set_child_webview_idle_suspend(
+    app: AppHandle,
+    timeout_secs: Option<u64>,
+) -> Result<(), String> {
+    let secs = timeout_secs.unwrap_or(0);
+    if secs != 0 && secs < MIN_IDLE_SUSPEND_SECS {
+        return Err(format!(
+            "idle suspend timeout must be at least {MIN_IDLE_SUSPEND_SECS} seconds"
+        ));
+    }
+    settings::save_setting(&app, SETTING_IDLE_SUSPEND_SECS, &secs)?;
+    IDLE_SUSPEND_SECS.store(secs, Ordering::Relaxed);
+    log::info!("Child webview idle suspend timeout set to {}s", secs);
+    Ok(())
+}
+
+/// 获取空闲自动挂起超时（秒），关闭时返回 `None`
+#[tauri::command]
+pub(crate) async fn get_child_webview_idle_suspend() -> Result<Option<u64>, String> {
+    Ok(idle_suspend_timeout().map(|timeout| timeout.as_secs()))
+}
+
+/// 启动空闲自动挂起检查任务：定期挂起隐藏时间超过超时的子 WebView
+pub(crate) fn start_idle_suspend_monitor(app: &AppHandle) {
+    if let Some(secs) = settings::load_setting::<u64>(app, SETTING_IDLE_SUSPEND_SECS) {
+        IDLE_SUSPEND_SECS.store(secs, Ordering::Relaxed);
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(IDLE_SUSPEND_CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            let timeout = idle_suspend_timeout();
+            if timeout.is_none() {
+                continue;
+            }
+
+            let state = app.state::<ChildWebviewManager>();
+            let Ok(mut webviews) = state.webviews.lock() else {
+                continue;
+            };
+            let now = Instant::now();
+            for (id, entry) in webviews.iter_mut() {
+                if entry.suspended_url.is_none()
+                    && idle_suspend_due(entry.hidden_since, timeout, now)
+                {
+                    if let Err(err) = suspend_managed_webview(id, entry) {
+                        log::warn!("Idle suspend failed for child webview {}: {}", id, err);
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// 整页截图保存目录（位于应用缓存目录下）
+const SCREENSHOTS_DIR_NAME: &str = "screenshots";
+/// 整页截图文件的保留时长，超过后在启动或下一次截图时删除
+const SCREENSHOT_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+/// 截图目录最多保留的文件数（不含本次截图）
+const SCREENSHOT_MAX_FILES: usize = 20;
+/// 等待引擎完成整页截图的超时时间（长页面渲染较慢）
+const FULL_PAGE_CAPTURE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 整页截图结果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FullPageScreenshot {
+    /// PNG 文件路径（临时文件，超过保留时长或数量上限后自动删除，前端需及时另存）
+    path: String,
+    width: u32,
+    height: u32,
+    size_bytes: u64,
+}
+
+/// 从 PNG 文件头（IHDR 块）读取宽高
+fn png_dimensions(png: &[u8]) -> Option<(u32, u32)> {
+    const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+    if png.len() < 24 || png[..8] != SIGNATURE || &png[12..16] != b"IHDR" {
+        return None;
+    }
+    let width = u32::from_be_bytes(png[16..20].try_into().ok()?);
+    let height = u32::from_be_bytes(png[20..24].try_into().ok()?);
+    Some((width, height))
+}
+
+/// 选出需要删除的截图：超过保留时长的，以及按修改时间从新到旧超出数量上限的
+fn screenshots_to_prune(mut files: Vec<(PathBuf, SystemTime)>, now: SystemTime) -> Vec<PathBuf> {
+    files.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+    files
+        .into_iter()
+        .enumerate()
+        .filter(|(index, (_, modified))| {
+            *index >= SCREENSHOT_MAX_FILES
+                || now
+                    .duration_since(*modified)
+                    .is_ok_and(|age| age > SCREENSHOT_MAX_AGE)
+        })
+        .map(|(_, (path, _))| path)
+        .collect()
+}
+
+/// 删除截图目录中过期或超出数量上限的 PNG 文件
+fn prune_screenshots(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let files: Vec<(PathBuf, SystemTime)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("png") {
+                return None;
+            }
+            let metadata = entry.metadata().ok().filter(|meta| meta.is_file())?;
+            Some((path, metadata.modified().ok()?))
+        })
+        .collect();
+    for path in screenshots_to_prune(files, SystemTime::now()) {
+        if let Err(err) = fs::remove_file(&path) {
+            log::warn!("Failed to remove screenshot {}: {}", path.display(), err);
+        }
+    }
+}
+
+/// 启动时清理过期的整页截图
+pub(crate) fn cleanup_screenshots(app: &AppHandle) {
+    if let Ok(dir) = app_paths::cache_dir(app) {
+        prune_screenshots(&dir.join(SCREENSHOTS_DIR_NAME));
+    }
+}
+
+/// 将 WebView ID 转换为安全的文件名片段
+fn screenshot_file_stem(id: &str) -> String {
+    let stem: String = id
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if stem.is_empty() {
+        "webview".to_string()
+    } else {
+        stem
+    }
+}
+
+/// 使用 WebKitGTK 的整文档快照截取包含滚动区域在内的完整页面
+#[cfg(target_os = "linux")]
+async fn capture_full_page_png(webview: &Webview) -> Result<Vec<u8>, String> {
+    let (tx, rx) = tokio::sync::oneshot::channel::<Result<Vec<u8>, String>>();
+    webview
+        .with_webview(move |platform| {
+            use webkit2gtk::{gio::Cancellable, SnapshotOptions, SnapshotRegion, WebViewExt};
+
+            platform.inner().snapshot(
+                SnapshotRegion::FullDocument,
+                SnapshotOptions::NONE,
+                None::<&Cancellable>,
+                move |result| {
+                    let encoded = result.map_err(|err| err.to_string()).and_then(|surface| {
+                        let mut png = Vec::new();
+                        surface
+                            .write_to_png(&mut png)
+                            .map_err(|err| format!("failed to encode snapshot: {err}"))?;
+                        Ok(png)
+                    });
+                    let _ = tx.send(encoded);
+                },
+            );
+        })
+        .map_err(|err| format!("failed to access webview: {err}"))?;
+
+    match tokio::time::timeout(FULL_PAGE_CAPTURE_TIMEOUT, rx).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(_)) => Err("snapshot callback dropped".to_string()),
+        Err(_) => Err("timed out waiting for full-page snapshot".to_string()),
+    }
+}
+
+/// 截图结果发送端，原生回调可能在发起调用失败后不再触发，因此允许多处尝试发送
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+type FullPageCaptureSender =
+    Arc<Mutex<Option<tokio::sync::oneshot::Sender<Result<Vec<u8>, String>>>>>;
+
+/// 发送截图结果，只有第一次发送生效
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+fn finish_full_page_capture(tx: &FullPageCaptureSender, result: Result<Vec<u8>, String>) {
+    let sender = match tx.lock() {
+        Ok(mut guard) => guard.take(),
+        Err(poisoned) => poisoned.into_inner().take(),
+    };
+    if let Some(sender) = sender {
+        let _ = sender.send(result);
+    }
+}
+
+/// 等待原生截图回调
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+async fn await_full_page_capture(
+    rx: tokio::sync::oneshot::Receiver<Result<Vec<u8>, String>>,
+) -> Result<Vec<u8>, String> {
+    match tokio::time::timeout(FULL_PAGE_CAPTURE_TIMEOUT, rx).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(_)) => Err("snapshot callback dropped".to_string()),
+        Err(_) => Err("timed out waiting for full-page snapshot".to_string()),
+    }
+}
+
+/// 根据 `Page.getLayoutMetrics` 的结果生成整页截图参数
+///
+/// 仅设置 `captureBeyondViewport` 时仍只截取视口，需要按 `cssContentSize`（旧版本为
+/// `contentSize`）传入覆盖整个文档的 `clip`
+#[cfg(any(target_os = "windows", test))]
+fn cdp_full_page_screenshot_params(layout_metrics: &str) -> Result<String, String> {
+    let value: serde_json::Value = serde_json::from_str(layout_metrics)
+        .map_err(|err| format!("invalid layout metrics response: {err}"))?;
+    let size = value
+        .get("cssContentSize")
+        .or_else(|| value.get("contentSize"))
+        .ok_or_else(|| "layout metrics response has no content size".to_string())?;
+    let dimension = |key: &str| {
+        size.get(key)
+            .and_then(serde_json::Value::as_f64)
+            .filter(|value| *value > 0.0)
+            .map(f64::ceil)
+            .ok_or_else(|| format!("layout metrics response has no content {key}"))
+    };
+    let (width, height) = (dimension("width")?, dimension("height")?);
+    Ok(serde_json::json!({
+        "format": "png",
+        "captureBeyondViewport": true,
+        "fromSurface": true,
+        "clip": { "x": 0, "y": 0, "width": width, "height": height, "scale": 1 },
+    })
+    .to_string())
+}
+
+/// 解析 `Page.captureScreenshot` 返回的 JSON（`{"data": "<base64 PNG>"}`）
+#[cfg(any(target_os = "windows", test))]
+fn decode_cdp_screenshot(json: &str) -> Result<Vec<u8>, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(json).map_err(|err| format!("invalid screenshot response: {err}"))?;
+    let data = value
+        .get("data")
+        .and_then(serde_json::Value::as_str)
+        .ok_or_else(|| "screenshot response has no data".to_string())?;
+    crate::utils::decode_base64(data)
+}
+
+/// 调用 DevTools 协议方法，结果（JSON 文本或错误）交给 `on_done`
+#[cfg(target_os = "windows")]
+fn call_devtools_method(
+    core: &webview2_com::Microsoft::Web::WebView2::Win32::ICoreWebView2,
+    method: &str,
+    params: &str,
+    on_done: impl FnOnce(Result<String, String>) + 'static,
+) {
+    use webview2_com::{CallDevToolsProtocolMethodCompletedHandler, CoTaskMemPWSTR};
+
+    // 发起调用失败时回调不会触发，由调用处兜底发送错误
+    let on_done = Arc::new(Mutex::new(Some(on_done)));
+    let callback_on_done = on_done.clone();
+    let method_name = method.to_string();
+    let handler =
+        CallDevToolsProtocolMethodCompletedHandler::create(Box::new(move |result, json| {
+            if let Some(on_done) =
+                crate::utils::lock_or_recover(&callback_on_done, "devtools callback").take()
+            {
+                on_done(
+                    result
+                        .map(|()| json)
+                        .map_err(|err| format!("{method_name} failed: {err}")),
+                );
+            }
+            Ok(())
+        }));
+    let method_w = CoTaskMemPWSTR::from(method);
+    let params_w = CoTaskMemPWSTR::from(params);
+    let started = unsafe {
+        core.CallDevToolsProtocolMethod(
+            *method_w.as_ref().as_pcwstr(),
+            *params_w.as_ref().as_pcwstr(),
+            &handler,
+        )
+    };
+    if let Err(err) = started {
+        if let Some(on_done) = crate::utils::lock_or_recover(&on_done, "devtools callback").take() {
+            on_done(Err(format!("failed to call {method}: {err}")));
+        }
+    }
+}
+
+/// 通过 WebView2 的 DevTools 协议截取整页：先用 `Page.getLayoutMetrics` 取得文档尺寸，
+/// 再以覆盖整个文档的裁剪区域调用 `Page.captureScreenshot`
+#[cfg(target_os = "windows")]
+async fn capture_full_page_png(webview: &Webview) -> Result<Vec<u8>, String> {
+    let (tx, rx) = tokio::sync::oneshot::channel::<Result<Vec<u8>, String>>();
+    let tx: FullPageCaptureSender = Arc::new(Mutex::new(Some(tx)));
+    webview
+        .with_webview(move |platform| {
+            let core = match unsafe { platform.controller().CoreWebView2() } {
+                Ok(core) => core,
+                Err(err) => {
+                    finish_full_page_capture(&tx, Err(format!("failed to access WebView2: {err}")));
+                    return;
+                }
+            };
+            let capture_core = core.clone();
+            call_devtools_method(
+                &core,
+                "Page.getLayoutMetrics",
+                "{}",
+                move |metrics| match metrics.and_then(|json| cdp_full_page_screenshot_params(&json))
+                {
+                    Ok(params) => {
+                        call_devtools_method(
+                            &capture_core,
+                            "Page.captureScreenshot",
+                            &params,
+                            move |result| {
+                                finish_full_page_capture(
+                                    &tx,
+                                    result.and_then(|json| decode_cdp_screenshot(&json)),
+                                );
+                            },
+                        );
+                    }
+                    Err(err) => finish_full_page_capture(&tx, Err(err)),
+                },
+            );
+        })
+        .map_err(|err| format!("failed to access webview: {err}"))?;
+
+    await_full_page_capture(rx).await
+}
+
+/// WKWebView 整页截图的最大高度（点），过高的位图会超出纹理上限导致截图失败
+#[cfg(target_os = "macos")]
+const WK_FULL_PAGE_MAX_HEIGHT: f64 = 16_384.0;
+
+#[cfg(target_os = "macos")]
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct WkPoint {
+    x: f64,
+    y: f64,
+}
+
+#[cfg(target_os = "macos")]
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct WkSize {
+    width: f64,
+    height: f64,
+}
+
+/// 与 `CGRect` 内存布局一致，用于设置 `WKPDFConfiguration.rect`
+#[cfg(target_os = "macos")]
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct WkRect {
+    origin: WkPoint,
+    size: WkSize,
+}
+
+#[cfg(target_os = "macos")]
+unsafe impl objc2::encode::Encode for WkPoint {
+    const ENCODING: objc2::encode::Encoding =
+        objc2::encode::Encoding::Struct("CGPoint", &[f64::ENCODING, f64::ENCODING]);
+}
+
+#[cfg(target_os = "macos")]
+unsafe impl objc2::encode::Encode for WkSize {
+    const ENCODING: objc2::encode::Encoding =
+        objc2::encode::Encoding::Struct("CGSize", &[f64::ENCODING, f64::ENCODING]);
+}
+
+#[cfg(target_os = "macos")]
+unsafe impl objc2::encode::Encode for WkRect {
+    const ENCODING: objc2::encode::Encoding =
+        objc2::encode::Encoding::Struct("CGRect", &[WkPoint::ENCODING, WkSize::ENCODING]);
+}
+
+/// 读取 `NSError` 的描述文字
+#[cfg(target_os = "macos")]
+unsafe fn ns_error_message(error: *mut objc2::runtime::AnyObject) -> String {
+    use objc2::msg_send;
+    use objc2::runtime::AnyObject;
+
+    let description: *mut AnyObject = msg_send![error, localizedDescription];
+    if description.is_null() {
+        return "unknown error".to_string();
+    }
+    let utf8: *const std::ffi::c_char = msg_send![description, UTF8String];
+    if utf8.is_null() {
+        return "unknown error".to_string();
+    }
+    std::ffi::CStr::from_ptr(utf8)
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// 将 `NSImage` 编码为 PNG 字节
+#[cfg(target_os = "macos")]
+unsafe fn ns_image_png_bytes(image: *mut objc2::runtime::AnyObject) -> Result<Vec<u8>, String> {
+    use objc2::runtime::AnyObject;
+    use objc2::{class, msg_send};
+
+    // NSBitmapImageFileTypePNG
+    const PNG_FILE_TYPE: usize = 4;
+
+    let tiff: *mut AnyObject = msg_send![image, TIFFRepresentation];
+    if tiff.is_null() {
+        return Err("snapshot has no bitmap representation".to_string());
+    }
+    let rep: *mut AnyObject = msg_send![class!(NSBitmapImageRep), imageRepWithData: tiff];
+    if rep.is_null() {
+        return Err("failed to decode snapshot bitmap".to_string());
+    }
+    let properties: *mut AnyObject = msg_send![class!(NSDictionary), dictionary];
+    let png: *mut AnyObject =
+        msg_send![rep, representationUsingType: PNG_FILE_TYPE, properties: properties];
+    if png.is_null() {
+        return Err("failed to encode snapshot".to_string());
+    }
+    let length: usize = msg_send![png, length];
+    let bytes: *const std::ffi::c_void = msg_send![png, bytes];
+    if bytes.is_null() || length == 0 {
+        return Err("snapshot is empty".to_string());
+    }
+    Ok(std::slice::from_raw_parts(bytes.cast::<u8>(), length).to_vec())
+}
+
+/// 使用 WKWebView 截取整页
+///
+/// `takeSnapshot` 只渲染 frame 内的内容，撑高 frame 会让用户看到页面尺寸变化；
+/// 这里改用 `createPDF` 按文档尺寸离屏生成单页 PDF，再栅格化为 PNG，
+/// 页面布局与显示都不受影响。需要 macOS 11 及以上。
+#[cfg(target_os = "macos")]
+async fn capture_full_page_png(webview: &Webview) -> Result<Vec<u8>, String> {
+    let (tx, rx) = tokio::sync::oneshot::channel::<Result<Vec<u8>, String>>();
+    let tx: FullPageCaptureSender = Arc::new(Mutex::new(Some(tx)));
+    webview
+        .with_webview(move |platform| {
+            let wk_webview = platform.inner() as usize;
+            unsafe { wk_measure_and_capture(wk_webview, tx) };
+        })
+        .map_err(|err| format!("failed to access webview: {err}"))?;
+
+    await_full_page_capture(rx).await
+}
+
+/// 第一步：测量文档尺寸（CSS 像素），随后在主线程回调中生成整页 PDF
+#[cfg(target_os = "macos")]
+unsafe fn wk_measure_and_capture(wk_webview: usize, tx: FullPageCaptureSender) {
+    use block2::RcBlock;
+    use objc2::runtime::AnyObject;
+    use objc2::{class, msg_send, sel};
+
+    let webview = wk_webview as *mut AnyObject;
+    let supports_pdf: bool =
+        msg_send![webview, respondsToSelector: sel!(createPDFWithConfiguration:completionHandler:)];
+    if !supports_pdf {
+        finish_full_page_capture(
+            &tx,
+            Err("full-page capture requires macOS 11 or later".to_string()),
+        );
+        return;
+    }
+
+    let script: *mut AnyObject = msg_send![
+        class!(NSString),
+        stringWithUTF8String: c"(() => { const root = document.documentElement; const body = document.body; return JSON.stringify([Math.max(root.scrollWidth, body ? body.scrollWidth : 0), Math.max(root.scrollHeight, body ? body.scrollHeight : 0)]); })()".as_ptr()
+    ];
+    let callback_tx = tx.clone();
+    let handler = RcBlock::new(move |result: *mut AnyObject, error: *mut AnyObject| {
+        if !error.is_null() {
+            let message = format!("failed to measure page: {}", ns_error_message(error));
+            finish_full_page_capture(&callback_tx, Err(message));
+            return;
+        }
+        let size = if result.is_null() {
+            None
+        } else {
+            let utf8: *const std::ffi::c_char = msg_send![result, UTF8String];
+            (!utf8.is_null())
+                .then(|| {
+                    std::ffi::CStr::from_ptr(utf8)
+                        .to_string_lossy()
+                        .into_owned()
+                })
+                .and_then(|json| serde_json::from_str::<(f64, f64)>(&json).ok())
+        };
+        match size {
+            Some((width, height)) if width > 0.0 && height > 0.0 => {
+                wk_create_full_page_pdf(wk_webview, width, height, callback_tx.clone());
+            }
+            _ => finish_full_page_capture(
+                &callback_tx,
+                Err("failed to measure page size".to_string()),
+            ),
+        }
+    });
+    let _: () = msg_send![webview, evaluateJavaScript: script, completionHandler: &*handler];
+}
+
+/// 第二步：按文档尺寸生成 PDF 并转为 PNG（高度超过上限时截断）
+#[cfg(target_os = "macos")]
+unsafe fn wk_create_full_page_pdf(
+    wk_webview: usize,
+    width: f64,
+    height: f64,
+    tx: FullPageCaptureSender,
+) {
+    use block2::RcBlock;
+    use objc2::runtime::AnyObject;
+    use objc2::{class, msg_send};
+
+    let webview = wk_webview as *mut AnyObject;
+    let configuration: *mut AnyObject = msg_send![class!(WKPDFConfiguration), new];
+    let rect = WkRect {
+        origin: WkPoint { x: 0.0, y: 0.0 },
+        size: WkSize {
+            width,
+            height: height.min(WK_FULL_PAGE_MAX_HEIGHT),
+        },
+    };
+    let _: () = msg_send![configuration, setRect: rect];
+
+    let handler = RcBlock::new(move |data: *mut AnyObject, error: *mut AnyObject| {
+        let encoded = if data.is_null() {
+            Err(format!("snapshot failed: {}", ns_error_message(error)))
+        } else {
+            let image: *mut AnyObject = msg_send![class!(NSImage), alloc];
+            let image: *mut AnyObject = msg_send![image, initWithData: data];
+            if image.is_null() {
+                Err("failed to decode page PDF".to_string())
+            } else {
+                let encoded = ns_image_png_bytes(image);
+                let _: () = msg_send![image, release];
+                encoded
+            }
+        };
+        finish_full_page_capture(&tx, encoded);
+    });
+    let _: () = msg_send![
+        webview,
+        createPDFWithConfiguration: configuration,
+        completionHandler: &*handler
+    ];
+    let _: () = msg_send![configuration, release];
+}
+
+/// 其他平台没有可用的整文档截图接口
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+async fn capture_full_page_png(_webview: &Webview) -> Result<Vec<u8>, String> {
+    Err(format!(
+        "full-page capture is not supported on {WEBVIEW_ENGINE} yet"
+    ))
+}
+
+/// 截取子 WebView 的整页（含滚动区域）截图，保存为 PNG 临时文件并返回路径
+#[tauri::command]
+pub(crate) async fn capture_child_webview_full_page(
+    app: AppHandle,
+    state: State<'_, ChildWebviewManager>,
+    id: String,
+) -> Result<FullPageScreenshot, String> {
+    let webview = {
+        let webviews = state
+            .webviews
+            .lock()
+            .map_err(|err| format!("failed to lock webview map: {err}"))?;
+        let entry = webviews
+            .get(&id)
+            .ok_or_else(|| format!("child webview not found: {id}"))?;
+        if entry.suspended_url.is_some() {
+            return Err(format!("child webview is suspended: {id}"));
+        }
+        entry.webview.clone()
+    };
+
+    let png = capture_full_page_png(&webview).await?;
+    let (width, height) =
+        png_dimensions(&png).ok_or_else(|| "snapshot is not a valid PNG".to_string())?;
+
+    let dir = app_paths::cache_dir(&app)?.join(SCREENSHOTS_DIR_NAME);
+    fs::create_dir_all(&dir).map_err(|err| format!("failed to create screenshot dir: {err}"))?;
+    prune_screenshots(&dir);
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis())
+        .unwrap_or_default();
+    let path = dir.join(format!("{}-{}.png", screenshot_file_stem(&id), timestamp));
+    fs::write(&path, &png).map_err(|err| format!("failed to write screenshot: {err}"))?;
+
+    log::info!(
+        "Captured full-page screenshot of child webview {}: {}x{} ({} bytes)",
+        id,
+        width,
+        height,
+        png.len()
+    );
+    Ok(FullPageScreenshot {
+        path: path.to_string_lossy().into_owned(),
+        width,
+        height,
+        size_bytes: png.len() as u64,
+    })
+}
+
+/// 生成插入/更新 `<style>` 元素的脚本
+fn build_css_injection_script(style_id: &str, css: &str) -> String {
+    let id = serde_json::Value::from(style_id);
+    let css = serde_json::Value::from(css);
+    format!(
+        "(function() {{ var el = document.getElementById({id}); if (!el) {{ el = document.createElement('style'); el.id = {id}; (document.head || document.documentElement).appendChild(el); }} el.textContent = {css}; }})();"
+    )
+}
+
+/// 生成移除 `<style>` 元素的脚本
+fn build_css_removal_script(style_id: &str) -> String {
+    let id = serde_json::Value::from(style_id);
+    format!("(function() {{ var el = document.getElementById({id}); if (el) el.remove(); }})();")
+}
+
+/// 页面加载完成后重新应用所有已注入样式
+fn reapply_injected_css(webview: &Webview, styles: &InjectedStyles) {
+    let Ok(styles) = styles.lock() else {
+        return;
+    };
+    for (style_id, css) in styles.iter() {
+        if let Err(err) = webview.eval(build_css_injection_script(style_id, css)) {
+            log::warn!("Failed to reapply injected CSS {}: {}", style_id, err);
+        }
+    }
+}
+
+/// 向子 WebView 注入 CSS，返回可用于移除的样式 ID
+#[tauri::command]
+pub(crate) async fn inject_child_webview_css(
+    state: State<'_, ChildWebviewManager>,
+    id: String,
+    css: String,
+) -> Result<String, String> {
+    let webviews = state
+        .webviews
+        .lock()
+        .map_err(|err| format!("failed to lock webview map: {err}"))?;
+    let entry = webviews
+        .get(&id)
+        .ok_or_else(|| format!("child webview not found: {id}"))?;
+
+    let style_id = format!(
+        "ai-ask-injected-style-{}",
+        NEXT_INJECTED_STYLE_ID.fetch_add(1, Ordering::Relaxed)
+    );
+    entry
+        .webview
+        .eval(build_css_injection_script(&style_id, &css))
+        .map_err(|err| format!("css injection failed: {err}"))?;
+
+    entry
+        .injected_css
+        .lock()
+        .map_err(|err| format!("failed to lock injected styles: {err}"))?
+        .push((style_id.clone(), css));
+
+    log::debug!("Injected CSS into child webview {}: {}", id, style_id);
+    Ok(style_id)
+}
+
+/// 移除此前注入到子 WebView 的 CSS
+#[tauri::command]
+pub(crate) async fn remove_child_webview_css(
+    state: State<'_, ChildWebviewManager>,
+    id: String,
+    style_id: String,
+) -> Result<(), String> {
+    let webviews = state
+        .webviews
+        .lock()
+        .map_err(|err| format!("failed to lock webview map: {err}"))?;
+    let entry = webviews
+        .get(&id)
+        .ok_or_else(|| format!("child webview not found: {id}"))?;
+
+    entry
+        .injected_css
+        .lock()
+        .map_err(|err| format!("failed to lock injected styles: {err}"))?
+        .retain(|(existing, _)| existing != &style_id);
+
+    entry
+        .webview
+        .eval(build_css_removal_script(&style_id))
+        .map_err(|err| format!("css removal failed: {err}"))?;
+
+    log::debug!(
+        "Removed injected CSS from child webview {}: {}",
+        id,
+        style_id
+    );
+    Ok(())
+}
+
+/// 设置子 WebView 导航黑名单，空列表表示允许所有导航
+#[tauri::command]
+pub(crate) async fn set_child_webview_navigation_blocklist(
+    patterns: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let normalized = normalize_navigation_blocklist(&patterns);
+
+    log::info!(
+        "Child webview navigation blocklist updated: {:?}",
+        normalized
+    );
+    match navigation_blocklist().write() {
+        Ok(mut guard) => *guard = normalized.clone(),
+        Err(poisoned) => *poisoned.into_inner() = normalized.clone(),
+    }
+    Ok(normalized)
+}
+
+/// 注入脚本存放目录（位于应用数据目录下）
+const INJECTION_SCRIPTS_DIR: &str = "injection-scripts";
+
+/// 校验注入脚本名称：仅允许相对路径的普通组成部分，拒绝绝对路径与 `..`
+fn validate_injection_script_name(name: &str) -> Result<(), String> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() {
+        return Err("script name cannot be empty".into());
+    }
+
+    let path = Path::new(trimmed);
+    if path.is_absolute() || trimmed.starts_with(['/', '\\']) {
+        return Err(format!("absolute script path is not allowed: {name}"));
+    }
+
+    let all_normal = path
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)));
+    if !all_normal || trimmed.split(['/', '\\']).any(|part| part == "..") {
+        return Err(format!("invalid script name: {name}"));
+    }
+
+    Ok(())
+}
+
+/// 读取应用数据目录下的注入脚本，供 `evaluate_child_webview_script` 使用
+#[tauri::command]
+pub(crate) async fn read_injection_script(app: AppHandle, name: String) -> Result<String, String> {
+    validate_injection_script_name(&name)?;
+
+    let scripts_dir = app_paths::data_dir(&app)?.join(INJECTION_SCRIPTS_DIR);
+    let script_path = scripts_dir.join(name.trim());
+
+    // 解析符号链接后再次确认仍位于脚本目录内
+    let canonical_dir = scripts_dir
+        .canonicalize()
+        .map_err(|err| format!("injection scripts directory unavailable: {err}"))?;
+    let canonical_path = script_path
+        .canonicalize()
+        .map_err(|err| format!("injection script not found: {name} ({err})"))?;
+    if !canonical_path.starts_with(&canonical_dir) {
+        log::warn!("Rejected injection script outside scripts dir: {}", name);
+        return Err(format!("invalid script name: {name}"));
+    }
+
+    let content = fs::read_to_string(&canonical_path)
+        .map_err(|err| format!("failed to read injection script {name}: {err}"))?;
+    log::debug!(
+        "Loaded injection script: name={}, len={}",
+        name,
+        content.len()
+    );
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        build_css_injection_script, build_css_removal_script, build_extra_headers_script,
+        build_probe_script, build_referrer_policy_script, cdp_full_page_screenshot_params,
+        clamp_zoom_factor, decode_cdp_screenshot, detect_load_error, host_matches_pattern,
+        idle_suspend_due, load_retry_delay, normalize_external_url_schemes,
+        normalize_navigation_blocklist, png_dimensions, redact_headers_for_log,
+        remote_debugging_port, screenshot_file_stem, screenshots_to_prune,
+        should_open_in_default_browser, should_use_desktop_user_agent,
+        validate_injection_script_name, EnsureChildWebviewPayload, InjectionAggregate,
+        INJECTION_AGGREGATION_STALE_AFTER, MAX_INJECTION_CHUNKS,
+    };
+    use std::time::{Duration, Instant};
+    use tauri::Url;
+
+    #[test]
+    fn allows_well_known_schemes() {
+        let http = Url::parse("http://example.com").unwrap();
+        assert!(should_open_in_default_browser(&http));
+
+        let https = Url::parse("https://example.com").unwrap();
+        assert!(should_open_in_default_browser(&https));
+
+        let mailto = Url::parse("mailto:user@example.com").unwrap();
+        assert!(should_open_in_default_browser(&mailto));
+
+        let tel = Url::parse("tel:123456").unwrap();
         assert!(should_open_in_default_browser(&tel));
     }
 
@@ -789,4 +3471,370 @@ mod tests {
             "https://chatgpt.com"
         ));
     }
+
+    #[test]
+    fn injection_script_name_accepts_relative_paths() {
+        assert!(validate_injection_script_name("chatgpt.js").is_ok());
+        assert!(validate_injection_script_name("platforms/claude.js").is_ok());
+    }
+
+    #[test]
+    fn injection_script_name_rejects_traversal_and_absolute() {
+        assert!(validate_injection_script_name("").is_err());
+        assert!(validate_injection_script_name("../secret.js").is_err());
+        assert!(validate_injection_script_name("a/../../b.js").is_err());
+        assert!(validate_injection_script_name("..\\b.js").is_err());
+        assert!(validate_injection_script_name("/etc/passwd").is_err());
+        assert!(validate_injection_script_name("\\windows\\x.js").is_err());
+    }
+
+    #[test]
+    fn probe_script_embeds_request_id_and_body() {
+        let script = build_probe_script(42, "return 1 + 1;");
+        assert!(script.contains("__requestId: 42"));
+        assert!(script.contains("return 1 + 1;"));
+        assert!(script.contains("http://injection.localhost/probe-result?d="));
+        assert!(!script.contains("injection.localhost/begin"));
+    }
+
+    #[test]
+    fn normalizes_custom_external_schemes() {
+        let input = vec![
+            " Slack: ".to_string(),
+            "vscode".to_string(),
+            "https".to_string(),
+            "slack".to_string(),
+        ];
+        assert_eq!(
+            normalize_external_url_schemes(&input),
+            vec!["slack", "vscode", "https"]
+        );
+    }
+
+    #[test]
+    fn invalid_external_schemes_fall_back_to_defaults() {
+        let input = vec![
+            "".to_string(),
+            "javascript".to_string(),
+            "1abc".to_string(),
+            "bad scheme".to_string(),
+        ];
+        assert_eq!(
+            normalize_external_url_schemes(&input),
+            vec!["http", "https", "mailto", "tel"]
+        );
+        assert_eq!(
+            normalize_external_url_schemes(&[]),
+            vec!["http", "https", "mailto", "tel"]
+        );
+    }
+
+    #[test]
+    fn css_injection_script_escapes_content() {
+        let script = build_css_injection_script("style-1", "body { content: \"</style>\"; }");
+        assert!(script.contains(r#"getElementById("style-1")"#));
+        assert!(script.contains(r#"el.textContent = "body { content: \"</style>\"; }";"#));
+    }
+
+    #[test]
+    fn css_removal_script_targets_style_id() {
+        let script = build_css_removal_script("style-2");
+        assert!(script.contains(r#"getElementById("style-2")"#));
+        assert!(script.contains("el.remove()"));
+    }
+
+    #[test]
+    fn navigation_pattern_matches_exact_host() {
+        assert!(host_matches_pattern(
+            "tracker.example.com",
+            "tracker.example.com"
+        ));
+        assert!(host_matches_pattern(
+            "Tracker.Example.com",
+            "tracker.example.com"
+        ));
+        assert!(!host_matches_pattern(
+            "other.example.com",
+            "tracker.example.com"
+        ));
+    }
+
+    #[test]
+    fn navigation_pattern_matches_wildcard_domain() {
+        assert!(host_matches_pattern("example.com", "*.example.com"));
+        assert!(host_matches_pattern("a.b.example.com", "*.example.com"));
+        assert!(!host_matches_pattern("badexample.com", "*.example.com"));
+    }
+
+    #[test]
+    fn navigation_blocklist_dedups_non_adjacent_patterns() {
+        let patterns = [
+            "Example.com.",
+            "*.tracker.io",
+            " ",
+            "example.com",
+            "*.",
+            "*.TRACKER.io",
+        ]
+        .map(String::from);
+        assert_eq!(
+            normalize_navigation_blocklist(&patterns),
+            vec!["example.com".to_string(), "*.tracker.io".to_string()]
+        );
+    }
+
+    #[test]
+    fn detects_engine_error_pages() {
+        let chrome = Url::parse("chrome-error://chromewebdata/").unwrap();
+        assert!(detect_load_error(&chrome).is_some());
+
+        let neterror = Url::parse("about:neterror?e=dnsNotFound").unwrap();
+        assert!(detect_load_error(&neterror).is_some());
+
+        let normal = Url::parse("https://chatgpt.com/").unwrap();
+        assert!(detect_load_error(&normal).is_none());
+
+        let blank = Url::parse("about:blank").unwrap();
+        assert!(detect_load_error(&blank).is_none());
+    }
+
+    #[test]
+    fn load_retry_delay_doubles_and_caps() {
+        use std::time::Duration;
+
+        assert_eq!(load_retry_delay(1000, 1), Duration::from_millis(1000));
+        assert_eq!(load_retry_delay(1000, 2), Duration::from_millis(2000));
+        assert_eq!(load_retry_delay(1000, 3), Duration::from_millis(4000));
+        assert_eq!(load_retry_delay(1000, 20), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn redacts_sensitive_header_values() {
+        let headers = std::collections::HashMap::from([
+            ("Authorization".to_string(), "Bearer abc".to_string()),
+            ("X-Api-Key".to_string(), "secret".to_string()),
+            ("Referer".to_string(), "https://example.com".to_string()),
+        ]);
+        assert_eq!(
+            redact_headers_for_log(&headers),
+            vec![
+                "Authorization: ***",
+                "Referer: https://example.com",
+                "X-Api-Key: ***"
+            ]
+        );
+    }
+
+    #[test]
+    fn extra_headers_script_skips_invalid_names() {
+        let empty = std::collections::HashMap::new();
+        assert!(build_extra_headers_script(&empty).is_none());
+
+        let invalid =
+            std::collections::HashMap::from([("Bad Header".to_string(), "x".to_string())]);
+        assert!(build_extra_headers_script(&invalid).is_none());
+
+        let valid = std::collections::HashMap::from([("X-Tenant".to_string(), "acme".to_string())]);
+        let script = build_extra_headers_script(&valid).unwrap();
+        assert!(script.contains(r#"{"X-Tenant":"acme"}"#));
+    }
+
+    #[test]
+    fn referrer_policy_script_validates_policy() {
+        let script = build_referrer_policy_script(" No-Referrer ").unwrap();
+        assert!(script.contains(r#"const POLICY = "no-referrer";"#));
+        assert!(build_referrer_policy_script("always").is_err());
+        assert!(build_referrer_policy_script("\"; alert(1); \"").is_err());
+    }
+
+    #[test]
+    fn aggregates_chunks_until_end() {
+        let now = Instant::now();
+        let mut aggregate = InjectionAggregate::default();
+        assert!(aggregate.begin(2, now).is_ok());
+        assert_eq!(aggregate.push_chunk("abc", Some(2), 16, now), Ok(true));
+        assert_eq!(aggregate.push_chunk("def", Some(2), 16, now), Ok(true));
+        assert_eq!(
+            aggregate.finish(Some(2)),
+            Some((2, 2, "abcdef".to_string()))
+        );
+        assert_eq!(aggregate.finish(None), Some((0, 0, String::new())));
+    }
+
+    #[test]
+    fn aborts_aggregation_when_payload_too_large() {
+        let now = Instant::now();
+        let mut aggregate = InjectionAggregate::default();
+        assert!(aggregate.begin(3, now).is_ok());
+        assert_eq!(aggregate.push_chunk("abcd", Some(3), 6, now), Ok(true));
+        assert_eq!(
+            aggregate.push_chunk("efgh", Some(3), 6, now),
+            Err("payload_too_large")
+        );
+        assert!(aggregate.data.is_empty());
+
+        // 中止后的分片与 end 信号都被忽略
+        assert_eq!(aggregate.push_chunk("ij", Some(3), 6, now), Ok(false));
+        assert!(aggregate.data.is_empty());
+        assert_eq!(aggregate.finish(Some(3)), None);
+
+        // 新一轮 begin 恢复正常聚合
+        assert!(aggregate.begin(1, now).is_ok());
+        assert_eq!(aggregate.push_chunk("ok", Some(1), 6, now), Ok(true));
+        assert_eq!(aggregate.finish(Some(1)), Some((1, 1, "ok".to_string())));
+    }
+
+    #[test]
+    fn rejects_invalid_chunk_counts() {
+        let now = Instant::now();
+        let mut aggregate = InjectionAggregate::default();
+        assert_eq!(aggregate.begin(0, now), Err("invalid_chunk_count"));
+        assert_eq!(
+            aggregate.begin(MAX_INJECTION_CHUNKS + 1, now),
+            Err("invalid_chunk_count")
+        );
+        assert_eq!(aggregate.push_chunk("abc", None, 16, now), Ok(false));
+        assert_eq!(aggregate.finish(None), None);
+        assert!(aggregate.begin(MAX_INJECTION_CHUNKS, now).is_ok());
+    }
+
+    #[test]
+    fn rejects_begin_while_aggregation_in_flight() {
+        let now = Instant::now();
+        let mut aggregate = InjectionAggregate::default();
+        assert!(aggregate.begin(3, now).is_ok());
+        // 尚未收到分片时允许重启
+        assert!(aggregate.begin(2, now).is_ok());
+        assert_eq!(aggregate.push_chunk("ab", Some(2), 16, now), Ok(true));
+
+        assert_eq!(aggregate.begin(5, now), Err("aggregation_in_progress"));
+        // 被拒绝的 begin 所属的分片与 end 不影响当前聚合
+        assert_eq!(aggregate.push_chunk("zz", Some(5), 16, now), Ok(false));
+        assert_eq!(aggregate.finish(Some(5)), None);
+        assert_eq!(aggregate.push_chunk("cd", Some(2), 16, now), Ok(true));
+        assert_eq!(aggregate.finish(Some(2)), Some((2, 2, "abcd".to_string())));
+
+        // 超时未完成的聚合允许新的 begin 接管
+        assert!(aggregate.begin(2, now).is_ok());
+        assert_eq!(aggregate.push_chunk("ab", Some(2), 16, now), Ok(true));
+        let later = now + INJECTION_AGGREGATION_STALE_AFTER;
+        assert!(aggregate.begin(1, later).is_ok());
+    }
+
+    #[test]
+    fn idle_suspend_requires_hidden_past_timeout() {
+        let hidden_at = Instant::now();
+        let timeout = Some(Duration::from_secs(60));
+        let later = hidden_at + Duration::from_secs(120);
+        assert!(idle_suspend_due(Some(hidden_at), timeout, later));
+        assert!(!idle_suspend_due(Some(hidden_at), timeout, hidden_at));
+        assert!(!idle_suspend_due(None, timeout, later));
+        assert!(!idle_suspend_due(Some(hidden_at), None, later));
+    }
+
+    #[test]
+    fn reads_png_dimensions_from_header() {
+        let mut png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 13];
+        png.extend_from_slice(b"IHDR");
+        png.extend_from_slice(&1280u32.to_be_bytes());
+        png.extend_from_slice(&7200u32.to_be_bytes());
+        assert_eq!(png_dimensions(&png), Some((1280, 7200)));
+        assert_eq!(png_dimensions(b"not a png"), None);
+    }
+
+    #[test]
+    fn screenshot_file_stem_is_filesystem_safe() {
+        assert_eq!(screenshot_file_stem("chatgpt"), "chatgpt");
+        assert_eq!(screenshot_file_stem("../a b:c"), "___a_b_c");
+        assert_eq!(screenshot_file_stem(""), "webview");
+    }
+
+    #[test]
+    fn decode_cdp_screenshot_reads_base64_data() {
+        assert_eq!(
+            decode_cdp_screenshot(r#"{"data":"iVBORw=="}"#).unwrap(),
+            vec![0x89, b'P', b'N', b'G']
+        );
+        assert!(decode_cdp_screenshot(r#"{"error":"x"}"#).is_err());
+        assert!(decode_cdp_screenshot("not json").is_err());
+    }
+
+    #[test]
+    fn cdp_full_page_screenshot_params_clip_whole_document() {
+        let params: serde_json::Value = serde_json::from_str(
+            &cdp_full_page_screenshot_params(
+                r#"{"contentSize":{"width":1,"height":1},"cssContentSize":{"x":0,"y":0,"width":1280,"height":5000.4}}"#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(params["captureBeyondViewport"], true);
+        assert_eq!(params["clip"]["width"], 1280.0);
+        assert_eq!(params["clip"]["height"], 5001.0);
+        assert_eq!(params["clip"]["scale"], 1);
+
+        assert!(
+            cdp_full_page_screenshot_params(r#"{"contentSize":{"width":800,"height":600}}"#)
+                .is_ok()
+        );
+        assert!(cdp_full_page_screenshot_params(r#"{"cssContentSize":{"width":0}}"#).is_err());
+        assert!(cdp_full_page_screenshot_params("not json").is_err());
+    }
+
+    #[test]
+    fn screenshots_to_prune_drops_expired_and_excess_files() {
+        let now = std::time::SystemTime::now();
+        let minutes_ago = |minutes: u64| now - std::time::Duration::from_secs(minutes * 60);
+        let mut files: Vec<_> = (0..25)
+            .map(|index| {
+                (
+                    std::path::PathBuf::from(format!("{index}.png")),
+                    minutes_ago(index),
+                )
+            })
+            .collect();
+        files.push((std::path::PathBuf::from("old.png"), minutes_ago(48 * 60)));
+
+        let pruned = screenshots_to_prune(files, now);
+        assert_eq!(pruned.len(), 6);
+        assert!(pruned.contains(&std::path::PathBuf::from("old.png")));
+        assert!(pruned.contains(&std::path::PathBuf::from("20.png")));
+        assert!(!pruned.contains(&std::path::PathBuf::from("19.png")));
+    }
+
+    #[test]
+    fn clamp_zoom_factor_limits_range() {
+        assert_eq!(clamp_zoom_factor(1.25), Ok(1.25));
+        assert_eq!(clamp_zoom_factor(0.1), Ok(0.25));
+        assert_eq!(clamp_zoom_factor(12.0), Ok(5.0));
+        assert!(clamp_zoom_factor(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn remote_debugging_port_parses_browser_args() {
+        assert_eq!(
+            remote_debugging_port("--disable-gpu --remote-debugging-port=9222"),
+            Some(9222)
+        );
+        assert_eq!(remote_debugging_port("--remote-debugging-port=0"), None);
+        assert_eq!(remote_debugging_port("--disable-gpu"), None);
+    }
+
+    #[test]
+    fn proxy_override_distinguishes_missing_and_null() {
+        let parse = |json: &str| serde_json::from_str::<EnsureChildWebviewPayload>(json).unwrap();
+
+        let inherit = parse(r#"{"id":"a","url":"https://example.com"}"#);
+        assert_eq!(inherit.proxy_url, None);
+
+        let direct = parse(r#"{"id":"a","url":"https://example.com","proxyUrl":null}"#);
+        assert_eq!(direct.proxy_url, Some(None));
+
+        let custom =
+            parse(r#"{"id":"a","url":"https://example.com","proxyUrl":"http://127.0.0.1:7890"}"#);
+        assert_eq!(
+            custom.proxy_url,
+            Some(Some("http://127.0.0.1:7890".to_string()))
+        );
+    }
 }
@@ -1,14 +1,450 @@
 //! 主窗口控制模块
 //!
 //! 提供主窗口的显示、隐藏、切换等实用函数，并暴露对应的 Tauri 命令。
+//! 同时管理额外的面板窗口（多个 AI 面板并排对比），托盘与快捷键操作作用于最近聚焦的面板。
 
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
 use std::time::Duration;
 
-use tauri::{Emitter, Manager, Window};
+use serde::{Deserialize, Serialize};
+use tauri::{
+    menu::{Menu, MenuItem},
+    AppHandle, Emitter, Manager, PhysicalPosition, PhysicalSize, State, Theme, Url,
+    UserAttentionType, WebviewUrl, WebviewWindowBuilder, Window,
+};
+
+use crate::global_selection;
+use crate::screen;
+use crate::selection_toolbar::ToolbarManager;
+use crate::settings;
+
+/// 主窗口标签
+pub(crate) const MAIN_WINDOW_LABEL: &str = "main";
+/// 面板窗口标签前缀
+const PANEL_LABEL_PREFIX: &str = "panel-";
+const PANEL_DEFAULT_WIDTH: f64 = 1200.0;
+const PANEL_DEFAULT_HEIGHT: f64 = 800.0;
+const PANEL_MIN_WIDTH: f64 = 900.0;
+const PANEL_MIN_HEIGHT: f64 = 600.0;
+
+const SETTING_MINIMIZE_TO_TRAY: &str = "minimizeToTray";
+const SETTING_MAIN_WINDOW_DECORATIONS: &str = "mainWindowDecorations";
+const SETTING_MAIN_WINDOW_GEOMETRY: &str = "mainWindowGeometry";
+const SETTING_TRAY_CLICK_ACTION: &str = "trayClickAction";
+const SETTING_TRAY_MIDDLE_CLICK_ACTION: &str = "trayMiddleClickAction";
+const SETTING_TRAY_DOUBLE_CLICK_ACTION: &str = "trayDoubleClickAction";
+const SETTING_TRAY_MENU_LABELS: &str = "trayMenuLabels";
+/// 托盘图标 ID（见 tauri.conf.json）
+pub(crate) const TRAY_ID: &str = "main";
+/// 打开偏好设置事件，由主窗口前端监听
+const EVENT_OPEN_SETTINGS: &str = "open-settings";
+/// 移动/缩放停止后延迟保存窗口几何信息的时间，拖动过程中只产生一次写入
+const GEOMETRY_SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+/// 主窗口装饰状态变化事件，前端据此设置/移除自定义拖拽区域
+const EVENT_DECORATIONS_CHANGED: &str = "main-window:decorations-changed";
+
+/// 点击托盘图标时执行的动作
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum TrayClickAction {
+    /// 切换当前面板窗口的显示/隐藏
+    #[default]
+    ToggleWindow,
+    /// 始终显示并聚焦当前面板窗口
+    ShowWindow,
+    /// 像划词快捷键一样读取当前选区并弹出工具栏
+    TriggerSelection,
+    /// 显示主窗口并打开偏好设置
+    OpenSettings,
+    /// 不做任何操作（仅通过托盘菜单交互）
+    None,
+}
+
+/// 托盘图标的点击方式，每种方式可单独配置动作
+///
+/// 双击仅在 Windows 上由托盘图标上报，其他平台的 `DoubleClick` 动作不会被触发。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum TrayClickKind {
+    #[default]
+    Left,
+    Middle,
+    DoubleClick,
+}
+
+impl TrayClickKind {
+    fn setting_key(self) -> &'static str {
+        match self {
+            Self::Left => SETTING_TRAY_CLICK_ACTION,
+            Self::Middle => SETTING_TRAY_MIDDLE_CLICK_ACTION,
+            Self::DoubleClick => SETTING_TRAY_DOUBLE_CLICK_ACTION,
+        }
+    }
+
+    fn default_action(self) -> TrayClickAction {
+        match self {
+            Self::Left => TrayClickAction::ToggleWindow,
+            Self::Middle => TrayClickAction::TriggerSelection,
+            Self::DoubleClick => TrayClickAction::OpenSettings,
+        }
+    }
+}
+
+/// 各点击方式对应的托盘动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TrayClickActions {
+    left: TrayClickAction,
+    middle: TrayClickAction,
+    double_click: TrayClickAction,
+}
+
+impl Default for TrayClickActions {
+    fn default() -> Self {
+        Self {
+            left: TrayClickKind::Left.default_action(),
+            middle: TrayClickKind::Middle.default_action(),
+            double_click: TrayClickKind::DoubleClick.default_action(),
+        }
+    }
+}
+
+impl TrayClickActions {
+    fn get(&self, kind: TrayClickKind) -> TrayClickAction {
+        match kind {
+            TrayClickKind::Left => self.left,
+            TrayClickKind::Middle => self.middle,
+            TrayClickKind::DoubleClick => self.double_click,
+        }
+    }
+
+    fn slot_mut(&mut self, kind: TrayClickKind) -> &mut TrayClickAction {
+        match kind {
+            TrayClickKind::Left => &mut self.left,
+            TrayClickKind::Middle => &mut self.middle,
+            TrayClickKind::DoubleClick => &mut self.double_click,
+        }
+    }
+}
+
+/// 托盘菜单各项的显示文字，随界面语言切换
+///
+/// 菜单项 ID（`show`/`settings`/`quit`）固定不变，托盘菜单事件处理不受影响。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct TrayMenuLabels {
+    show: String,
+    settings: String,
+    quit: String,
+}
+
+impl Default for TrayMenuLabels {
+    fn default() -> Self {
+        Self {
+            show: "显示主窗口".to_string(),
+            settings: "偏好设置".to_string(),
+            quit: "退出".to_string(),
+        }
+    }
+}
+
+impl TrayMenuLabels {
+    fn validate(&self) -> Result<(), String> {
+        for (name, label) in [
+            ("show", &self.show),
+            ("settings", &self.settings),
+            ("quit", &self.quit),
+        ] {
+            if label.trim().is_empty() {
+                return Err(format!("Tray menu label `{name}` must not be empty"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 按给定文字构建托盘菜单
+fn build_tray_menu(app: &AppHandle, labels: &TrayMenuLabels) -> tauri::Result<Menu<tauri::Wry>> {
+    let show_item = MenuItem::with_id(app, "show", labels.show.trim(), true, None::<&str>)?;
+    let settings_item =
+        MenuItem::with_id(app, "settings", labels.settings.trim(), true, None::<&str>)?;
+    let quit_item = MenuItem::with_id(app, "quit", labels.quit.trim(), true, None::<&str>)?;
+    Menu::with_items(app, &[&show_item, &settings_item, &quit_item])
+}
+
+/// 启动时构建托盘菜单，使用上次保存的文字（未保存时使用默认中文文字）
+pub(crate) fn build_saved_tray_menu(app: &AppHandle) -> tauri::Result<Menu<tauri::Wry>> {
+    let labels = settings::load_setting::<TrayMenuLabels>(app, SETTING_TRAY_MENU_LABELS)
+        .filter(|labels| labels.validate().is_ok())
+        .unwrap_or_default();
+    build_tray_menu(app, &labels)
+}
+
+/// 设置托盘菜单文字并立即重建菜单，文字会持久化供下次启动使用
+#[tauri::command]
+pub(crate) async fn set_tray_menu_labels(
+    app: AppHandle,
+    labels: TrayMenuLabels,
+) -> Result<(), String> {
+    labels.validate()?;
+    log::info!("Updating tray menu labels: {:?}", labels);
+    settings::save_setting(&app, SETTING_TRAY_MENU_LABELS, &labels)?;
+
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        log::warn!("Tray icon not found, labels will apply on next launch");
+        return Ok(());
+    };
+    let menu = build_tray_menu(&app, &labels).map_err(|err| err.to_string())?;
+    tray.set_menu(Some(menu)).map_err(|err| err.to_string())
+}
+
+/// 主窗口行为偏好
+///
+/// 启动时从后端设置加载，运行期以原子变量缓存，供窗口事件回调无锁读取。
+#[derive(Default)]
+pub(crate) struct WindowPreferences {
+    minimize_to_tray: AtomicBool,
+    /// 一次性标记：下一次关闭主窗口时直接退出应用，而不是隐藏到托盘（不持久化）
+    next_close_quits: AtomicBool,
+    tray_click_actions: Mutex<TrayClickActions>,
+}
+
+impl WindowPreferences {
+    /// 从持久化设置加载偏好
+    pub(crate) fn load(app: &AppHandle) -> Self {
+        let minimize_to_tray =
+            settings::load_setting::<bool>(app, SETTING_MINIMIZE_TO_TRAY).unwrap_or(false);
+        let mut tray_click_actions = TrayClickActions::default();
+        for kind in [
+            TrayClickKind::Left,
+            TrayClickKind::Middle,
+            TrayClickKind::DoubleClick,
+        ] {
+            if let Some(action) = settings::load_setting::<TrayClickAction>(app, kind.setting_key())
+            {
+                *tray_click_actions.slot_mut(kind) = action;
+            }
+        }
+        log::debug!(
+            "Window preferences loaded: minimize_to_tray={}, tray_click_actions={:?}",
+            minimize_to_tray,
+            tray_click_actions
+        );
+        Self {
+            minimize_to_tray: AtomicBool::new(minimize_to_tray),
+            next_close_quits: AtomicBool::new(false),
+            tray_click_actions: Mutex::new(tray_click_actions),
+        }
+    }
+
+    pub(crate) fn minimize_to_tray(&self) -> bool {
+        self.minimize_to_tray.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn tray_click_actions(&self) -> TrayClickActions {
+        match self.tray_click_actions.lock() {
+            Ok(actions) => *actions,
+            Err(poisoned) => *poisoned.into_inner(),
+        }
+    }
+
+    /// 读取并清除“下一次关闭即退出”标记
+    pub(crate) fn take_next_close_quits(&self) -> bool {
+        self.next_close_quits.swap(false, Ordering::SeqCst)
+    }
+}
+
+/// 主窗口位置与尺寸（物理像素）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct MainWindowGeometry {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+/// 尚未落盘的最新几何信息
+static PENDING_GEOMETRY: Mutex<Option<MainWindowGeometry>> = Mutex::new(None);
+/// 防抖定时器代数，每次移动/缩放递增以使之前的定时器失效
+static GEOMETRY_SAVE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// 读取主窗口当前几何信息；最小化、最大化或全屏时不记录，保留上一次的普通窗口尺寸
+fn current_main_window_geometry(window: &Window) -> Option<MainWindowGeometry> {
+    if window.is_minimized().unwrap_or(true)
+        || window.is_maximized().unwrap_or(true)
+        || window.is_fullscreen().unwrap_or(true)
+    {
+        return None;
+    }
+    let position = window.outer_position().ok()?;
+    let size = window.inner_size().ok()?;
+    if size.width == 0 || size.height == 0 {
+        return None;
+    }
+    Some(MainWindowGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    })
+}
+
+/// 主窗口移动或缩放时调用：记录最新几何信息，并在停止操作一段时间后写入存储
+pub(crate) fn schedule_main_window_geometry_save(window: &Window) {
+    let Some(geometry) = current_main_window_geometry(window) else {
+        return;
+    };
+    {
+        let mut pending = match PENDING_GEOMETRY.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if pending.as_ref() == Some(&geometry) {
+            return;
+        }
+        *pending = Some(geometry);
+    }
+
+    let generation = GEOMETRY_SAVE_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let app = window.app_handle().clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(GEOMETRY_SAVE_DEBOUNCE).await;
+        if GEOMETRY_SAVE_GENERATION.load(Ordering::SeqCst) == generation {
+            flush_main_window_geometry(&app);
+        }
+    });
+}
+
+/// 立即写入尚未落盘的主窗口几何信息（应用退出前调用）
+pub(crate) fn flush_main_window_geometry(app: &AppHandle) {
+    let geometry = match PENDING_GEOMETRY.lock() {
+        Ok(mut guard) => guard.take(),
+        Err(poisoned) => poisoned.into_inner().take(),
+    };
+    let Some(geometry) = geometry else {
+        return;
+    };
+
+    log::debug!("Saving main window geometry: {:?}", geometry);
+    if let Err(err) = settings::save_setting(app, SETTING_MAIN_WINDOW_GEOMETRY, &geometry) {
+        log::warn!("Failed to save main window geometry: {}", err);
+    }
+}
+
+/// 启动时恢复上次保存的主窗口位置与尺寸；保存的位置已不在任何显示器上时忽略
+pub(crate) fn restore_main_window_geometry(app: &AppHandle) {
+    let Some(geometry) =
+        settings::load_setting::<MainWindowGeometry>(app, SETTING_MAIN_WINDOW_GEOMETRY)
+    else {
+        return;
+    };
+    let Some(window) = app.get_window(MAIN_WINDOW_LABEL) else {
+        return;
+    };
+
+    let title_bar_point = (
+        f64::from(geometry.x) + f64::from(geometry.width) / 2.0,
+        f64::from(geometry.y) + 10.0,
+    );
+    let on_screen = screen::available_monitors(app)
+        .iter()
+        .any(|monitor| monitor.bounds.contains(title_bar_point));
+    if !on_screen {
+        log::info!("Saved main window position is off-screen, keeping default placement");
+        return;
+    }
+
+    log::debug!("Restoring main window geometry: {:?}", geometry);
+    if let Err(err) = window.set_size(PhysicalSize::new(geometry.width, geometry.height)) {
+        log::warn!("Failed to restore main window size: {}", err);
+    }
+    if let Err(err) = window.set_position(PhysicalPosition::new(geometry.x, geometry.y)) {
+        log::warn!("Failed to restore main window position: {}", err);
+    }
+}
+
+/// 面板窗口管理器
+///
+/// 记录额外创建的面板窗口标签以及最近获得焦点的面板（含主窗口）。
+#[derive(Default)]
+pub(crate) struct PanelWindowManager {
+    state: Mutex<PanelWindowState>,
+}
+
+#[derive(Default)]
+struct PanelWindowState {
+    panels: HashSet<String>,
+    last_focused: Option<String>,
+}
+
+impl PanelWindowManager {
+    fn register(&self, label: &str) {
+        if let Ok(mut state) = self.state.lock() {
+            state.panels.insert(label.to_string());
+        }
+    }
+
+    /// 窗口获得焦点时记录（仅主窗口与面板窗口）
+    pub(crate) fn mark_focused(&self, label: &str) {
+        if !is_panel_capable_label(label) {
+            return;
+        }
+        if let Ok(mut state) = self.state.lock() {
+            state.last_focused = Some(label.to_string());
+        }
+    }
+
+    /// 窗口销毁时清理记录
+    pub(crate) fn remove(&self, label: &str) {
+        if let Ok(mut state) = self.state.lock() {
+            state.panels.remove(label);
+            if state.last_focused.as_deref() == Some(label) {
+                state.last_focused = None;
+            }
+        }
+    }
+
+    fn last_focused(&self) -> Option<String> {
+        self.state
+            .lock()
+            .ok()
+            .and_then(|state| state.last_focused.clone())
+    }
+}
+
+fn is_panel_capable_label(label: &str) -> bool {
+    label == MAIN_WINDOW_LABEL || label.starts_with(PANEL_LABEL_PREFIX)
+}
+
+fn panel_window_label(id: &str) -> Result<String, String> {
+    let id = id.trim();
+    if id.is_empty()
+        || !id
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+    {
+        return Err(format!("invalid panel id: {id}"));
+    }
+    Ok(format!("{PANEL_LABEL_PREFIX}{id}"))
+}
+
+/// 面板地址：http/https 视为外部地址，其余视为应用内路由
+fn panel_webview_url(url: &str) -> Result<WebviewUrl, String> {
+    let url = url.trim();
+    if url.starts_with("http://") || url.starts_with("https://") {
+        let parsed = Url::parse(url).map_err(|err| err.to_string())?;
+        return Ok(WebviewUrl::External(parsed));
+    }
+    if url.contains("://") {
+        return Err(format!("unsupported panel url: {url}"));
+    }
+    Ok(WebviewUrl::App(url.into()))
+}
 
 /// 尝试解析主窗口实例
 pub(crate) fn resolve_main_window(app: &tauri::AppHandle) -> Option<Window> {
-    if let Some(window) = app.get_window("main") {
+    if let Some(window) = app.get_window(MAIN_WINDOW_LABEL) {
         log::trace!("resolve_main_window: using explicit 'main' window");
         return Some(window);
     }
@@ -23,6 +459,20 @@ pub(crate) fn resolve_main_window(app: &tauri::AppHandle) -> Option<Window> {
     fallback
 }
 
+/// 解析最近聚焦的面板窗口（含主窗口），没有记录时回退到主窗口
+pub(crate) fn resolve_active_panel_window(app: &AppHandle) -> Option<Window> {
+    let last_focused = app
+        .try_state::<PanelWindowManager>()
+        .and_then(|manager| manager.last_focused());
+
+    if let Some(window) = last_focused.and_then(|label| app.get_window(&label)) {
+        log::trace!("resolve_active_panel_window: using '{}'", window.label());
+        return Some(window);
+    }
+
+    resolve_main_window(app)
+}
+
 /// 隐藏主窗口（向前端广播事件后再隐藏）
 pub(crate) async fn hide_main_window(window: &Window) -> Result<(), String> {
     log::debug!("Hiding main window");
@@ -39,6 +489,32 @@ pub(crate) async fn hide_main_window(window: &Window) -> Result<(), String> {
     Ok(())
 }
 
+/// 主窗口被最小化时按偏好隐藏到托盘
+///
+/// 窗口事件中没有独立的最小化事件，因此在 `Resized` 回调里检查最小化状态。
+pub(crate) fn handle_main_window_resized(window: &Window) {
+    let Some(preferences) = window.try_state::<WindowPreferences>() else {
+        return;
+    };
+    if !preferences.minimize_to_tray() {
+        return;
+    }
+
+    let minimized = window.is_minimized().unwrap_or(false);
+    let visible = window.is_visible().unwrap_or(false);
+    if !minimized || !visible {
+        return;
+    }
+
+    log::debug!("Main window minimized, hiding to tray");
+    let window = window.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(err) = hide_main_window(&window).await {
+            log::error!("Failed to hide minimized window: {}", err);
+        }
+    });
+}
+
 /// 显示主窗口（并恢复焦点与最小化状态）
 pub(crate) async fn show_main_window(window: &Window) -> Result<(), String> {
     show_main_window_internal(window, true).await
@@ -123,6 +599,420 @@ pub(crate) async fn hide_window(window: Window) -> Result<(), String> {
     hide_main_window(&window).await
 }
 
+/// 设置最小化时是否隐藏到托盘
+#[tauri::command]
+pub(crate) async fn set_minimize_to_tray(
+    app: AppHandle,
+    preferences: State<'_, WindowPreferences>,
+    enabled: bool,
+) -> Result<(), String> {
+    log::info!("Setting minimize_to_tray={}", enabled);
+    settings::save_setting(&app, SETTING_MINIMIZE_TO_TRAY, &enabled)?;
+    preferences
+        .minimize_to_tray
+        .store(enabled, Ordering::SeqCst);
+    Ok(())
+}
+
+/// 设置点击托盘图标时执行的动作
+///
+/// `click` 省略时设置左键单击动作。
+#[tauri::command]
+pub(crate) async fn set_tray_click_action(
+    app: AppHandle,
+    preferences: State<'_, WindowPreferences>,
+    action: TrayClickAction,
+    click: Option<TrayClickKind>,
+) -> Result<(), String> {
+    let kind = click.unwrap_or_default();
+    log::info!("Setting tray click action: {:?} -> {:?}", kind, action);
+    settings::save_setting(&app, kind.setting_key(), &action)?;
+    match preferences.tray_click_actions.lock() {
+        Ok(mut actions) => *actions.slot_mut(kind) = action,
+        Err(poisoned) => *poisoned.into_inner().slot_mut(kind) = action,
+    }
+    Ok(())
+}
+
+/// 获取点击托盘图标时执行的动作，`click` 省略时返回左键单击动作
+#[tauri::command]
+pub(crate) async fn get_tray_click_action(
+    preferences: State<'_, WindowPreferences>,
+    click: Option<TrayClickKind>,
+) -> Result<TrayClickAction, String> {
+    Ok(preferences
+        .tray_click_actions()
+        .get(click.unwrap_or_default()))
+}
+
+/// 获取所有点击方式对应的托盘动作
+#[tauri::command]
+pub(crate) async fn get_tray_click_actions(
+    preferences: State<'_, WindowPreferences>,
+) -> Result<TrayClickActions, String> {
+    Ok(preferences.tray_click_actions())
+}
+
+/// 显示主窗口并通知前端打开偏好设置
+pub(crate) fn open_settings_in_main_window(app: &AppHandle) {
+    let Some(window) = resolve_main_window(app) else {
+        log::warn!("Cannot open settings: main window not found");
+        return;
+    };
+    tauri::async_runtime::spawn(async move {
+        if show_main_window_without_restore(&window).await.is_ok() {
+            let _ = window.emit(EVENT_OPEN_SETTINGS, ());
+        }
+    });
+}
+
+/// 左键单击的延迟分发代数，双击到达时递增以取消尚未执行的单击动作
+#[cfg(target_os = "windows")]
+static TRAY_LEFT_CLICK_GENERATION: AtomicU64 = AtomicU64::new(0);
+/// 双击之后紧跟的第二次左键抬起不再视为单击
+#[cfg(target_os = "windows")]
+static TRAY_SUPPRESS_NEXT_LEFT_CLICK: AtomicBool = AtomicBool::new(false);
+
+/// 系统双击间隔
+#[cfg(target_os = "windows")]
+fn system_double_click_interval() -> Duration {
+    use windows::Win32::UI::Input::KeyboardAndMouse::GetDoubleClickTime;
+
+    Duration::from_millis(u64::from(unsafe { GetDoubleClickTime() }))
+}
+
+/// 处理托盘图标左键单击
+///
+/// Windows 上一次双击会先后送达“单击、双击、单击”三个事件：双击动作未设为 `None` 时，
+/// 单击动作延迟一个系统双击间隔执行，期间收到双击则取消，避免双击时先切换两次窗口。
+pub(crate) fn handle_tray_left_click(app: &AppHandle) {
+    #[cfg(target_os = "windows")]
+    {
+        if TRAY_SUPPRESS_NEXT_LEFT_CLICK.swap(false, Ordering::SeqCst) {
+            log::debug!("Tray left click ignored: trailing click of a double-click");
+            return;
+        }
+        let double_click_action = app
+            .try_state::<WindowPreferences>()
+            .map(|preferences| {
+                preferences
+                    .tray_click_actions()
+                    .get(TrayClickKind::DoubleClick)
+            })
+            .unwrap_or_else(|| TrayClickKind::DoubleClick.default_action());
+        if double_click_action != TrayClickAction::None {
+            let generation = TRAY_LEFT_CLICK_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                tokio::time::sleep(system_double_click_interval()).await;
+                if TRAY_LEFT_CLICK_GENERATION.load(Ordering::SeqCst) == generation {
+                    handle_tray_click(&app, TrayClickKind::Left);
+                }
+            });
+            return;
+        }
+    }
+
+    handle_tray_click(app, TrayClickKind::Left);
+}
+
+/// 处理托盘图标左键双击（仅 Windows 上报），取消尚未执行的单击动作
+pub(crate) fn handle_tray_double_click(app: &AppHandle) {
+    #[cfg(target_os = "windows")]
+    {
+        TRAY_LEFT_CLICK_GENERATION.fetch_add(1, Ordering::SeqCst);
+        TRAY_SUPPRESS_NEXT_LEFT_CLICK.store(true, Ordering::SeqCst);
+    }
+
+    handle_tray_click(app, TrayClickKind::DoubleClick);
+}
+
+/// 处理托盘图标点击，按用户为该点击方式设置的动作分发
+pub(crate) fn handle_tray_click(app: &AppHandle, kind: TrayClickKind) {
+    let action = app
+        .try_state::<WindowPreferences>()
+        .map(|preferences| preferences.tray_click_actions().get(kind))
+        .unwrap_or_else(|| kind.default_action());
+    log::debug!("Tray icon clicked: {:?}, action={:?}", kind, action);
+
+    match action {
+        TrayClickAction::ToggleWindow | TrayClickAction::ShowWindow => {
+            let Some(window) = resolve_active_panel_window(app) else {
+                log::warn!("Tray click ignored: no panel window available");
+                return;
+            };
+            tauri::async_runtime::spawn(async move {
+                let result = if action == TrayClickAction::ShowWindow {
+                    show_main_window(&window).await
+                } else {
+                    toggle_main_window_visibility(&window).await
+                };
+                if let Err(err) = result {
+                    log::warn!("Tray click action {:?} failed: {}", action, err);
+                }
+            });
+        }
+        TrayClickAction::TriggerSelection => {
+            let Some(toolbar_state) = app.try_state::<ToolbarManager>() else {
+                log::warn!("Tray click triggered selection but toolbar manager state missing");
+                return;
+            };
+            let toolbar_manager = toolbar_state.inner().clone();
+            global_selection::trigger_toolbar_from_hotkey(app.clone(), toolbar_manager);
+        }
+        TrayClickAction::OpenSettings => open_settings_in_main_window(app),
+        TrayClickAction::None => {}
+    }
+}
+
+/// 主窗口可见性状态
+#[derive(Debug, Serialize)]
+pub(crate) struct MainWindowState {
+    visible: bool,
+    focused: bool,
+    minimized: bool,
+}
+
+/// 查询主窗口是否可见、聚焦或最小化
+#[tauri::command]
+pub(crate) async fn get_main_window_state(app: AppHandle) -> Result<MainWindowState, String> {
+    let window = resolve_main_window(&app).ok_or_else(|| "main window not found".to_string())?;
+    Ok(MainWindowState {
+        visible: window.is_visible().map_err(|err| err.to_string())?,
+        focused: window.is_focused().map_err(|err| err.to_string())?,
+        minimized: window.is_minimized().map_err(|err| err.to_string())?,
+    })
+}
+
+/// 系统主题变化时广播给所有窗口的事件
+const EVENT_SYSTEM_THEME_CHANGED: &str = "system-theme-changed";
+
+/// 系统浅色/深色主题
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum SystemTheme {
+    Light,
+    Dark,
+}
+
+impl From<Theme> for SystemTheme {
+    fn from(theme: Theme) -> Self {
+        match theme {
+            Theme::Dark => Self::Dark,
+            _ => Self::Light,
+        }
+    }
+}
+
+/// 最近一次广播的系统主题，多个窗口同时收到主题变化时只广播一次
+static LAST_SYSTEM_THEME: Mutex<Option<SystemTheme>> = Mutex::new(None);
+
+/// 查询当前系统主题（以主窗口跟随的系统主题为准）
+#[tauri::command]
+pub(crate) async fn get_system_theme(app: AppHandle) -> Result<SystemTheme, String> {
+    let window = resolve_main_window(&app)
+        .or_else(|| {
+            app.webview_windows()
+                .into_values()
+                .next()
+                .map(|w| w.as_ref().window())
+        })
+        .ok_or_else(|| "no window available to query theme".to_string())?;
+    let theme = SystemTheme::from(window.theme().map_err(|err| err.to_string())?);
+    if let Ok(mut last) = LAST_SYSTEM_THEME.lock() {
+        last.get_or_insert(theme);
+    }
+    Ok(theme)
+}
+
+/// 处理窗口的系统主题变化事件，主题确有变化时向所有窗口广播 `system-theme-changed`
+pub(crate) fn handle_system_theme_changed(window: &Window, theme: Theme) {
+    let theme = SystemTheme::from(theme);
+    let changed = match LAST_SYSTEM_THEME.lock() {
+        Ok(mut last) => last.replace(theme) != Some(theme),
+        Err(_) => true,
+    };
+    if !changed {
+        return;
+    }
+
+    log::info!("System theme changed to {:?}", theme);
+    if let Err(err) = window.app_handle().emit(EVENT_SYSTEM_THEME_CHANGED, theme) {
+        log::warn!("Failed to emit system theme change: {}", err);
+    }
+}
+
+/// 设置下一次关闭主窗口时直接退出应用（仅生效一次，不修改持久化偏好）
+#[tauri::command]
+pub(crate) async fn set_next_close_quits(
+    preferences: State<'_, WindowPreferences>,
+    enabled: bool,
+) -> Result<(), String> {
+    log::info!("Setting next_close_quits={}", enabled);
+    preferences
+        .next_close_quits
+        .store(enabled, Ordering::SeqCst);
+    Ok(())
+}
+
+/// 获取最小化时是否隐藏到托盘
+#[tauri::command]
+pub(crate) async fn get_minimize_to_tray(
+    preferences: State<'_, WindowPreferences>,
+) -> Result<bool, String> {
+    Ok(preferences.minimize_to_tray())
+}
+
+/// 请求用户注意（macOS 弹跳 Dock 图标，Windows 闪烁任务栏按钮）
+///
+/// `level` 支持 `critical` 与 `informational`；窗口重新获得焦点时自动清除。
+#[tauri::command]
+pub(crate) async fn request_user_attention(window: Window, level: String) -> Result<(), String> {
+    let attention = match level.trim().to_ascii_lowercase().as_str() {
+        "critical" => UserAttentionType::Critical,
+        "informational" => UserAttentionType::Informational,
+        other => return Err(format!("Unsupported attention level: {other}")),
+    };
+
+    if window.is_focused().unwrap_or(false) {
+        log::debug!("Window already focused, skip user attention request");
+        return Ok(());
+    }
+
+    log::debug!("Requesting user attention: level={}", level);
+    window
+        .request_user_attention(Some(attention))
+        .map_err(|err| {
+            log::error!("Failed to request user attention: {}", err);
+            err.to_string()
+        })
+}
+
+/// 窗口获得焦点时清除用户注意请求
+pub(crate) fn clear_user_attention(window: &Window) {
+    if let Err(err) = window.request_user_attention(None) {
+        log::trace!("Failed to clear user attention: {}", err);
+    }
+}
+
+/// 启动时应用已保存的主窗口装饰设置（未保存时沿用配置文件中的默认值）
+pub(crate) fn apply_saved_main_window_decorations(app: &AppHandle) {
+    let Some(enabled) = settings::load_setting::<bool>(app, SETTING_MAIN_WINDOW_DECORATIONS) else {
+        return;
+    };
+    let Some(window) = resolve_main_window(app) else {
+        return;
+    };
+
+    log::debug!("Applying saved main window decorations: {}", enabled);
+    if let Err(err) = window.set_decorations(enabled) {
+        log::warn!("Failed to apply saved window decorations: {}", err);
+    }
+}
+
+/// 运行时切换主窗口的原生装饰（无边框模式）
+///
+/// 关闭装饰后窗口失去原生标题栏，前端收到事件后需设置自定义拖拽区域。
+#[tauri::command]
+pub(crate) async fn set_main_window_decorations(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    let window = resolve_main_window(&app).ok_or_else(|| "Main window not found".to_string())?;
+
+    log::info!("Setting main window decorations: {}", enabled);
+    window.set_decorations(enabled).map_err(|err| {
+        log::error!("Failed to toggle window decorations: {}", err);
+        format!("Toggling window decorations is not supported on this platform: {err}")
+    })?;
+
+    settings::save_setting(&app, SETTING_MAIN_WINDOW_DECORATIONS, &enabled)?;
+
+    let _ = window.emit(
+        EVENT_DECORATIONS_CHANGED,
+        serde_json::json!({ "decorations": enabled }),
+    );
+    Ok(())
+}
+
+/// 主窗口贴靠位置
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub(crate) enum SnapPosition {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    Fill,
+}
+
+/// 在工作区内计算贴靠目标矩形（物理像素），返回 `(x, y, width, height)`
+fn snap_target_rect(
+    position: SnapPosition,
+    area_position: PhysicalPosition<i32>,
+    area_size: PhysicalSize<u32>,
+) -> (i32, i32, u32, u32) {
+    let half_width = area_size.width / 2;
+    let half_height = area_size.height / 2;
+    let right_x = area_position.x + half_width as i32;
+    let bottom_y = area_position.y + half_height as i32;
+    let (x, y) = (area_position.x, area_position.y);
+
+    match position {
+        SnapPosition::Left => (x, y, half_width, area_size.height),
+        SnapPosition::Right => (right_x, y, area_size.width - half_width, area_size.height),
+        SnapPosition::Top => (x, y, area_size.width, half_height),
+        SnapPosition::Bottom => (x, bottom_y, area_size.width, area_size.height - half_height),
+        SnapPosition::TopLeft => (x, y, half_width, half_height),
+        SnapPosition::TopRight => (right_x, y, area_size.width - half_width, half_height),
+        SnapPosition::BottomLeft => (x, bottom_y, half_width, area_size.height - half_height),
+        SnapPosition::BottomRight => (
+            right_x,
+            bottom_y,
+            area_size.width - half_width,
+            area_size.height - half_height,
+        ),
+        SnapPosition::Fill => (x, y, area_size.width, area_size.height),
+    }
+}
+
+/// 将主窗口贴靠到所在显示器工作区（不含任务栏/Dock）的指定区域
+#[tauri::command]
+pub(crate) async fn snap_main_window(app: AppHandle, position: SnapPosition) -> Result<(), String> {
+    let window = resolve_main_window(&app).ok_or_else(|| "Main window not found".to_string())?;
+
+    let monitor = window
+        .current_monitor()
+        .map_err(|err| err.to_string())?
+        .or_else(|| window.primary_monitor().ok().flatten())
+        .ok_or_else(|| "No monitor available for snapping".to_string())?;
+    let work_area = monitor.work_area();
+
+    let (x, y, width, height) = snap_target_rect(position, work_area.position, work_area.size);
+    log::debug!(
+        "Snapping main window: position={:?}, rect=({}, {}, {}x{})",
+        position,
+        x,
+        y,
+        width,
+        height
+    );
+
+    if window.is_maximized().unwrap_or(false) {
+        window.unmaximize().map_err(|err| err.to_string())?;
+    }
+    window
+        .set_size(PhysicalSize::new(width, height))
+        .map_err(|err| err.to_string())?;
+    window
+        .set_position(PhysicalPosition::new(x, y))
+        .map_err(|err| err.to_string())?;
+    Ok(())
+}
+
 /// 在主窗口中打开指定平台
 ///
 /// 此命令会：
@@ -166,3 +1056,146 @@ pub(crate) async fn open_platform_in_main_window(
 
     Ok(())
 }
+
+/// 创建额外的面板窗口；若已存在则直接显示并聚焦
+#[tauri::command]
+pub(crate) async fn create_panel_window(
+    app: AppHandle,
+    manager: State<'_, PanelWindowManager>,
+    id: String,
+    url: String,
+) -> Result<(), String> {
+    let label = panel_window_label(&id)?;
+
+    if let Some(window) = app.get_window(&label) {
+        log::debug!("Panel window already exists, focusing: {}", label);
+        return show_main_window(&window).await;
+    }
+
+    log::info!("Creating panel window: label={}, url={}", label, url);
+    let window = WebviewWindowBuilder::new(&app, &label, panel_webview_url(&url)?)
+        .title("AI Ask")
+        .inner_size(PANEL_DEFAULT_WIDTH, PANEL_DEFAULT_HEIGHT)
+        .min_inner_size(PANEL_MIN_WIDTH, PANEL_MIN_HEIGHT)
+        .decorations(false)
+        .resizable(true)
+        .visible(true)
+        .focused(true)
+        .build()
+        .map_err(|err| format!("Failed to create panel window: {err}"))?;
+
+    manager.register(&label);
+    manager.mark_focused(window.label());
+    Ok(())
+}
+
+/// 聚焦指定面板窗口
+#[tauri::command]
+pub(crate) async fn focus_panel(app: AppHandle, id: String) -> Result<(), String> {
+    let label = panel_window_label(&id)?;
+    let window = app
+        .get_window(&label)
+        .ok_or_else(|| format!("panel window not found: {id}"))?;
+    show_main_window(&window).await
+}
+
+/// 关闭指定面板窗口
+#[tauri::command]
+pub(crate) async fn close_panel(
+    app: AppHandle,
+    manager: State<'_, PanelWindowManager>,
+    id: String,
+) -> Result<(), String> {
+    let label = panel_window_label(&id)?;
+    if let Some(window) = app.get_window(&label) {
+        log::info!("Closing panel window: {}", label);
+        window.destroy().map_err(|err| err.to_string())?;
+    }
+    manager.remove(&label);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{snap_target_rect, SnapPosition, SystemTheme};
+    use tauri::{PhysicalPosition, PhysicalSize};
+
+    fn snap(
+        position: SnapPosition,
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+    ) -> (i32, i32, u32, u32) {
+        snap_target_rect(
+            position,
+            PhysicalPosition::new(x, y),
+            PhysicalSize::new(width, height),
+        )
+    }
+
+    #[test]
+    fn snaps_each_edge_within_work_area() {
+        let cases = [
+            (SnapPosition::Left, (0, 40, 960, 1000)),
+            (SnapPosition::Right, (960, 40, 960, 1000)),
+            (SnapPosition::Top, (0, 40, 1920, 500)),
+            (SnapPosition::Bottom, (0, 540, 1920, 500)),
+            (SnapPosition::TopLeft, (0, 40, 960, 500)),
+            (SnapPosition::TopRight, (960, 40, 960, 500)),
+            (SnapPosition::BottomLeft, (0, 540, 960, 500)),
+            (SnapPosition::BottomRight, (960, 540, 960, 500)),
+            (SnapPosition::Fill, (0, 40, 1920, 1000)),
+        ];
+        for (position, expected) in cases {
+            assert_eq!(snap(position, 0, 40, 1920, 1000), expected, "{position:?}");
+        }
+    }
+
+    #[test]
+    fn snaps_relative_to_secondary_monitor_offset() {
+        assert_eq!(
+            snap(SnapPosition::Right, 1920, 0, 2560, 1400),
+            (3200, 0, 1280, 1400)
+        );
+        assert_eq!(
+            snap(SnapPosition::BottomLeft, -1280, -200, 1280, 1024),
+            (-1280, 312, 640, 512)
+        );
+    }
+
+    #[test]
+    fn odd_sizes_give_remainder_to_right_and_bottom_halves() {
+        let (left_x, _, left_width, _) = snap(SnapPosition::Left, 0, 0, 1365, 767);
+        let (right_x, _, right_width, _) = snap(SnapPosition::Right, 0, 0, 1365, 767);
+        assert_eq!((left_x, left_width), (0, 682));
+        assert_eq!((right_x, right_width), (682, 683));
+
+        let (_, top_y, _, top_height) = snap(SnapPosition::Top, 0, 0, 1365, 767);
+        let (_, bottom_y, _, bottom_height) = snap(SnapPosition::Bottom, 0, 0, 1365, 767);
+        assert_eq!((top_y, top_height), (0, 383));
+        assert_eq!((bottom_y, bottom_height), (383, 384));
+    }
+
+    #[test]
+    fn minimum_work_area_does_not_underflow() {
+        assert_eq!(snap(SnapPosition::Left, 10, 20, 1, 1), (10, 20, 0, 1));
+        assert_eq!(
+            snap(SnapPosition::BottomRight, 10, 20, 1, 1),
+            (10, 20, 1, 1)
+        );
+        assert_eq!(snap(SnapPosition::Fill, 10, 20, 0, 0), (10, 20, 0, 0));
+    }
+
+    #[test]
+    fn system_theme_serializes_lowercase() {
+        assert_eq!(
+            serde_json::to_string(&SystemTheme::Dark).unwrap(),
+            "\"dark\""
+        );
+        assert_eq!(
+            serde_json::to_string(&SystemTheme::Light).unwrap(),
+            "\"light\""
+        );
+    }
+}
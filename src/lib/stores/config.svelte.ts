@@ -36,6 +36,7 @@ class ConfigStore {
 
       await this.refreshSelectionToolbarTemporaryDisableIfExpired()
       await this.syncSelectionToolbarPolicies()
+      await this.syncActiveProxy()
 
       // 同步自启动状态
       await this.syncAutoLaunchStatus()
@@ -98,6 +99,18 @@ class ConfigStore {
     }
   }
 
+  /**
+   * 同步代理配置到后端，更新检查、下载与代理测试统一使用该代理
+   */
+  async syncActiveProxy() {
+    try {
+      await invoke('set_active_proxy', { config: this.config.proxy ?? null })
+    }
+ catch (error) {
+      logger.error('Failed to sync active proxy', error)
+    }
+  }
+
   async syncSelectionToolbarPolicies() {
     try {
       await invoke('set_selection_toolbar_enabled', {
@@ -183,6 +196,10 @@ class ConfigStore {
       if (updates.theme !== undefined) {
         this.applyTheme()
       }
+
+      if (updates.proxy !== undefined) {
+        await this.syncActiveProxy()
+      }
     }
  catch (error) {
       logger.error('Failed to update config', error)
@@ -384,6 +401,7 @@ class ConfigStore {
       this.config = DEFAULT_CONFIG
       this.applyTheme()
       await this.syncSelectionToolbarPolicies()
+      await this.syncActiveProxy()
     }
  catch (error) {
       logger.error('Failed to reset config', error)
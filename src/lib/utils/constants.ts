@@ -339,6 +339,8 @@ export const EVENTS = {
   CHILD_WEBVIEW_READY: 'child-webview:ready',
   /** 注入结果（通过特殊导航传回） */
   CHILD_WEBVIEW_INJECTION_RESULT: 'child-webview:injection-result',
+  /** 注入结果聚合进行中时新一轮注入被拒绝（进行中的注入不受影响） */
+  CHILD_WEBVIEW_INJECTION_REJECTED: 'child-webview:injection-rejected',
   /** 划词工具栏临时禁用状态更新事件 */
   SELECTION_TOOLBAR_TEMP_DISABLE_CHANGED: 'selection-toolbar:temporary-disable-changed',
   /** 请求主窗口打开便签事件 */